@@ -1,8 +1,21 @@
+use haste_core::deploy::config::{AgentConfig, AGENT_CONFIG_FILE};
+use haste_core::deploy::service::serve;
 use haste_core::run;
 
+use std::env;
+
 pub mod systemd;
 
 fn main() {
+    env_logger::init();
     run();
-    println!("Hello, world!");
+
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| AGENT_CONFIG_FILE.to_string());
+    let config = AgentConfig::load(&path).expect("fail to load agent config");
+    if let Err(err) = serve(config) {
+        eprintln!("agent exit due {}", err);
+        std::process::exit(1);
+    }
 }
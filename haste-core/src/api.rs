@@ -0,0 +1,1009 @@
+//! http/json api of leader.
+//!
+//!   POST   /deploys[?dry_run=true]                  deploy a cluster by the json of DeployParm
+//!   GET    /clusters                                list clusters
+//!   GET    /clusters/{name}[?stats=true]            inspect the cluster and it's instances
+//!   GET    /clusters/{name}/audit                   the jobs of cluster
+//!   POST   /clusters/{name}/actions                 {"action": "restart", "hosts": [...]}
+//!                                                   or with "instances": ["{ip}:{port}", ...]
+//!   DELETE /clusters/{name}/instances/{ip}:{port}[?force=true]
+//!   POST   /clusters/{name}/upgrade                 {"version": "5.0.3"}, rolling upgrade
+//!   POST   /clusters/{name}/config[?dry_run=true]   {"template": "..."}, rolling config change
+//!                                                   by the template, the current one if not set
+//!   GET    /agents                                  list agents with their capacity
+//!   GET    /templates                               list templates
+//!   PUT    /templates/{cache_type}/{name}           {"files": {"redis.conf": "...", ...}}
+//!   GET    /specs                                   list specs
+//!   GET    /specs/{name}                            the spec and it's reconcile status
+//!   PUT    /specs/{name}                            save the json of ClusterSpec as next revision
+//!   DELETE /specs/{name}                            stop reconciling, the cluster is kept
+//!   GET    /jobs                                    list jobs
+//!   GET    /jobs/{job_id}                           the status of job
+//!
+//! deploys, actions and removes run as jobs, they reply `202 Accepted` with the job at once.
+//! the jobs lock their cluster, so it's a `409 Conflict` if another job is running on it.
+//! errors are replied as
+//!
+//! ```json
+//! {"error": {"code": "bad_request", "message": "...", "details": ["..."]}}
+//! ```
+
+use crate::deploy::plan::{cache_type_name, parse_cache_type};
+use crate::deploy::server::{
+    self, send_action, template_files, DeployParm, InvalidParm, Template, STATE_ONLINE,
+    STATE_STOPPED,
+};
+use crate::job::{
+    audit_log, check_job_id, gen_job_id, list_jobs, spawn_deploy, spawn_job, ClusterBusy,
+    ClusterLock, DeployConflict, DeployStart, Job,
+};
+use crate::ledger::{self, Ledger};
+use crate::myetcd::MyEtcd;
+use crate::offer::{get_capacity, Offer};
+use crate::proto::{Action, CacheType, Instance, SystemdAction};
+use crate::reconfig::{plan_reconfig, spawn_reconfig, ReconfigError};
+use crate::spec::{
+    delete_spec, list_specs, load_spec, load_status, save_spec, ClusterSpec, RevisionConflict,
+    SpecStatus,
+};
+use crate::stats::{cluster_instances, scrape_cluster, ClusterStats, LOW_OPS};
+use crate::upgrade::{spawn_upgrade, UpgradeError};
+
+use failure::{format_err, Error};
+use futures::sync::oneshot;
+use futures::{Future, Stream};
+use hyper::service::service_fn;
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use log::{error, info, warn};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_derive::{Deserialize, Serialize};
+
+use tera::Tera;
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::thread;
+
+#[derive(Debug, Serialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: String) -> ApiError {
+        ApiError {
+            status,
+            code,
+            message,
+            details: Vec::new(),
+        }
+    }
+
+    pub fn bad_request<S: Into<String>>(message: S) -> ApiError {
+        ApiError::new(StatusCode::BAD_REQUEST, "bad_request", message.into())
+    }
+
+    pub fn not_found<S: Into<String>>(message: S) -> ApiError {
+        ApiError::new(StatusCode::NOT_FOUND, "not_found", message.into())
+    }
+
+    pub fn conflict<S: Into<String>>(message: S) -> ApiError {
+        ApiError::new(StatusCode::CONFLICT, "conflict", message.into())
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(err: Error) -> ApiError {
+        // another job is running on the cluster
+        if let Some(busy) = err.downcast_ref::<ClusterBusy>() {
+            return ApiError::conflict(format!("{}", busy));
+        }
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal",
+            format!("{}", err),
+        )
+    }
+}
+
+type ApiResult = Result<Response<Body>, ApiError>;
+
+#[derive(Serialize)]
+struct ErrorReply<'a> {
+    error: &'a ApiError,
+}
+
+#[derive(Deserialize)]
+struct ActionRequest {
+    action: String,
+    // all the hosts of cluster if both are empty
+    #[serde(default)]
+    hosts: Vec<String>,
+    // instances as `{ip}:{port}`, which take precedence over hosts
+    #[serde(default)]
+    instances: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct UpgradeRequest {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct ReconfigRequest {
+    #[serde(default)]
+    template: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TemplateRequest {
+    // file name -> content
+    files: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct TemplateSummary {
+    cache_type: String,
+    name: String,
+    files: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct AgentSummary {
+    host: String,
+    addr: String,
+    // the capacity registered by agent
+    capacity: Option<Offer>,
+    // the capacity minus the reservations
+    available: Option<Offer>,
+}
+
+#[derive(Serialize)]
+struct SpecDetail {
+    spec: ClusterSpec,
+    // none until it's reconciled
+    status: Option<SpecStatus>,
+}
+
+#[derive(Serialize)]
+struct ClusterSummary {
+    name: String,
+    cache_type: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+struct InstanceDetail {
+    addr: String,
+    role: String,
+    slaveof: String,
+    slots: String,
+    state: String,
+}
+
+#[derive(Serialize)]
+struct ClusterDetail {
+    name: String,
+    cache_type: String,
+    version: String,
+    template: String,
+    appids: Vec<String>,
+    config: BTreeMap<String, String>,
+    instances: Vec<InstanceDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<ClusterStats>,
+}
+
+/// serve the api on addr as `{ip}:{port}` in background. each request is handled in it's
+/// own thread since etcd and grpc calls are blocking.
+pub fn spawn_api(addr: &str, etcd: &str) -> Result<(), Error> {
+    let addr: SocketAddr = addr
+        .parse()
+        .map_err(|err| format_err!("bad api addr {} due {}", addr, err))?;
+    let etcd = etcd.to_string();
+    let server = Server::try_bind(&addr)?
+        .serve(move || {
+            let etcd = etcd.clone();
+            service_fn(move |req| handle(etcd.clone(), req))
+        })
+        .map_err(|err| error!("api server exit due {}", err));
+    info!("serve api on http://{}", addr);
+    thread::spawn(move || hyper::rt::run(server));
+    Ok(())
+}
+
+fn handle(
+    etcd: String,
+    req: Request<Body>,
+) -> Box<dyn Future<Item = Response<Body>, Error = hyper::Error> + Send> {
+    let (parts, body) = req.into_parts();
+    let work = body.concat2().and_then(move |body| {
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            let query = parse_query(parts.uri.query().unwrap_or(""));
+            let rslt = route(&etcd, &parts.method, parts.uri.path(), &query, &body);
+            let reply = rslt.unwrap_or_else(|err| {
+                warn!("{} {} fail due {}", parts.method, parts.uri, err.message);
+                reply_json(err.status, &ErrorReply { error: &err })
+            });
+            let _ = tx.send(reply);
+        });
+        rx.or_else(|_| {
+            let err = ApiError::from(format_err!("the handler was gone"));
+            Ok(reply_json(err.status, &ErrorReply { error: &err }))
+        })
+    });
+    Box::new(work)
+}
+
+fn route(
+    etcd: &str,
+    method: &Method,
+    path: &str,
+    query: &HashMap<String, String>,
+    body: &[u8],
+) -> ApiResult {
+    let segs: Vec<_> = path.split('/').filter(|x| !x.is_empty()).collect();
+    match (method, &segs[..]) {
+        (&Method::POST, ["deploys"]) => deploy(etcd, query, body),
+        (&Method::GET, ["clusters"]) => list_clusters(etcd),
+        (&Method::GET, ["clusters", name]) => show_cluster(etcd, name, query),
+        (&Method::GET, ["clusters", name, "audit"]) => {
+            check_name("cluster", name)?;
+            reply_ok(&audit_log(&MyEtcd::open(etcd)?, name)?)
+        }
+        (&Method::POST, ["clusters", name, "actions"]) => cluster_action(etcd, name, body),
+        (&Method::DELETE, ["clusters", name, "instances", addr]) => {
+            remove_instance(etcd, name, addr, query)
+        }
+        (&Method::POST, ["clusters", name, "upgrade"]) => upgrade(etcd, name, body),
+        (&Method::POST, ["clusters", name, "config"]) => reconfig(etcd, name, query, body),
+        (&Method::GET, ["agents"]) => list_agents(etcd),
+        (&Method::GET, ["templates"]) => list_templates(etcd),
+        (&Method::PUT, ["templates", cache_type, name]) => {
+            push_template(etcd, cache_type, name, body)
+        }
+        (&Method::GET, ["specs"]) => reply_ok(&list_specs(&MyEtcd::open(etcd)?)?),
+        (&Method::GET, ["specs", name]) => show_spec(etcd, name),
+        (&Method::PUT, ["specs", name]) => put_spec(etcd, name, body),
+        (&Method::DELETE, ["specs", name]) => {
+            check_name("spec", name)?;
+            let myetcd = MyEtcd::open(etcd)?;
+            if load_spec(&myetcd, name)?.is_none() {
+                return Err(ApiError::not_found(format!("spec {} is not found", name)));
+            }
+            delete_spec(&myetcd, name)?;
+            reply_ok(&name)
+        }
+        (&Method::GET, ["jobs"]) => reply_ok(&list_jobs(&MyEtcd::open(etcd)?)?),
+        (&Method::GET, ["jobs", id]) => {
+            check_job_id(id).map_err(ApiError::bad_request)?;
+            let myetcd = MyEtcd::open(etcd)?;
+            match Job::load(&myetcd, id)? {
+                Some(job) => reply_ok(&job),
+                None => Err(ApiError::not_found(format!("job {} is not found", id))),
+            }
+        }
+        _ => Err(ApiError::not_found(format!(
+            "no api of {} {}",
+            method, path
+        ))),
+    }
+}
+
+fn deploy(etcd: &str, query: &HashMap<String, String>, body: &[u8]) -> ApiResult {
+    let param: DeployParm = parse_json(body)?;
+    match spawn_deploy(etcd, &param, is_true(query, "dry_run")) {
+        Ok(DeployStart::Planned(plan)) => reply_ok(&plan),
+        Ok(DeployStart::Started(job)) => Ok(reply_json(StatusCode::ACCEPTED, &job)),
+        Err(err) => match err.downcast::<DeployConflict>() {
+            Ok(conflict) => Err(ApiError::conflict(format!("{}", conflict))),
+            Err(err) => Err(invalid_or_internal(err)),
+        },
+    }
+}
+
+fn list_clusters(etcd: &str) -> ApiResult {
+    let myetcd = MyEtcd::open(etcd)?;
+    let mut clusters = Vec::new();
+    for key in myetcd.list_keys("/haste/clusters")? {
+        let name = key.rsplit('/').next().unwrap_or("").to_string();
+        // the cluster being deployed for the first time has no cache type
+        let cache_type = match myetcd.get_value(&format!("{}/cache_type", key))? {
+            Some(cache_type) => cache_type,
+            None => continue,
+        };
+        let version = myetcd
+            .get_value(&format!("{}/version", key))?
+            .unwrap_or_default();
+        clusters.push(ClusterSummary {
+            name,
+            cache_type,
+            version,
+        });
+    }
+    reply_ok(&clusters)
+}
+
+fn show_cluster(etcd: &str, name: &str, query: &HashMap<String, String>) -> ApiResult {
+    check_name("cluster", name)?;
+    let myetcd = MyEtcd::open(etcd)?;
+    let base = format!("/haste/clusters/{}", name);
+    let cache_type = myetcd
+        .get_value(&format!("{}/cache_type", base))?
+        .ok_or_else(|| ApiError::not_found(format!("cluster {} is not found", name)))?;
+    let get = |key: &str| -> Result<String, Error> {
+        Ok(myetcd
+            .get_value(&format!("{}/{}", base, key))?
+            .unwrap_or_default())
+    };
+
+    let mut instances = Vec::new();
+    for addr in cluster_instances(&myetcd, name)? {
+        let fields: HashMap<_, _> = myetcd
+            .list(&format!("{}/instances/{}", base, addr))?
+            .into_iter()
+            .filter_map(|(key, value)| key.rsplit('/').next().map(|x| (x.to_string(), value)))
+            .collect();
+        let field = |key: &str| fields.get(key).cloned().unwrap_or_default();
+        instances.push(InstanceDetail {
+            role: field("role"),
+            slaveof: field("slaveof"),
+            slots: field("slots"),
+            state: field("state"),
+            addr,
+        });
+    }
+
+    let stats = if is_true(query, "stats") && cache_type != cache_type_name(CacheType::Memcache) {
+        Some(scrape_cluster(&myetcd, name)?)
+    } else {
+        None
+    };
+
+    let detail = ClusterDetail {
+        name: name.to_string(),
+        version: get("version")?,
+        template: get("template")?,
+        appids: myetcd
+            .list_keys(&format!("{}/appids", base))?
+            .into_iter()
+            .filter_map(|key| key.rsplit('/').next().map(|x| x.to_string()))
+            .collect(),
+        config: myetcd
+            .list(&format!("{}/config", base))?
+            .into_iter()
+            .filter_map(|(key, value)| key.rsplit('/').next().map(|x| (x.to_string(), value)))
+            .collect(),
+        cache_type,
+        instances,
+        stats,
+    };
+    reply_ok(&detail)
+}
+
+// run the systemd action on the hosts of cluster one by one.
+fn cluster_action(etcd: &str, name: &str, body: &[u8]) -> ApiResult {
+    check_name("cluster", name)?;
+    let req: ActionRequest = parse_json(body)?;
+    let act = match req.action.as_str() {
+        "restart" => SystemdAction::Restart,
+        "start" => SystemdAction::Start,
+        "stop" => SystemdAction::Stop,
+        _ => {
+            return Err(ApiError::bad_request(format!(
+                "bad action {}, must be one of restart, start and stop",
+                req.action
+            )))
+        }
+    };
+
+    let myetcd = MyEtcd::open(etcd)?;
+    let hosts = cluster_hosts(&myetcd, name)?;
+    if hosts.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "cluster {} is not found",
+            name
+        )));
+    }
+
+    // the ports of each target host, all the ports of cluster if it's empty
+    let mut targets: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    let mut unknown = Vec::new();
+    if !req.instances.is_empty() {
+        for addr in &req.instances {
+            let (host, port) = parse_addr(addr)?;
+            match hosts.get(&host) {
+                Some(ports) if ports.contains(&port) => targets.entry(host).or_default().push(port),
+                _ => unknown.push(addr.clone()),
+            }
+        }
+    } else if !req.hosts.is_empty() {
+        for host in &req.hosts {
+            if hosts.contains_key(host) {
+                targets.insert(host.clone(), Vec::new());
+            } else {
+                unknown.push(host.clone());
+            }
+        }
+    } else {
+        targets = hosts.keys().map(|x| (x.clone(), Vec::new())).collect();
+    }
+    if !unknown.is_empty() {
+        let mut err = ApiError::bad_request(format!("targets are not in cluster {}", name));
+        err.details = unknown;
+        return Err(err);
+    }
+
+    // the stopped instances are marked, so the reconciler never restores them
+    let state = if act == SystemdAction::Stop {
+        STATE_STOPPED
+    } else {
+        STATE_ONLINE
+    };
+    let state_keys: BTreeMap<_, Vec<_>> = targets
+        .iter()
+        .map(|(host, ports)| {
+            let ports = if ports.is_empty() {
+                &hosts[host]
+            } else {
+                ports
+            };
+            let keys = ports
+                .iter()
+                .map(|port| format!("/haste/clusters/{}/instances/{}:{}/state", name, host, port))
+                .collect();
+            (host.clone(), keys)
+        })
+        .collect();
+
+    let actions: Vec<_> = targets
+        .into_iter()
+        .map(|(host, ports)| {
+            let mut action = Action::new();
+            action.set_action(act);
+            if ports.is_empty() {
+                action.set_cluster(name.to_string());
+            }
+            let insts: Vec<_> = ports
+                .into_iter()
+                .map(|port| {
+                    let mut inst = Instance::new();
+                    inst.set_port(port);
+                    inst
+                })
+                .collect();
+            action.set_insts(insts.into());
+            (host, action)
+        })
+        .collect();
+
+    let job = Job::new(&gen_job_id(name), &req.action, name);
+    // never change the instances under a rolling upgrade or reconfig
+    let lock = ClusterLock::acquire(&myetcd, etcd, name, &req.action)?;
+    let addr = etcd.to_string();
+    let job = spawn_job(&myetcd, etcd, job, move || {
+        let _lock = lock;
+        let myetcd = MyEtcd::open(&addr)?;
+        for (host, action) in &actions {
+            send_action(&myetcd, host, action)?;
+            for key in &state_keys[host] {
+                myetcd.set(key, state)?;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(reply_json(StatusCode::ACCEPTED, &job))
+}
+
+// the instance must be in low ops unless forced, it's stopped and removed by agent, then
+// it's reservation and meta data are removed.
+fn remove_instance(
+    etcd: &str,
+    name: &str,
+    addr: &str,
+    query: &HashMap<String, String>,
+) -> ApiResult {
+    check_name("cluster", name)?;
+    let (host, port) = parse_addr(addr)?;
+    let myetcd = MyEtcd::open(etcd)?;
+    let base = format!("/haste/clusters/{}", name);
+    let cache_type = match myetcd.get_value(&format!("{}/cache_type", base))? {
+        Some(cache_type) => parse_cache_type(&cache_type)?,
+        None => {
+            return Err(ApiError::not_found(format!(
+                "cluster {} is not found",
+                name
+            )))
+        }
+    };
+    if !cluster_instances(&myetcd, name)?.iter().any(|x| x == addr) {
+        return Err(ApiError::not_found(format!(
+            "instance {} is not in cluster {}",
+            addr, name
+        )));
+    }
+
+    // memcache has no ops to check
+    if !is_true(query, "force") && cache_type != CacheType::Memcache {
+        let stats = scrape_cluster(&myetcd, name)?;
+        if let Err(err) = stats.check_low_ops(&[addr.to_string()], LOW_OPS) {
+            return Err(ApiError::conflict(format!("{}", err)));
+        }
+    }
+
+    let mut action = Action::new();
+    action.set_action(SystemdAction::Remove);
+    let mut inst = Instance::new();
+    inst.set_port(port);
+    action.set_insts(vec![inst].into());
+    let job = Job::new(&gen_job_id(name), "remove", name);
+    let lock = ClusterLock::acquire(&myetcd, etcd, name, "remove")?;
+    let etcd_addr = etcd.to_string();
+    let inst_dir = format!("{}/instances/{}", base, addr);
+    let job = spawn_job(&myetcd, etcd, job, move || {
+        let _lock = lock;
+        let myetcd = MyEtcd::open(&etcd_addr)?;
+        send_action(&myetcd, &host, &action)?;
+        ledger::release(&myetcd, &host, port as usize)?;
+        myetcd.delete_dir(&inst_dir)
+    })?;
+    Ok(reply_json(StatusCode::ACCEPTED, &job))
+}
+
+fn list_agents(etcd: &str) -> ApiResult {
+    let myetcd = MyEtcd::open(etcd)?;
+    let mut agents = Vec::new();
+    for (key, addr) in myetcd.list("/haste/agent")? {
+        let host = key.rsplit('/').next().unwrap_or("").to_string();
+        let capacity = get_capacity(&myetcd, &host)?;
+        let available = match capacity.as_ref() {
+            Some(offer) => Some(Ledger::load(&myetcd, &host)?.0.available(offer)),
+            None => None,
+        };
+        agents.push(AgentSummary {
+            host,
+            addr,
+            capacity,
+            available,
+        });
+    }
+    reply_ok(&agents)
+}
+
+fn upgrade(etcd: &str, name: &str, body: &[u8]) -> ApiResult {
+    check_name("cluster", name)?;
+    let req: UpgradeRequest = parse_json(body)?;
+    let err = match spawn_upgrade(etcd, name, &req.version) {
+        Ok(job) => return Ok(reply_json(StatusCode::ACCEPTED, &job)),
+        Err(err) => err,
+    };
+    match err.downcast::<UpgradeError>() {
+        Ok(UpgradeError::NotFound(name)) => Err(ApiError::not_found(format!(
+            "cluster {} is not found",
+            name
+        ))),
+        Ok(err @ UpgradeError::Unsupported(..)) => Err(ApiError::bad_request(format!("{}", err))),
+        Err(err) => Err(invalid_or_internal(err)),
+    }
+}
+
+fn reconfig(etcd: &str, name: &str, query: &HashMap<String, String>, body: &[u8]) -> ApiResult {
+    check_name("cluster", name)?;
+    let req: ReconfigRequest = parse_json(body)?;
+    let tpl_name = req.template.as_deref();
+    if let Some(tpl_name) = tpl_name {
+        check_name("template", tpl_name)?;
+    }
+
+    let rslt = if is_true(query, "dry_run") {
+        plan_reconfig(etcd, name, tpl_name).map(|plan| reply_json(StatusCode::OK, &plan))
+    } else {
+        spawn_reconfig(etcd, name, tpl_name).map(|job| reply_json(StatusCode::ACCEPTED, &job))
+    };
+    let err = match rslt {
+        Ok(reply) => return Ok(reply),
+        Err(err) => err,
+    };
+    match err.downcast::<ReconfigError>() {
+        Ok(err @ ReconfigError::NotFound(_)) => Err(ApiError::not_found(format!("{}", err))),
+        Ok(err) => Err(ApiError::bad_request(format!("{}", err))),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn list_templates(etcd: &str) -> ApiResult {
+    let myetcd = MyEtcd::open(etcd)?;
+    let mut templates = Vec::new();
+    for cache_type in &[
+        CacheType::Redis,
+        CacheType::RedisCluster,
+        CacheType::Memcache,
+    ] {
+        let cache_type = cache_type_name(*cache_type);
+        for dir in myetcd.list_keys(&format!("/haste/templates/{}", cache_type))? {
+            templates.push(TemplateSummary {
+                cache_type: cache_type.to_string(),
+                name: base_name(&dir),
+                files: myetcd
+                    .list_keys(&dir)?
+                    .iter()
+                    .map(|x| base_name(x))
+                    .collect(),
+            });
+        }
+    }
+    reply_ok(&templates)
+}
+
+fn push_template(etcd: &str, cache_type: &str, name: &str, body: &[u8]) -> ApiResult {
+    let cache_type =
+        parse_cache_type(cache_type).map_err(|err| ApiError::bad_request(format!("{}", err)))?;
+    check_name("template", name)?;
+    let req: TemplateRequest = parse_json(body)?;
+
+    let problems = template_problems(cache_type, &req.files);
+    if !problems.is_empty() {
+        let mut err = ApiError::bad_request(format!("bad template {}", name));
+        err.details = problems;
+        return Err(err);
+    }
+
+    let myetcd = MyEtcd::open(etcd)?;
+    let dir = format!("/haste/templates/{}/{}", cache_type_name(cache_type), name);
+    for (file, content) in &req.files {
+        myetcd.set(&format!("{}/{}", dir, file), content)?;
+    }
+    info!("push template {} with {} files", dir, req.files.len());
+    reply_ok(&TemplateSummary {
+        cache_type: cache_type_name(cache_type).to_string(),
+        name: name.to_string(),
+        files: req.files.keys().cloned().collect(),
+    })
+}
+
+// all the files of the cache type must be pushed together and parsed by tera.
+fn template_problems(cache_type: CacheType, files: &BTreeMap<String, String>) -> Vec<String> {
+    let expected = template_files(cache_type);
+    let mut problems: Vec<_> = expected
+        .iter()
+        .filter(|x| !files.contains_key(**x))
+        .map(|x| format!("file {} is missing", x))
+        .collect();
+    for (file, content) in files {
+        if !expected.contains(&file.as_str()) {
+            problems.push(format!("file {} is unknown", file));
+            continue;
+        }
+        if let Err(err) = Tera::default().add_raw_template(file, content) {
+            problems.push(format!("file {} is bad template due {}", file, err));
+        }
+    }
+    // the variables are only checked by rendering
+    if problems.is_empty() {
+        let files = files.iter().map(|(k, v)| (k.as_str(), v.as_str()));
+        if let Err(err) = Template::parse(files).and_then(|tpl| tpl.check(cache_type)) {
+            problems.push(format!("{}", err));
+        }
+    }
+    problems
+}
+
+fn show_spec(etcd: &str, name: &str) -> ApiResult {
+    check_name("spec", name)?;
+    let myetcd = MyEtcd::open(etcd)?;
+    let spec = load_spec(&myetcd, name)?
+        .ok_or_else(|| ApiError::not_found(format!("spec {} is not found", name)))?;
+    reply_ok(&SpecDetail {
+        spec,
+        status: load_status(&myetcd, name)?,
+    })
+}
+
+// the spec is only saved here, the reconciler applies it later.
+fn put_spec(etcd: &str, name: &str, body: &[u8]) -> ApiResult {
+    check_name("spec", name)?;
+    let spec: ClusterSpec = parse_json(body)?;
+    if spec.name != name {
+        return Err(ApiError::bad_request(format!(
+            "name {} of spec is not {}",
+            spec.name, name
+        )));
+    }
+    if spec.replicas == 0 {
+        return Err(ApiError::bad_request("replicas must be greater than 0"));
+    }
+    let myetcd = MyEtcd::open(etcd)?;
+    spec.to_param()
+        .validate(&myetcd)
+        .map_err(invalid_or_internal)?;
+
+    match save_spec(&myetcd, &spec) {
+        Ok(saved) => {
+            info!("save spec {} as revision {}", name, saved.revision);
+            reply_ok(&saved)
+        }
+        Err(err) => match err.downcast::<RevisionConflict>() {
+            Ok(conflict) => Err(ApiError::conflict(format!("{}", conflict))),
+            Err(err) => Err(err.into()),
+        },
+    }
+}
+
+// the ports of cluster on each host
+fn cluster_hosts(myetcd: &MyEtcd, name: &str) -> Result<BTreeMap<String, Vec<i64>>, Error> {
+    let mut hosts: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    for addr in cluster_instances(myetcd, name)? {
+        match parse_addr(&addr) {
+            Ok((host, port)) => hosts.entry(host).or_default().push(port),
+            Err(_) => warn!("bad instance {} of cluster {}", addr, name),
+        }
+    }
+    Ok(hosts)
+}
+
+fn base_name(key: &str) -> String {
+    key.rsplit('/').next().unwrap_or("").to_string()
+}
+
+/// names of clusters are used in etcd keys and unit names, so only `[a-zA-Z0-9_-]` is allowed.
+pub fn check_name(kind: &str, name: &str) -> Result<(), ApiError> {
+    server::check_name(kind, name).map_err(ApiError::bad_request)
+}
+
+// the problems of `InvalidParm` are replied as details.
+fn invalid_or_internal(err: Error) -> ApiError {
+    match err.downcast::<InvalidParm>() {
+        Ok(invalid) => {
+            let mut err =
+                ApiError::bad_request(format!("invalid param of cluster {}", invalid.name));
+            err.details = invalid.problems;
+            err
+        }
+        Err(err) => err.into(),
+    }
+}
+
+// {ip}:{port} -> (ip, port)
+fn parse_addr(addr: &str) -> Result<(String, i64), ApiError> {
+    let mut kv = addr.rsplitn(2, ':');
+    match (kv.next(), kv.next()) {
+        (Some(port), Some(host)) if !host.is_empty() => match port.parse::<u16>() {
+            Ok(port) if port != 0 => Ok((host.to_string(), i64::from(port))),
+            _ => Err(ApiError::bad_request(format!("bad port of {}", addr))),
+        },
+        _ => Err(ApiError::bad_request(format!(
+            "bad addr {}, must be {{ip}}:{{port}}",
+            addr
+        ))),
+    }
+}
+
+fn parse_json<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|err| ApiError::bad_request(format!("bad json: {}", err)))
+}
+
+// no percent decoding, the values are only flags
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|x| !x.is_empty())
+        .map(|kv| {
+            let mut kv = kv.splitn(2, '=');
+            let key = kv.next().unwrap_or("").to_string();
+            (key, kv.next().unwrap_or("").to_string())
+        })
+        .collect()
+}
+
+fn is_true(query: &HashMap<String, String>, key: &str) -> bool {
+    matches!(
+        query.get(key).map(|x| x.as_str()),
+        Some("") | Some("1") | Some("true")
+    )
+}
+
+fn reply_ok<T: Serialize>(data: &T) -> ApiResult {
+    Ok(reply_json(StatusCode::OK, data))
+}
+
+fn reply_json<T: Serialize>(status: StatusCode, data: &T) -> Response<Body> {
+    let body = match serde_json::to_vec_pretty(data) {
+        Ok(body) => body,
+        Err(err) => {
+            error!("fail to encode json due {}", err);
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::empty())
+                .unwrap();
+        }
+    };
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // no etcd is listening, so only the requests rejected before etcd can be routed
+    const ETCD: &str = "http://127.0.0.1:1";
+
+    fn route_err(method: Method, path: &str, body: &str) -> ApiError {
+        let query = parse_query(path.splitn(2, '?').nth(1).unwrap_or(""));
+        let path = path.splitn(2, '?').next().unwrap();
+        match route(ETCD, &method, path, &query, body.as_bytes()) {
+            Ok(reply) => panic!("{} {} is replied as {}", method, path, reply.status()),
+            Err(err) => err,
+        }
+    }
+
+    fn files(files: &[(&str, &str)]) -> BTreeMap<String, String> {
+        files
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_addrs() {
+        assert_eq!(
+            parse_addr("10.0.0.1:7000").unwrap(),
+            ("10.0.0.1".to_string(), 7000)
+        );
+        assert_eq!(
+            parse_addr("[::1]:7000").unwrap(),
+            ("[::1]".to_string(), 7000)
+        );
+        for addr in &[
+            "10.0.0.1:0",
+            ":7000",
+            "10.0.0.1",
+            "10.0.0.1:",
+            "10.0.0.1:65536",
+            "10.0.0.1:-1",
+            "10.0.0.1:port",
+        ] {
+            let err = parse_addr(addr).unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST, "{}", addr);
+        }
+    }
+
+    #[test]
+    fn parse_flags() {
+        let query = parse_query("dry_run&force=1&stats=true&debug=false&&x=");
+        assert!(is_true(&query, "dry_run"));
+        assert!(is_true(&query, "force"));
+        assert!(is_true(&query, "stats"));
+        assert!(is_true(&query, "x"));
+        assert!(!is_true(&query, "debug"));
+        assert!(!is_true(&query, "missing"));
+        assert!(parse_query("").is_empty());
+    }
+
+    #[test]
+    fn route_rejects_bad_requests() {
+        let err = route_err(Method::GET, "/nothing", "");
+        assert_eq!((err.status, err.code), (StatusCode::NOT_FOUND, "not_found"));
+        let err = route_err(Method::POST, "/clusters", "");
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let bad = [
+            (Method::GET, "/clusters/my.cluster", ""),
+            (Method::GET, "/clusters/my.cluster/audit", ""),
+            (Method::GET, "/jobs/..-1571472000000", ""),
+            (Method::GET, "/jobs/mycluster", ""),
+            (Method::GET, "/specs/my.spec", ""),
+            (Method::POST, "/deploys", "{"),
+            (
+                Method::POST,
+                "/clusters/mycluster/actions",
+                r#"{"action": "kill"}"#,
+            ),
+            (Method::POST, "/clusters/mycluster/upgrade", "{}"),
+            (
+                Method::DELETE,
+                "/clusters/mycluster/instances/10.0.0.1:0",
+                "",
+            ),
+            (Method::DELETE, "/clusters/mycluster/instances/:7000", ""),
+            (Method::PUT, "/templates/mysql/default", "{}"),
+            (Method::PUT, "/templates/redis/de.fault", "{}"),
+        ];
+        for (method, path, body) in bad.iter() {
+            let err = route_err(method.clone(), path, body);
+            assert_eq!(
+                (err.status, err.code),
+                (StatusCode::BAD_REQUEST, "bad_request"),
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn route_template_problems_as_details() {
+        let body = r#"{"files": {"redis.conf": "port {{ port", "nodes.conf": ""}}"#;
+        let err = route_err(Method::PUT, "/templates/redis_cluster/default", body);
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.details.len(), 3);
+    }
+
+    #[test]
+    fn problems_of_template() {
+        let redis_conf = "port {{ port }}\n";
+        let service = "[Service]\nExecStart={{ binary }} {{ conf }}\n";
+
+        let problems = template_problems(CacheType::Redis, &files(&[("redis.conf", redis_conf)]));
+        assert_eq!(problems, vec!["file cache.service is missing"]);
+
+        let problems = template_problems(
+            CacheType::Memcache,
+            &files(&[("cache.service", service), ("redis.conf", redis_conf)]),
+        );
+        assert_eq!(problems, vec!["file redis.conf is unknown"]);
+
+        let problems = template_problems(
+            CacheType::Redis,
+            &files(&[("cache.service", service), ("redis.conf", "port {% if %}")]),
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("file redis.conf is bad template"));
+
+        // only found by rendering
+        let problems = template_problems(
+            CacheType::Redis,
+            &files(&[
+                ("cache.service", service),
+                ("redis.conf", "port {{ no_such_var }}\n"),
+            ]),
+        );
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn invalid_parm_as_details() {
+        let err = invalid_or_internal(
+            InvalidParm {
+                name: "mycluster".to_string(),
+                problems: vec![
+                    "max_memory must be greater than 0".to_string(),
+                    "bad".to_string(),
+                ],
+            }
+            .into(),
+        );
+        assert_eq!(
+            (err.status, err.code),
+            (StatusCode::BAD_REQUEST, "bad_request")
+        );
+        assert_eq!(err.message, "invalid param of cluster mycluster");
+        assert_eq!(err.details.len(), 2);
+
+        let err = invalid_or_internal(format_err!("etcd is down"));
+        assert_eq!(
+            (err.status, err.code),
+            (StatusCode::INTERNAL_SERVER_ERROR, "internal")
+        );
+        assert!(err.details.is_empty());
+
+        let err = invalid_or_internal(
+            ClusterBusy {
+                name: "mycluster".to_string(),
+                holder: "upgrade mycluster-1571472000000".to_string(),
+            }
+            .into(),
+        );
+        assert_eq!((err.status, err.code), (StatusCode::CONFLICT, "conflict"));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::offer::Offer;
 
 use failure::{format_err, Error};
+use serde_derive::{Deserialize, Serialize};
 
 use std::collections::{HashMap, VecDeque};
 use std::fmt;
@@ -275,6 +276,7 @@ struct InstanceCount {
     count: usize,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Chunks(pub Vec<Instance>);
 
 impl Chunks {
@@ -291,6 +293,7 @@ impl Chunks {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Instance {
     pub host: String,
     pub port: usize,
@@ -330,6 +333,7 @@ impl Instance {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Slot {
     begin: usize,
     end: usize,
@@ -1,2 +1,12 @@
 pub mod agent;
+pub mod binary;
+pub mod build;
+pub mod config;
+pub mod files;
+pub mod gc;
+pub mod metrics;
+pub mod plan;
+pub mod ports;
 pub mod server;
+pub mod service;
+pub mod status;
@@ -1,51 +1,71 @@
-use crate::proto::{CacheInfo, CacheType, SystemdAction};
-use crate::systemd::{do_action, service_name};
-
-use failure::Error;
-use log::{debug, info, warn};
-use reqwest;
-use tempfile::tempdir;
-
-use std::fs::{self, File};
-use std::io::Write;
+use crate::deploy::binary::Binary;
+use crate::deploy::build::BuildConfig;
+use crate::deploy::files::{load_good, save_good, write_file, FilePolicy};
+use crate::layout::{ports_under, InstanceLayout, LEGACY_DATA_ROOTS, UNIT_DIR};
+use crate::proto::{CacheInfo, ChangeKind, InstanceChange, SystemdAction, UnitState};
+use crate::supervisor::Supervisor;
+use crate::systemd::{do_action, retire_units, unit_of};
+
+use failure::{format_err, Error};
+use log::{error, info, warn};
+
+use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-pub const LIB_DIR: &str = "/data/haste/lib";
-pub const INSTANCE_DIR: &str = "/data/haste/instance";
-pub const SYSTEMD_DIR: &str = "/etc/systemd/";
+/// the environment shared by all the deploys of agent.
+#[derive(Clone)]
+pub struct AgentEnv {
+    pub locker: Arc<Mutex<()>>,
+    pub build: BuildConfig,
+    pub policy: FilePolicy,
+    pub layout: InstanceLayout,
+    pub supervisor: Arc<dyn Supervisor>,
+}
+
+impl AgentEnv {
+    pub fn do_action(&self, action: SystemdAction, port: i64) -> Result<(), Error> {
+        do_action(self.supervisor.as_ref(), action, port)
+    }
+}
 
 pub struct CacheDeployer {
     ci: CacheInfo,
-    locker: Arc<Mutex<()>>,
+    env: AgentEnv,
 }
 
 impl CacheDeployer {
-    pub fn new(ci: CacheInfo, locker: Arc<Mutex<()>>) -> Self {
-        CacheDeployer { ci, locker }
+    pub fn new(ci: CacheInfo, env: AgentEnv) -> Self {
+        CacheDeployer { ci, env }
     }
 
     /// deploy cache instances
     ///
-    ///   0. clean dirty dir
+    ///   0. check the paths of files and clean dirty dir
     ///   1. check if binary exists
     ///       1.1 if not exists
     ///       1.2 flock binary lock (defer unlock)
-    ///       1.2 download binary file
+    ///       1.3 check again and download binary file
     ///   2. redner files
     ///   3. setup systemd service
     ///   4. spwan systemd service
     ///
-    pub fn deploy(&mut self) -> Result<(), Error> {
+    /// converge instead if `converge` of cache info is set.
+    pub fn deploy(&mut self) -> Result<Vec<InstanceChange>, Error> {
+        if self.ci.get_converge() {
+            return self.converge();
+        }
+
         info!("start deploy to {:?}", self.ci);
+        self.check_files()?;
 
         info!("trying to clean dirty service files");
         self.clean_dirty()?;
 
-        if self.check_binary()? {
-            info!("download and setup cache binary files");
-            self.setup_binary()?;
-        }
+        info!("check or setup cache binary files");
+        self.setup_binary()?;
 
         info!("render config files");
         self.render_files()?;
@@ -53,31 +73,146 @@ impl CacheDeployer {
         self.setup_systemd()?;
         info!("spawn cache service");
         self.spawn_cache()?;
+        self.save_good()?;
         info!("all terms is done, good luck.");
 
+        let changes = self
+            .ci
+            .get_insts()
+            .iter()
+            .map(|inst| {
+                let files: Vec<_> = inst
+                    .get_files()
+                    .iter()
+                    .map(|x| x.get_fpath().to_string())
+                    .collect();
+                let mut change = InstanceChange::new();
+                change.set_port(inst.get_port());
+                change.set_kind(ChangeKind::Created);
+                change.set_files(files.into());
+                change
+            })
+            .collect();
+        Ok(changes)
+    }
+
+    /// converge cache instances to the desired state without losing data
+    ///
+    ///   1. check the paths of files and check or setup binary
+    ///   2. retire the legacy `cache-{port}` unit of each instance
+    ///   3. rewrite the changed files only, the data dirs are kept
+    ///   4. reload systemd if any unit was changed
+    ///   5. start the new instances and restart the changed ones
+    ///
+    pub fn converge(&mut self) -> Result<Vec<InstanceChange>, Error> {
+        info!("start converge to {:?}", self.ci);
+        self.check_files()?;
+        self.setup_binary()?;
+
+        let mut reload = false;
+        let mut changes = Vec::new();
+        for inst in self.ci.get_insts() {
+            // the unit is renamed, so the instance is restarted by the new one
+            retire_units(
+                self.env.supervisor.as_ref(),
+                self.ci.get_cluster(),
+                inst.get_port(),
+            )?;
+            let mut exists = false;
+            let mut files = Vec::new();
+            for file in inst.get_files() {
+                let path = Path::new(file.get_fpath());
+                match fs::read_to_string(path) {
+                    Ok(current) => {
+                        exists = true;
+                        if current == file.get_content() || is_runtime_file(path) {
+                            continue;
+                        }
+                    }
+                    Err(ref err) if err.kind() == ErrorKind::NotFound => {}
+                    Err(err) => return Err(err.into()),
+                }
+
+                write_file(file)?;
+                reload = reload || is_unit_file(path);
+                files.push(file.get_fpath().to_string());
+            }
+
+            let kind = if !exists {
+                ChangeKind::Created
+            } else if files.is_empty() {
+                ChangeKind::Unchanged
+            } else {
+                ChangeKind::Updated
+            };
+            info!(
+                "instance {} was {:?} with files {:?}",
+                inst.get_port(),
+                kind,
+                files
+            );
+
+            let mut change = InstanceChange::new();
+            change.set_port(inst.get_port());
+            change.set_kind(kind);
+            change.set_files(files.into());
+            changes.push(change);
+        }
+
+        if reload {
+            self.setup_systemd()?;
+        }
+
+        for change in changes.iter_mut() {
+            if let ChangeKind::Updated = change.get_kind() {
+                self.env
+                    .do_action(SystemdAction::Restart, change.get_port())?;
+                change.set_restarted(true);
+            } else {
+                // start is a no-op for the running instances
+                self.env
+                    .do_action(SystemdAction::Start, change.get_port())?;
+            }
+        }
+        self.save_good()?;
+        Ok(changes)
+    }
+
+    fn check_files(&self) -> Result<(), Error> {
+        for inst in self.ci.get_insts() {
+            self.env.policy.check_all(inst.get_files())?;
+        }
+        Ok(())
+    }
+
+    // the files of the started instances are the last known good ones.
+    fn save_good(&self) -> Result<(), Error> {
+        for inst in self.ci.get_insts() {
+            save_good(inst.get_port(), inst.get_files())?;
+        }
         Ok(())
     }
 
     // check if files and delete them:
-    //   1. /data/cache/instance/{port} exists
-    //   2. if /etc/systemd/cache@{port}.service file exists
+    //   1. {data_root}/{port} exists
+    //   2. if any unit of port exists, include the legacy cache-{port}.service
     fn clean_service_dirty(&self, port: i64) -> Result<bool, Error> {
         let mut exists = false;
         info!("tryint to check if instance on port {} exists", port);
-        let mut inst_pb = PathBuf::from(INSTANCE_DIR);
-        inst_pb.push(&format!("{}", port));
-        if inst_pb.as_path().exists() {
-            fs::remove_dir_all(inst_pb.as_path())?;
+        let inst_dir = self.env.layout.instance_dir(port);
+        if inst_dir.exists() {
+            fs::remove_dir_all(&inst_dir)?;
             exists = true;
         }
 
-        let mut systemd_pb = PathBuf::from(SYSTEMD_DIR);
-        systemd_pb.push(&service_name(port));
-        if systemd_pb.as_path().exists() {
-            if let Err(err) = do_action(SystemdAction::Stop, port) {
+        if retire_units(self.env.supervisor.as_ref(), self.ci.get_cluster(), port)? {
+            exists = true;
+        }
+        if unit_of(port).is_ok() {
+            if let Err(err) = self.env.do_action(SystemdAction::Stop, port) {
                 warn!("the service is not running but ignore it. error: {:?}", err);
             }
-            if let Err(err) = do_action(SystemdAction::Remove, port) {
+            if let Err(err) = self.env.do_action(SystemdAction::Remove, port) {
                 warn!("remove service not done but ignore it by error {:?}", err);
             }
             exists = true;
@@ -99,65 +234,16 @@ impl CacheDeployer {
         Ok(())
     }
 
-    fn check_binary(&self) -> Result<bool, Error> {
-        let cache_type = self.ci.get_cache_type();
-        let version = self.ci.get_version().to_string();
-
-        let mut lib_dir = PathBuf::from(LIB_DIR);
-        lib_dir.push(cache_type_as_str(cache_type));
-        lib_dir.push(version);
-        lib_dir.push(cache_type_as_binary_name(cache_type));
-        Ok(lib_dir.as_path().exists())
+    fn binary(&self) -> Result<Binary, Error> {
+        Binary::new(self.ci.get_cache_type(), self.ci.get_version())
     }
 
     fn setup_binary(&self) -> Result<(), Error> {
-        let fs = self.ci.get_file_server().to_string();
-        let version = self.ci.get_version().to_string();
-        let cache_type = cache_type_as_str(self.ci.get_cache_type());
-        let bname = cache_type_as_binary_name(self.ci.get_cache_type());
-
-        let url = format!("{}/{}-{}-{}", fs, cache_type, version, bname);
-
-        let dir = tempdir()?;
-        let path = dir
-            .path()
-            .join(format!("{}-{}-{}", cache_type, version, bname));
-        self.download_binary(&path, &url)?;
-
-        // rename
-        // make dir first
-        let mut real_pb = PathBuf::from(LIB_DIR);
-        real_pb.push(&cache_type);
-        real_pb.push(&version);
-
-        fs::create_dir_all(real_pb.as_path())?;
-
-        real_pb.push(bname);
-        let real_path = real_pb.as_path();
-        let _guard = self.locker.lock();
-        fs::rename(&path, real_path)?;
-
-        if cfg!(not(windows)) {
-            use std::fs::Permissions;
-            use std::os::unix::fs::PermissionsExt;
-
-            let bfile = File::open(real_path)?;
-            bfile.set_permissions(Permissions::from_mode(0755))?;
-        }
-
-        Ok(())
-    }
-
-    fn download_binary<T: AsRef<Path>>(&self, fpath: T, url: &str) -> Result<(), Error> {
-        let mut file = File::create(fpath.as_ref())?;
-        let mut response = reqwest::get(url)?;
-        let size = response.copy_to(&mut file)?;
-        info!(
-            "download succeed to file {:?} with {} bytes",
-            fpath.as_ref(),
-            size
-        );
-        Ok(())
+        self.binary()?.ensure(
+            self.ci.get_file_server(),
+            &self.env.build,
+            self.ci.get_job_id(),
+        )
     }
 
     fn render_files(&self) -> Result<(), Error> {
@@ -169,51 +255,437 @@ impl CacheDeployer {
             .flatten();
 
         for file in files {
-            debug!(
-                "create file {} with content {}",
-                file.get_fpath(),
-                file.get_content()
-            );
-            let path = Path::new(file.get_fpath());
-            if let Some(dir) = path.parent() {
-                fs::create_dir_all(dir)?;
-            }
-
-            let mut fp = File::create(path)?;
-            fp.write_all(file.get_content().as_bytes())?;
+            write_file(&file)?;
         }
 
         Ok(())
     }
 
     fn setup_systemd(&self) -> Result<(), Error> {
-        let _guard = self.locker.lock();
-        do_action(SystemdAction::Setup, -1)?;
+        let _guard = self.env.locker.lock();
+        self.env.do_action(SystemdAction::Setup, -1)?;
         Ok(())
     }
 
     fn spawn_cache(&self) -> Result<(), Error> {
         let ports = self.ci.get_insts().into_iter().map(|x| x.get_port());
         for port in ports {
-            do_action(SystemdAction::Start, port)?;
+            self.env.do_action(SystemdAction::Start, port)?;
         }
         Ok(())
     }
 }
 
-fn cache_type_as_binary_name(cache_type: CacheType) -> &'static str {
-    match cache_type {
-        CacheType::Memcache => "memcached",
-        _ => "redis-server",
+/// rewrite the last known good config files of the instance and restart it.
+pub fn restore(port: i64, env: &AgentEnv) -> Result<InstanceChange, Error> {
+    let good = load_good(port)?;
+    env.policy.check_all(&good)?;
+    let mut reload = false;
+    let mut files = Vec::new();
+    for file in &good {
+        let path = Path::new(file.get_fpath());
+        if let Ok(current) = fs::read_to_string(path) {
+            if current == file.get_content() {
+                continue;
+            }
+        }
+        write_file(file)?;
+        reload = reload || is_unit_file(path);
+        files.push(file.get_fpath().to_string());
+    }
+
+    if reload {
+        let _guard = env.locker.lock();
+        env.do_action(SystemdAction::Setup, -1)?;
     }
+    env.do_action(SystemdAction::Restart, port)?;
+    info!("restore instance {} with files {:?}", port, files);
+
+    let mut change = InstanceChange::new();
+    change.set_port(port);
+    change.set_kind(if files.is_empty() {
+        ChangeKind::Unchanged
+    } else {
+        ChangeKind::Updated
+    });
+    change.set_files(files.into());
+    change.set_restarted(true);
+    Ok(change)
 }
 
-fn cache_type_as_str(cache_type: CacheType) -> &'static str {
-    const CACHE_TYPE_REDIS: &str = "redis";
-    const CACHE_TYPE_MEMCACHE: &str = "memcache";
+/// move the instances under legacy data roots into the layout:
+///   1. check the data dir can be renamed, they must be in the same filesystem
+///   2. stop the instance if it's active
+///   3. rename it's data dir and replace the old dir in it's unit, configs and known good configs
+///   4. reload systemd and start it again if it was active
+///
+/// a failed instance is moved back and started again if it was active, the failures are only
+/// logged so the agent still serves the other instances.
+pub fn migrate(env: &AgentEnv) -> Vec<i64> {
+    let layout = &env.layout;
+    let mut migrated = Vec::new();
+    for root in LEGACY_DATA_ROOTS {
+        if Path::new(root) == Path::new(&layout.data_root) {
+            continue;
+        }
+
+        let ports = match ports_under(root) {
+            Ok(ports) => ports,
+            Err(err) => {
+                error!("fail to list instances under {} due {}", root, err);
+                continue;
+            }
+        };
+        let legacy = InstanceLayout::new(root);
+        for port in ports {
+            match migrate_instance(env, &legacy, port) {
+                Ok(true) => migrated.push(port),
+                Ok(false) => {}
+                Err(err) => error!(
+                    "fail to migrate instance {} under {} due {}",
+                    port, root, err
+                ),
+            }
+        }
+    }
+    migrated
+}
+
+// the file which mentions the old dir, it's moved with the data dir if it's a config.
+struct Rewrite {
+    path: PathBuf,
+    original: String,
+    content: String,
+}
+
+// migrate the instance on port, return false if it's skipped.
+fn migrate_instance(env: &AgentEnv, legacy: &InstanceLayout, port: i64) -> Result<bool, Error> {
+    let (old_dir, new_dir) = (legacy.instance_dir(port), env.layout.instance_dir(port));
+    if new_dir.exists() {
+        warn!("skip migrate {:?} due {:?} exists", old_dir, new_dir);
+        return Ok(false);
+    }
+    let parent = new_dir
+        .parent()
+        .ok_or_else(|| format_err!("bad instance dir {:?}", new_dir))?;
+    fs::create_dir_all(parent)?;
+    // rename can't move the dir into another filesystem, check it before the instance stops
+    if fs::metadata(&old_dir)?.dev() != fs::metadata(parent)?.dev() {
+        warn!(
+            "skip migrate {:?} due {:?} is in another filesystem",
+            old_dir, parent
+        );
+        return Ok(false);
+    }
+
+    let (from, to) = (
+        old_dir.to_string_lossy().to_string(),
+        new_dir.to_string_lossy().to_string(),
+    );
+    let unit = unit_of(port).ok();
+    let mut rewrites = Vec::new();
+    if let Some(name) = &unit {
+        let path = Path::new(UNIT_DIR).join(name);
+        rewrites.extend(rewrite_of(&path, &path, &from, &to)?);
+    }
+    for entry in fs::read_dir(&old_dir)? {
+        let path = entry?.path();
+        if let (Some(true), Some(name)) = (path.extension().map(|x| x == "conf"), path.file_name())
+        {
+            rewrites.extend(rewrite_of(&path, &new_dir.join(name), &from, &to)?);
+        }
+    }
+
+    // the stopped instances are kept stopped
+    let active = match &unit {
+        Some(name) => env.supervisor.status(name)?.state == UnitState::Active,
+        None => false,
+    };
+    info!(
+        "migrate instance {:?} to {:?}, it's active: {}",
+        old_dir, new_dir, active
+    );
+    if active {
+        env.do_action(SystemdAction::Stop, port)?;
+    }
+
+    let moved = move_instance(port, &old_dir, &new_dir, &rewrites, &from, &to);
+    // the unit was rewritten or restored, it's reloaded and started again in both cases
+    let started = start_again(env, port, unit.is_some(), active);
+    moved?;
+    started?;
+    Ok(true)
+}
+
+fn rewrite_of(path: &Path, moved: &Path, from: &str, to: &str) -> Result<Option<Rewrite>, Error> {
+    let original = fs::read_to_string(path)?;
+    let content = replace_dir(&original, from, to);
+    if content == original {
+        return Ok(None);
+    }
+    Ok(Some(Rewrite {
+        path: moved.to_path_buf(),
+        original,
+        content,
+    }))
+}
+
+// rename the data dir and rewrite the files, all of them are put back if any step fails.
+fn move_instance(
+    port: i64,
+    old_dir: &Path,
+    new_dir: &Path,
+    rewrites: &[Rewrite],
+    from: &str,
+    to: &str,
+) -> Result<(), Error> {
+    let good = load_good(port).ok();
+    fs::rename(old_dir, new_dir)
+        .map_err(|err| format_err!("fail to move {:?} to {:?} due {}", old_dir, new_dir, err))?;
+
+    let rslt = rewrites
+        .iter()
+        .try_for_each(|x| fs::write(&x.path, &x.content))
+        .map_err(Error::from)
+        .and_then(|_| match &good {
+            Some(good) => {
+                let moved: Vec<_> = good
+                    .iter()
+                    .map(|file| {
+                        let mut file = file.clone();
+                        file.set_fpath(replace_dir(file.get_fpath(), from, to));
+                        file.set_content(replace_dir(file.get_content(), from, to));
+                        file
+                    })
+                    .collect();
+                save_good(port, &moved)
+            }
+            None => Ok(()),
+        });
+    if let Err(err) = rslt {
+        warn!("fail to migrate {:?} due {}, move it back", old_dir, err);
+        for rewrite in rewrites {
+            if let Err(err) = fs::write(&rewrite.path, &rewrite.original) {
+                error!("fail to restore {:?} due {}", rewrite.path, err);
+            }
+        }
+        if let Some(good) = &good {
+            if let Err(err) = save_good(port, good) {
+                error!("fail to restore good configs of {} due {}", port, err);
+            }
+        }
+        if let Err(err) = fs::rename(new_dir, old_dir) {
+            error!("fail to move {:?} back due {}", new_dir, err);
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn start_again(env: &AgentEnv, port: i64, reload: bool, active: bool) -> Result<(), Error> {
+    if reload {
+        let _guard = env.locker.lock();
+        env.do_action(SystemdAction::Setup, -1)?;
+    }
+    if active {
+        env.do_action(SystemdAction::Start, port)?;
+    }
+    Ok(())
+}
+
+// replace the dir only where it's a whole path or the prefix of one, so `/data/cache/6379`
+// never matches `/data/cache/63790` or `/mnt/data/cache/6379`.
+fn replace_dir(content: &str, from: &str, to: &str) -> String {
+    let is_path_char = |c: char| c.is_alphanumeric() || "/._-".contains(c);
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(pos) = rest.find(from) {
+        let end = pos + from.len();
+        let starts = rest[..pos]
+            .chars()
+            .next_back()
+            .map(|c| !is_path_char(c))
+            .unwrap_or(true);
+        let ends = rest[end..]
+            .chars()
+            .next()
+            .map(|c| c == '/' || !is_path_char(c))
+            .unwrap_or(true);
+        out.push_str(&rest[..pos]);
+        out.push_str(if starts && ends { to } else { from });
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_unit_file(path: &Path) -> bool {
+    path.extension().map(|x| x == "service").unwrap_or(false)
+}
+
+// files which are rewritten by cache itself after startup, only create them if missing.
+fn is_runtime_file(path: &Path) -> bool {
+    path.file_name().map(|x| x == "nodes.conf").unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supervisor::mock::MockSupervisor;
+    use crate::systemd::legacy_service_name;
+    use tempfile::TempDir;
+
+    #[test]
+    fn replace_whole_dirs() {
+        let (from, to) = ("/data/cache/6379", "/data/haste/cache/6379");
+        assert_eq!(replace_dir(from, from, to), to);
+        assert_eq!(
+            replace_dir(
+                "dir /data/cache/6379\nlogfile /data/cache/6379/redis.log\n",
+                from,
+                to
+            ),
+            "dir /data/haste/cache/6379\nlogfile /data/haste/cache/6379/redis.log\n"
+        );
+        assert_eq!(
+            replace_dir(
+                "ExecStart=/usr/bin/redis-server \"/data/cache/6379/redis.conf\"",
+                from,
+                to
+            ),
+            "ExecStart=/usr/bin/redis-server \"/data/haste/cache/6379/redis.conf\""
+        );
+        assert_eq!(
+            replace_dir("WorkingDirectory=/data/cache/6379", from, to),
+            "WorkingDirectory=/data/haste/cache/6379"
+        );
+    }
+
+    #[test]
+    fn keep_other_dirs() {
+        let (from, to) = ("/data/cache/6379", "/data/haste/cache/6379");
+        for content in &[
+            "dir /data/cache/63790",
+            "dir /mnt/data/cache/6379",
+            "dir /data/cache/6379.bak",
+            "dir /data/cache/6379-old/",
+            "dir /data/cache/637",
+        ] {
+            assert_eq!(&replace_dir(content, from, to), content);
+        }
+        assert_eq!(
+            replace_dir("/data/cache/63790 /data/cache/6379", from, to),
+            "/data/cache/63790 /data/haste/cache/6379"
+        );
+    }
+
+    fn write_instance(dir: &Path, from: &str) -> Vec<Rewrite> {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("dump.rdb"), "data").unwrap();
+        let original = format!("port 1\ndir {}\n", from);
+        fs::write(dir.join("redis.conf"), &original).unwrap();
+        vec![Rewrite {
+            path: PathBuf::new(),
+            original,
+            content: String::new(),
+        }]
+    }
+
+    #[test]
+    fn move_instance_with_rewrites() {
+        let root = TempDir::new().unwrap();
+        let (old_dir, new_dir) = (root.path().join("old/1"), root.path().join("new/1"));
+        let (from, to) = (
+            old_dir.to_string_lossy().to_string(),
+            new_dir.to_string_lossy().to_string(),
+        );
+        let mut rewrites = write_instance(&old_dir, &from);
+        fs::create_dir_all(new_dir.parent().unwrap()).unwrap();
+        rewrites[0].path = new_dir.join("redis.conf");
+        rewrites[0].content = replace_dir(&rewrites[0].original, &from, &to);
+
+        // there are no known good configs of the port
+        move_instance(1, &old_dir, &new_dir, &rewrites, &from, &to).unwrap();
+        assert!(!old_dir.exists());
+        assert_eq!(
+            fs::read_to_string(new_dir.join("dump.rdb")).unwrap(),
+            "data"
+        );
+        assert_eq!(
+            fs::read_to_string(new_dir.join("redis.conf")).unwrap(),
+            format!("port 1\ndir {}\n", to)
+        );
+    }
+
+    #[test]
+    fn move_instance_back_on_failure() {
+        let root = TempDir::new().unwrap();
+        let (old_dir, new_dir) = (root.path().join("old/1"), root.path().join("new/1"));
+        let (from, to) = (
+            old_dir.to_string_lossy().to_string(),
+            new_dir.to_string_lossy().to_string(),
+        );
+        let mut rewrites = write_instance(&old_dir, &from);
+        fs::create_dir_all(new_dir.parent().unwrap()).unwrap();
+        rewrites[0].path = new_dir.join("redis.conf");
+        rewrites[0].content = replace_dir(&rewrites[0].original, &from, &to);
+        // the second rewrite fails due it's dir is missing
+        rewrites.push(Rewrite {
+            path: root.path().join("missing/unit.service"),
+            original: String::new(),
+            content: to.clone(),
+        });
+
+        assert!(move_instance(1, &old_dir, &new_dir, &rewrites, &from, &to).is_err());
+        assert!(!new_dir.exists());
+        assert_eq!(
+            fs::read_to_string(old_dir.join("dump.rdb")).unwrap(),
+            "data"
+        );
+        assert_eq!(
+            fs::read_to_string(old_dir.join("redis.conf")).unwrap(),
+            rewrites[0].original
+        );
+
+        // the new dir is taken, so nothing is moved
+        fs::create_dir_all(new_dir.join("taken")).unwrap();
+        assert!(move_instance(1, &old_dir, &new_dir, &rewrites[..1], &from, &to).is_err());
+        assert!(old_dir.join("dump.rdb").exists());
+    }
+
+    fn env_of(supervisor: Arc<MockSupervisor>, root: &TempDir) -> AgentEnv {
+        let data_root = root.path().to_string_lossy().to_string();
+        AgentEnv {
+            locker: Arc::new(Mutex::new(())),
+            build: BuildConfig::default(),
+            policy: FilePolicy::new(&[&data_root]),
+            layout: InstanceLayout::new(&data_root),
+            supervisor,
+        }
+    }
+
+    #[test]
+    fn start_again_only_if_active() {
+        let root = TempDir::new().unwrap();
+        let supervisor = Arc::new(MockSupervisor::default());
+        let env = env_of(supervisor.clone(), &root);
+        // there is no unit of the port, so the legacy name is used
+        let unit = legacy_service_name(1);
+
+        start_again(&env, 1, true, false).unwrap();
+        assert!(!supervisor.is_running(&unit));
+        start_again(&env, 1, false, true).unwrap();
+        assert!(supervisor.is_running(&unit));
+        assert_eq!(
+            supervisor.calls(),
+            vec![
+                ("reload".to_string(), "".to_string()),
+                ("start".to_string(), unit.clone()),
+            ]
+        );
 
-    match cache_type {
-        CacheType::Memcache => CACHE_TYPE_MEMCACHE,
-        _ => CACHE_TYPE_REDIS,
+        supervisor.fail_on(&unit);
+        assert!(start_again(&env, 1, true, true).is_err());
+        assert!(!supervisor.is_running(&unit));
     }
 }
@@ -0,0 +1,363 @@
+//! install cache binaries from the file server:
+//!   1. fetch the manifest `{file_server}/SHA256SUMS`, which is the output of `sha256sum`
+//!   2. download `{file_server}/{type}-{version}-{bin}` into `STAGING_DIR`, the partial
+//!      downloads are resumed by http range request
+//!   3. check the sha256 of the file with the manifest
+//!   4. run the binary with `--version`
+//!   5. rename it into `/data/haste/lib/{type}/{version}/{bin}`
+//!
+//! the staging dir is under `LIB_DIR`, so the rename is atomic and a corrupt binary is
+//! never installed.
+//!
+//! binaries may also be built from source by the mode of `BuildConfig`, they are checked
+//! by `--version` and renamed into place in the same way. a prebuilt binary which doesn't
+//! match it's checksum is never replaced by a build.
+//!
+//! installs are serialized by flock of `/data/haste/lib/{type}/.{version}.lock`, so only one
+//! download happens for each version even across agent processes.
+
+use crate::deploy::build::{build, BuildConfig, BuildMode};
+use crate::exporter::{DOWNLOAD_BYTES, DOWNLOAD_FAILURES};
+use crate::layout::LIB_DIR;
+use crate::proto::CacheType;
+
+use failure::{format_err, Error, Fail};
+use fs2::FileExt;
+use log::{info, warn};
+use reqwest::header::RANGE;
+use reqwest::{self, StatusCode};
+use sha2::{Digest, Sha256};
+
+use std::fs::{self, File, OpenOptions, Permissions};
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+pub const MANIFEST_NAME: &str = "SHA256SUMS";
+pub const STAGING_DIR: &str = "/data/haste/lib/.staging";
+const DOWNLOAD_RETRY: usize = 3;
+
+#[derive(Debug, Fail)]
+#[fail(
+    display = "checksum mismatch of {}, expect {} but got {}",
+    url, expect, actual
+)]
+pub struct ChecksumMismatch {
+    pub url: String,
+    pub expect: String,
+    pub actual: String,
+}
+
+pub struct Binary {
+    cache_type: CacheType,
+    version: String,
+}
+
+impl Binary {
+    /// the version must be like 4.0.11, since it's a part of the paths in `LIB_DIR`.
+    pub fn new(cache_type: CacheType, version: &str) -> Result<Binary, Error> {
+        if !is_version(version) {
+            return Err(format_err!("bad version {:?} of binary", version));
+        }
+        Ok(Binary {
+            cache_type,
+            version: version.to_string(),
+        })
+    }
+
+    pub fn cache_type(&self) -> CacheType {
+        self.cache_type
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// the name in file server as {type}-{version}-{bin}
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            cache_type_as_str(self.cache_type),
+            self.version,
+            cache_type_as_binary_name(self.cache_type)
+        )
+    }
+
+    /// /data/haste/lib/{type}/{version}
+    pub fn dir(&self) -> PathBuf {
+        let mut pb = PathBuf::from(LIB_DIR);
+        pb.push(cache_type_as_str(self.cache_type));
+        pb.push(&self.version);
+        pb
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir().join(cache_type_as_binary_name(self.cache_type))
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path().exists()
+    }
+
+    /// install the binary if it's not exists, only one of the concurrent callers downloads.
+    pub fn ensure(
+        &self,
+        file_server: &str,
+        build_config: &BuildConfig,
+        job_id: &str,
+    ) -> Result<(), Error> {
+        if self.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        // check again, it may be installed by others while waiting for the lock.
+        if self.exists() {
+            info!("binary {:?} was installed by others", self.path());
+            return Ok(());
+        }
+
+        fs::create_dir_all(STAGING_DIR)?;
+        let staging = Path::new(STAGING_DIR).join(format!("{}.part", self.file_name()));
+        match build_config.mode {
+            BuildMode::Never => self.download(file_server, &staging)?,
+            BuildMode::Always => build(self, build_config, file_server, job_id, &staging)?,
+            BuildMode::Fallback => {
+                if let Err(err) = self.download(file_server, &staging) {
+                    // the file server may be compromised, never build from it's source
+                    if err.downcast_ref::<ChecksumMismatch>().is_some() {
+                        return Err(err);
+                    }
+                    warn!(
+                        "fail to download {} due {}, build it",
+                        self.file_name(),
+                        err
+                    );
+                    build(self, build_config, file_server, job_id, &staging)?;
+                }
+            }
+        }
+        self.install(&staging)
+    }
+
+    /// {type}/{version}, the path relative to `LIB_DIR`
+    pub fn key(&self) -> String {
+        format!("{}/{}", cache_type_as_str(self.cache_type), self.version)
+    }
+
+    fn lock(&self) -> Result<File, Error> {
+        lock_version(cache_type_as_str(self.cache_type), &self.version)
+    }
+
+    // download the prebuilt binary with retry.
+    fn download(&self, file_server: &str, staging: &Path) -> Result<(), Error> {
+        let name = self.file_name();
+        let checksum = fetch_checksum(file_server, &name)?;
+        let url = format!("{}/{}", file_server, name);
+
+        let mut retry = 0;
+        loop {
+            match self.fetch(&url, staging, &checksum) {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    DOWNLOAD_FAILURES.inc();
+                    retry += 1;
+                    if retry >= DOWNLOAD_RETRY {
+                        return Err(err);
+                    }
+                    warn!("fail to fetch {} due {}, retry {} times", url, err, retry);
+                    thread::sleep(Duration::from_secs(1 << retry));
+                }
+            }
+        }
+    }
+
+    // check the staging binary and rename it into place.
+    fn install(&self, staging: &Path) -> Result<(), Error> {
+        fs::set_permissions(staging, Permissions::from_mode(0o755))?;
+        if let Err(err) = check_version(staging) {
+            let _ = fs::remove_file(staging);
+            return Err(err);
+        }
+
+        fs::create_dir_all(self.dir())?;
+        fs::rename(staging, self.path())?;
+        info!("install binary {:?} done", self.path());
+        Ok(())
+    }
+
+    // download the file and check it's sha256, the broken file was removed
+    // so the next try starts over.
+    fn fetch(&self, url: &str, staging: &Path, checksum: &str) -> Result<(), Error> {
+        download(url, staging)?;
+        if let Err(err) = check_sha256(url, staging, checksum) {
+            fs::remove_file(staging)?;
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+/// flock `/data/haste/lib/{type}/.{version}.lock` until the returned file was dropped.
+/// the lock is released when the file was closed, even if the process crashed.
+pub fn lock_version(cache_type: &str, version: &str) -> Result<File, Error> {
+    if !is_version(version) || cache_type.contains('/') || cache_type.contains("..") {
+        return Err(format_err!("bad binary {}/{}", cache_type, version));
+    }
+    let mut pb = PathBuf::from(LIB_DIR);
+    pb.push(cache_type);
+    fs::create_dir_all(&pb)?;
+    pb.push(format!(".{}.lock", version));
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&pb)?;
+    info!("waiting for binary lock {:?}", pb);
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+/// the version is dotted numbers like 4.0.11, so it's safe to be a part of path.
+pub fn is_version(version: &str) -> bool {
+    let parts: Vec<_> = version.split('.').collect();
+    (2..=3).contains(&parts.len())
+        && parts
+            .iter()
+            .all(|x| !x.is_empty() && x.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// find the sha256 of name from the manifest of file server.
+pub fn fetch_checksum(file_server: &str, name: &str) -> Result<String, Error> {
+    let url = format!("{}/{}", file_server, MANIFEST_NAME);
+    let manifest = reqwest::get(&url)?.error_for_status()?.text()?;
+    find_checksum(&manifest, name).ok_or_else(|| format_err!("{} is not found in {}", name, url))
+}
+
+/// find the sha256 of name in the manifest, which is the output of `sha256sum`.
+pub fn find_checksum(manifest: &str, name: &str) -> Option<String> {
+    for line in manifest.lines() {
+        let mut iter = line.split_whitespace();
+        match (iter.next(), iter.next()) {
+            // the name was prefixed with '*' in binary mode
+            (Some(sum), Some(fname)) if fname.trim_start_matches('*') == name => {
+                return Some(sum.to_lowercase());
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// check the sha256 of the file downloaded from url, it's a `ChecksumMismatch` if differs.
+pub fn check_sha256(url: &str, path: &Path, checksum: &str) -> Result<(), Error> {
+    let actual = sha256_file(path)?;
+    if actual != checksum {
+        return Err(ChecksumMismatch {
+            url: url.to_string(),
+            expect: checksum.to_string(),
+            actual,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+// download url into path, resume it if the path was partial downloaded.
+fn download(url: &str, path: &Path) -> Result<(), Error> {
+    let offset = fs::metadata(path).map(|x| x.len()).unwrap_or(0);
+    let client = reqwest::Client::new();
+    let mut req = client.get(url);
+    if offset > 0 {
+        req = req.header(RANGE, format!("bytes={}-", offset));
+    }
+
+    let mut response = req.send()?;
+    let status = response.status();
+    let mut file = match status {
+        StatusCode::PARTIAL_CONTENT => {
+            info!("resume download {} from {} bytes", url, offset);
+            OpenOptions::new().append(true).open(path)?
+        }
+        // the file was fully downloaded, let checksum decide.
+        StatusCode::RANGE_NOT_SATISFIABLE => return Ok(()),
+        _ if status.is_success() => File::create(path)?,
+        _ => return Err(format_err!("download {} fail with status {}", url, status)),
+    };
+
+    let size = response.copy_to(&mut file)?;
+    DOWNLOAD_BYTES.inc_by(size as i64);
+    file.sync_all()?;
+    info!("download {} bytes to file {:?}", size, path);
+    Ok(())
+}
+
+fn sha256_file(path: &Path) -> Result<String, Error> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    let sum: Vec<_> = hasher
+        .result()
+        .iter()
+        .map(|x| format!("{:02x}", x))
+        .collect();
+    Ok(sum.join(""))
+}
+
+fn check_version(path: &Path) -> Result<(), Error> {
+    let output = Command::new(path).arg("--version").output()?;
+    if !output.status.success() {
+        return Err(format_err!(
+            "binary {:?} is not executable, exit with {} and stderr: {}",
+            path,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    info!(
+        "binary {:?} version: {}",
+        path,
+        String::from_utf8_lossy(&output.stdout).trim()
+    );
+    Ok(())
+}
+
+pub fn cache_type_as_binary_name(cache_type: CacheType) -> &'static str {
+    match cache_type {
+        CacheType::Memcache => "memcached",
+        _ => "redis-server",
+    }
+}
+
+pub fn cache_type_as_str(cache_type: CacheType) -> &'static str {
+    const CACHE_TYPE_REDIS: &str = "redis";
+    const CACHE_TYPE_MEMCACHE: &str = "memcache";
+
+    match cache_type {
+        CacheType::Memcache => CACHE_TYPE_MEMCACHE,
+        _ => CACHE_TYPE_REDIS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_dotted_versions() {
+        for version in &["4.0.11", "5.0", "1.5.12"] {
+            assert!(is_version(version), "{}", version);
+            assert!(Binary::new(CacheType::RedisCluster, version).is_ok());
+        }
+        for version in &["", "4", "4.0.11.1", "4..11", "../../etc", "4.0/../..", "4.0.x"] {
+            assert!(!is_version(version), "{}", version);
+            assert!(Binary::new(CacheType::RedisCluster, version).is_err());
+        }
+        assert!(lock_version("redis", "../../etc").is_err());
+        assert!(lock_version("../redis", "4.0.11").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! build cache binaries from source, for the hosts which need patched builds.
+//!
+//! the source tarball `{type}-{version}.tar.gz` is fetched from `source` (a url or a local dir,
+//! file server by default) and checked by the `SHA256SUMS` of source, then extracted and built
+//! in a temporary dir under `STAGING_DIR` with a clean environment. the output of the build is
+//! saved into `{log_dir}/{job_id}/`.
+
+use crate::deploy::binary::{
+    cache_type_as_binary_name, cache_type_as_str, check_sha256, fetch_checksum, find_checksum,
+    Binary, MANIFEST_NAME, STAGING_DIR,
+};
+use crate::job::check_job_id;
+use crate::proto::CacheType;
+
+use failure::{format_err, Error};
+use log::info;
+use reqwest;
+use serde_derive::{Deserialize, Serialize};
+use tempfile::Builder;
+
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub const BUILD_LOG_DIR: &str = "/data/haste/build";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildMode {
+    // only download prebuilt binaries
+    Never,
+    // build when the prebuilt binary can't be downloaded
+    Fallback,
+    // always build from source
+    Always,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildConfig {
+    pub mode: BuildMode,
+    // url or local dir of source tarballs, empty means file server
+    pub source: String,
+    pub make_flags: Vec<String>,
+    pub log_dir: String,
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        BuildConfig {
+            mode: BuildMode::Never,
+            source: String::new(),
+            make_flags: Vec::new(),
+            log_dir: BUILD_LOG_DIR.to_string(),
+        }
+    }
+}
+
+/// build the binary from source and copy it into out.
+pub fn build(
+    binary: &Binary,
+    config: &BuildConfig,
+    file_server: &str,
+    job_id: &str,
+    out: &Path,
+) -> Result<(), Error> {
+    // the job id is a part of the log path, it must not escape the log dir
+    check_job_id(job_id).map_err(|err| format_err!("reject job id {:?}: {}", job_id, err))?;
+    let cache_type = binary.cache_type();
+    let tarball = format!(
+        "{}-{}.tar.gz",
+        cache_type_as_str(cache_type),
+        binary.version()
+    );
+
+    fs::create_dir_all(STAGING_DIR)?;
+    let sandbox = Builder::new().prefix(".build-").tempdir_in(STAGING_DIR)?;
+    let src_dir = sandbox.path().join("src");
+    fs::create_dir_all(&src_dir)?;
+
+    let source = if config.source.is_empty() {
+        file_server
+    } else {
+        &config.source
+    };
+    let tarball_path = fetch_source(source, &tarball, sandbox.path())?;
+
+    let mut log_path = PathBuf::from(&config.log_dir);
+    log_path.push(job_id);
+    fs::create_dir_all(&log_path)?;
+    log_path.push(format!("build-{}.log", binary.file_name()));
+    info!("build {} from source, log into {:?}", tarball, log_path);
+    let log = File::create(&log_path)?;
+
+    run(
+        &log,
+        &src_dir,
+        "tar",
+        &[
+            "-xzf".to_string(),
+            tarball_path.to_string_lossy().to_string(),
+            "--strip-components=1".to_string(),
+        ],
+    )?;
+    // memcached need to be configured first
+    if src_dir.join("configure").exists() && !src_dir.join("Makefile").exists() {
+        run(&log, &src_dir, "./configure", &[])?;
+    }
+    run(&log, &src_dir, "make", &config.make_flags)?;
+
+    let built = src_dir.join(built_path(cache_type));
+    if !built.exists() {
+        return Err(format_err!(
+            "build of {} done but {:?} not found, see {:?}",
+            tarball,
+            built,
+            log_path
+        ));
+    }
+    fs::copy(&built, out)?;
+    Ok(())
+}
+
+// fetch the tarball into dir and check it by the manifest of source.
+fn fetch_source(source: &str, tarball: &str, dir: &Path) -> Result<PathBuf, Error> {
+    let path = dir.join(tarball);
+    let url = format!("{}/{}", source, tarball);
+    let checksum = if source.starts_with("http://") || source.starts_with("https://") {
+        let checksum = fetch_checksum(source, tarball)?;
+        let mut response = reqwest::get(&url)?.error_for_status()?;
+        let mut file = File::create(&path)?;
+        response.copy_to(&mut file)?;
+        checksum
+    } else {
+        let manifest = Path::new(source).join(MANIFEST_NAME);
+        let checksum = find_checksum(&fs::read_to_string(&manifest)?, tarball)
+            .ok_or_else(|| format_err!("{} is not found in {:?}", tarball, manifest))?;
+        fs::copy(Path::new(source).join(tarball), &path)?;
+        checksum
+    };
+    check_sha256(&url, &path, &checksum)?;
+    Ok(path)
+}
+
+// run the command in dir with clean environment, output is appended into log.
+fn run(log: &File, dir: &Path, cmd: &str, args: &[String]) -> Result<(), Error> {
+    let status = Command::new(cmd)
+        .args(args)
+        .current_dir(dir)
+        .env_clear()
+        .env("PATH", env::var("PATH").unwrap_or_default())
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(log.try_clone()?))
+        .stderr(Stdio::from(log.try_clone()?))
+        .status()?;
+    if !status.success() {
+        return Err(format_err!(
+            "build command {} {} exit with {}",
+            cmd,
+            args.join(" "),
+            status
+        ));
+    }
+    Ok(())
+}
+
+fn built_path(cache_type: CacheType) -> String {
+    match cache_type {
+        CacheType::Memcache => cache_type_as_binary_name(cache_type).to_string(),
+        _ => format!("src/{}", cache_type_as_binary_name(cache_type)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_bad_job_ids() {
+        let binary = Binary::new(CacheType::Redis, "4.0.11").unwrap();
+        let config = BuildConfig::default();
+        for job_id in &[
+            "",
+            "../../etc-1",
+            "mycluster",
+            "my/cluster-1",
+            "mycluster-1/..",
+        ] {
+            let err = build(&binary, &config, "", job_id, Path::new("/nonexistent")).unwrap_err();
+            assert!(err.to_string().starts_with("reject job id"), "{}", err);
+        }
+    }
+}
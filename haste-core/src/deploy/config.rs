@@ -0,0 +1,106 @@
+use crate::deploy::build::BuildConfig;
+use crate::deploy::gc::REFS_FILE;
+use crate::deploy::metrics::MetricsConfig;
+use crate::deploy::ports::PORTS_FILE;
+use crate::layout::DEFAULT_DATA_ROOT;
+use crate::supervisor::SupervisorConfig;
+
+use failure::Error;
+use serde_derive::{Deserialize, Serialize};
+
+use std::fs;
+use std::path::Path;
+
+pub const AGENT_CONFIG_FILE: &str = "/etc/haste/agent.toml";
+
+/// config of agent, loaded from toml file like:
+///
+/// ```toml
+/// host = "192.168.1.10"
+/// listen = "0.0.0.0"
+/// port = 7788
+/// etcd = "http://etcd.example.com:2379"
+/// cpu = 800
+/// memory = 65536
+/// port_begin = 7000
+/// port_end = 8000
+/// binary_grace = 604800
+/// data_root = "/data/cache"
+///
+/// [build]
+/// mode = "fallback"
+/// source = "/opt/haste/src"
+/// make_flags = ["-j4", "MALLOC=libc"]
+///
+/// [supervisor]
+/// backend = "exec"
+/// run_dir = "/data/haste/run"
+///
+/// [metrics]
+/// interval = 10
+/// history = 360
+/// listen = "0.0.0.0:7789"
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentConfig {
+    // the ip which server use to connect
+    pub host: String,
+    pub listen: String,
+    pub port: u16,
+    pub etcd: String,
+
+    // the total capacity reported by offer, cpu is a percentage value.
+    pub cpu: usize,
+    pub memory: usize,
+
+    // ports in [port_begin, port_end) are managed by agent
+    pub port_begin: usize,
+    pub port_end: usize,
+    pub ports_file: String,
+
+    // unreferenced binaries are purged after grace seconds
+    pub binary_grace: u64,
+    pub refs_file: String,
+
+    pub build: BuildConfig,
+    // how the instances are run, systemd by default
+    pub supervisor: SupervisorConfig,
+    // host and process metrics sampling
+    pub metrics: MetricsConfig,
+
+    // the root of instance dirs
+    pub data_root: String,
+    // rendered files can only be written under the dirs of layout and these extra dirs
+    pub allowed_roots: Vec<String>,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        AgentConfig {
+            host: "127.0.0.1".to_string(),
+            listen: "0.0.0.0".to_string(),
+            port: 7788,
+            etcd: "http://127.0.0.1:2379".to_string(),
+            cpu: 100,
+            memory: 1024,
+            port_begin: 7000,
+            port_end: 8000,
+            ports_file: PORTS_FILE.to_string(),
+            binary_grace: 7 * 24 * 3600,
+            refs_file: REFS_FILE.to_string(),
+            build: BuildConfig::default(),
+            supervisor: SupervisorConfig::default(),
+            metrics: MetricsConfig::default(),
+            data_root: DEFAULT_DATA_ROOT.to_string(),
+            allowed_roots: Vec::new(),
+        }
+    }
+}
+
+impl AgentConfig {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<AgentConfig, Error> {
+        let data = fs::read_to_string(path)?;
+        Ok(toml::from_str(&data)?)
+    }
+}
@@ -0,0 +1,291 @@
+//! crash safe config files of the agent.
+//!
+//! each file is written into a temp file in the same dir, synced and renamed into place, the
+//! previous version is kept as `{fpath}.bak`. after an instance was deployed, it's whole config
+//! set was saved as the last known good one in `/data/haste/backup/{port}.json`, which can be
+//! restored later.
+//!
+//! files are only written under the allowed roots of `FilePolicy`, which rejects `..`
+//! traversal, symlinks and the files which are not regular ones. the only files allowed in
+//! `UNIT_DIR` are the units of instances like `cache-{cluster}-{port}.service`.
+
+use crate::layout::UNIT_DIR;
+use crate::proto::File as ProtoFile;
+use crate::systemd::parse_service_name;
+
+use failure::{format_err, Error};
+use log::{debug, info};
+use serde_derive::{Deserialize, Serialize};
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Component, Path, PathBuf};
+
+pub const BACKUP_DIR: &str = "/data/haste/backup";
+// all rendered files are configs, they are never executable
+const FILE_MODE: u32 = 0o644;
+
+#[derive(Clone, Debug)]
+pub struct FilePolicy {
+    roots: Vec<PathBuf>,
+    unit_dir: PathBuf,
+}
+
+impl FilePolicy {
+    pub fn new<T: AsRef<str>>(roots: &[T]) -> FilePolicy {
+        FilePolicy {
+            roots: roots.iter().map(|x| PathBuf::from(x.as_ref())).collect(),
+            unit_dir: PathBuf::from(UNIT_DIR),
+        }
+    }
+
+    /// check all the files before write any of them.
+    pub fn check_all(&self, files: &[ProtoFile]) -> Result<(), Error> {
+        for file in files {
+            self.check(file.get_fpath())
+                .map_err(|err| format_err!("reject file {}: {}", file.get_fpath(), err))?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, fpath: &str) -> Result<(), Error> {
+        let path = Path::new(fpath);
+        if !path.is_absolute() {
+            return Err(format_err!("path must be absolute"));
+        }
+        if path
+            .components()
+            .any(|x| x == Component::ParentDir || x == Component::CurDir)
+        {
+            return Err(format_err!("path must not contain '.' or '..'"));
+        }
+
+        if path.parent() == Some(self.unit_dir.as_path()) {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if parse_service_name(&name).is_none() {
+                return Err(format_err!(
+                    "only the units of instances can be written into {:?}",
+                    self.unit_dir
+                ));
+            }
+        } else {
+            self.check_under_root(path)?;
+        }
+
+        if let Ok(meta) = fs::symlink_metadata(path) {
+            if !meta.file_type().is_file() {
+                return Err(format_err!("target exists but is not a regular file"));
+            }
+            let mode = meta.permissions().mode() & 0o7777;
+            if mode & 0o7111 != 0 {
+                return Err(format_err!("unexpected file mode {:o}", mode));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_under_root(&self, path: &Path) -> Result<(), Error> {
+        let root = self
+            .roots
+            .iter()
+            .find(|root| path.starts_with(root) && path != root.as_path())
+            .ok_or_else(|| format_err!("path is not under allowed roots {:?}", self.roots))?;
+
+        // every existing component under root must not be a symlink,
+        // or the write may escape from root.
+        let mut current = root.clone();
+        for component in path.strip_prefix(root)?.components() {
+            current.push(component);
+            let meta = match fs::symlink_metadata(&current) {
+                Ok(meta) => meta,
+                Err(_) => break,
+            };
+            if meta.file_type().is_symlink() {
+                return Err(format_err!("{:?} is a symlink", current));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GoodFile {
+    pub fpath: String,
+    pub content: String,
+}
+
+/// write the file atomically and keep the previous one as backup.
+pub fn write_file(file: &ProtoFile) -> Result<(), Error> {
+    debug!(
+        "create file {} with content {}",
+        file.get_fpath(),
+        file.get_content()
+    );
+    let path = Path::new(file.get_fpath());
+    if path.exists() {
+        let bak = with_suffix(path, "bak")?;
+        write_atomic(&bak, fs::read(path)?.as_slice())?;
+    }
+    write_atomic(path, file.get_content().as_bytes())
+}
+
+/// save the config set of the instance as the last known good one.
+pub fn save_good(port: i64, files: &[ProtoFile]) -> Result<(), Error> {
+    let good: Vec<_> = files
+        .iter()
+        .map(|x| GoodFile {
+            fpath: x.get_fpath().to_string(),
+            content: x.get_content().to_string(),
+        })
+        .collect();
+    let data = serde_json::to_string(&good)?;
+    write_atomic(&good_path(port), data.as_bytes())
+}
+
+pub fn load_good(port: i64) -> Result<Vec<ProtoFile>, Error> {
+    let path = good_path(port);
+    if !path.exists() {
+        return Err(format_err!("no known good config of instance {}", port));
+    }
+    let good: Vec<GoodFile> = serde_json::from_reader(File::open(&path)?)?;
+    info!("load {} known good files of instance {}", good.len(), port);
+    Ok(good
+        .into_iter()
+        .map(|x| {
+            let mut file = ProtoFile::new();
+            file.set_fpath(x.fpath);
+            file.set_content(x.content);
+            file
+        })
+        .collect())
+}
+
+// write into {path}.tmp, fsync and rename it, then sync the dir to persist the rename.
+fn write_atomic(path: &Path, data: &[u8]) -> Result<(), Error> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| format_err!("bad file path {:?}", path))?;
+    fs::create_dir_all(dir)?;
+
+    // never follow the left temp file, it may be a symlink
+    let tmp = with_suffix(path, "tmp")?;
+    if fs::symlink_metadata(&tmp).is_ok() {
+        fs::remove_file(&tmp)?;
+    }
+    {
+        let mut fp = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(FILE_MODE)
+            .open(&tmp)?;
+        fp.write_all(data)?;
+        fp.sync_all()?;
+    }
+    fs::rename(&tmp, path)?;
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> Result<PathBuf, Error> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format_err!("bad file path {:?}", path))?;
+    Ok(path.with_file_name(format!("{}.{}", name.to_string_lossy(), suffix)))
+}
+
+fn good_path(port: i64) -> PathBuf {
+    Path::new(BACKUP_DIR).join(format!("{}.json", port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    fn policy(root: &TempDir, units: &TempDir) -> FilePolicy {
+        FilePolicy {
+            roots: vec![root.path().to_path_buf()],
+            unit_dir: units.path().to_path_buf(),
+        }
+    }
+
+    fn check(policy: &FilePolicy, path: &Path) -> Result<(), Error> {
+        policy.check(&path.to_string_lossy())
+    }
+
+    #[test]
+    fn allow_files_under_root() {
+        let (root, units) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let policy = policy(&root, &units);
+        assert!(check(&policy, &root.path().join("7000/redis.conf")).is_ok());
+
+        fs::create_dir(root.path().join("7001")).unwrap();
+        fs::write(root.path().join("7001/redis.conf"), "port 7001").unwrap();
+        fs::set_permissions(
+            root.path().join("7001/redis.conf"),
+            fs::Permissions::from_mode(0o644),
+        )
+        .unwrap();
+        assert!(check(&policy, &root.path().join("7001/redis.conf")).is_ok());
+    }
+
+    #[test]
+    fn reject_parent_dir() {
+        let (root, units) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let policy = policy(&root, &units);
+        assert!(check(&policy, &root.path().join("7000/../../etc/passwd")).is_err());
+        assert!(policy.check("7000/redis.conf").is_err());
+    }
+
+    #[test]
+    fn reject_outside_root() {
+        let (root, units) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let policy = policy(&root, &units);
+        assert!(policy.check("/etc/passwd").is_err());
+        assert!(check(&policy, root.path()).is_err());
+    }
+
+    #[test]
+    fn reject_symlink() {
+        let (root, units) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let outside = TempDir::new().unwrap();
+        let policy = policy(&root, &units);
+
+        symlink(outside.path(), root.path().join("7000")).unwrap();
+        assert!(check(&policy, &root.path().join("7000/redis.conf")).is_err());
+
+        fs::create_dir(root.path().join("7001")).unwrap();
+        symlink(
+            outside.path().join("passwd"),
+            root.path().join("7001/redis.conf"),
+        )
+        .unwrap();
+        assert!(check(&policy, &root.path().join("7001/redis.conf")).is_err());
+
+        symlink(
+            outside.path().join("sshd.service"),
+            units.path().join("cache-mycluster-7000.service"),
+        )
+        .unwrap();
+        assert!(check(&policy, &units.path().join("cache-mycluster-7000.service")).is_err());
+    }
+
+    #[test]
+    fn only_units_of_instances() {
+        let (root, units) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let policy = policy(&root, &units);
+        assert!(check(&policy, &units.path().join("cache-mycluster-7000.service")).is_ok());
+        assert!(check(&policy, &units.path().join("cache-7000.service")).is_ok());
+        assert!(check(&policy, &units.path().join("sshd.service")).is_err());
+        assert!(check(&policy, &units.path().join("cache-mycluster-7000.timer")).is_err());
+        assert!(check(
+            &policy,
+            &units
+                .path()
+                .join("multi-user.target.wants/cache-mycluster-7000.service")
+        )
+        .is_err());
+    }
+}
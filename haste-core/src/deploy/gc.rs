@@ -0,0 +1,379 @@
+//! garbage collection of the cached binaries in `/data/haste/lib/{type}/{version}`.
+//!
+//! the agent records which binary each deployed instance references in `REFS_FILE`.
+//! a version which is not referenced by any instance for longer than the grace period
+//! will be removed. the instances which were deployed before refs file exists are adopted
+//! by the binary in their units.
+
+use crate::deploy::binary::{cache_type_as_str, is_version, lock_version, Binary};
+use crate::deploy::plan::parse_cache_type;
+use crate::layout::{LIB_DIR, UNIT_DIR};
+use crate::proto::CachedBinary;
+use crate::systemd::list_units;
+
+use failure::Error;
+use log::{info, warn};
+use serde_derive::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{self, SystemTime};
+
+pub const REFS_FILE: &str = "/data/haste/lib/refs.json";
+
+#[derive(Default, Serialize, Deserialize)]
+struct Refs {
+    // port -> {type}/{version}
+    insts: BTreeMap<usize, String>,
+    // {type}/{version} -> the last time it was referenced
+    last_used: BTreeMap<String, u64>,
+}
+
+pub struct BinaryRefs {
+    path: PathBuf,
+    refs: Refs,
+}
+
+impl BinaryRefs {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<BinaryRefs, Error> {
+        let path = path.as_ref().to_path_buf();
+        let refs = if path.exists() {
+            serde_json::from_reader(File::open(&path)?)?
+        } else {
+            Refs::default()
+        };
+        let mut binary_refs = BinaryRefs { path, refs };
+        binary_refs.adopt_instances()?;
+        Ok(binary_refs)
+    }
+
+    /// mark the binary was used by the instances on ports, return the binaries which they
+    /// referenced before, so they can be put back by `restore` if the deploy fails.
+    pub fn reference(
+        &mut self,
+        binary: &Binary,
+        ports: &[usize],
+    ) -> Result<Vec<(usize, Option<String>)>, Error> {
+        let now = now();
+        let key = binary.key();
+        let mut prev = Vec::new();
+        for port in ports {
+            let old = self.refs.insts.insert(*port, key.clone());
+            if let Some(ref old) = old {
+                self.refs.last_used.insert(old.clone(), now);
+            }
+            prev.push((*port, old));
+        }
+        self.refs.last_used.insert(key, now);
+        self.save()?;
+        Ok(prev)
+    }
+
+    /// put back the references returned by `reference` after a failed deploy. the instance
+    /// references the binary in it's unit if the unit was written, or the one before.
+    pub fn restore(&mut self, prev: &[(usize, Option<String>)]) -> Result<(), Error> {
+        let now = now();
+        let units: BTreeMap<_, _> = list_units()?
+            .into_iter()
+            .map(|unit| (unit.port as usize, unit.name))
+            .collect();
+        for (port, key) in prev {
+            let key = units
+                .get(port)
+                .and_then(|name| fs::read_to_string(Path::new(UNIT_DIR).join(name)).ok())
+                .and_then(|content| unit_binary(&content))
+                .or_else(|| key.clone());
+            let replaced = match key {
+                Some(key) => self.refs.insts.insert(*port, key),
+                None => self.refs.insts.remove(port),
+            };
+            if let Some(replaced) = replaced {
+                self.refs.last_used.insert(replaced, now);
+            }
+        }
+        self.save()
+    }
+
+    /// the instances on ports were removed.
+    pub fn release(&mut self, ports: &[usize]) -> Result<(), Error> {
+        let now = now();
+        for port in ports {
+            if let Some(key) = self.refs.insts.remove(port) {
+                self.refs.last_used.insert(key, now);
+            }
+        }
+        self.save()
+    }
+
+    /// all the binaries in `LIB_DIR`.
+    pub fn list(&self) -> Result<Vec<CachedBinary>, Error> {
+        let mut binaries = Vec::new();
+        for (cache_type, version) in scan_lib_dir()? {
+            let key = format!("{}/{}", cache_type, version);
+            let ports: Vec<_> = self
+                .refs
+                .insts
+                .iter()
+                .filter(|(_, v)| **v == key)
+                .map(|(port, _)| *port as i64)
+                .collect();
+
+            let mut cb = CachedBinary::new();
+            cb.set_cache_type(cache_type);
+            cb.set_version(version);
+            cb.set_ports(ports);
+            cb.set_last_used(self.last_used(&key)? as i64);
+            binaries.push(cb);
+        }
+        Ok(binaries)
+    }
+
+    /// remove the binaries which were unreferenced for longer than grace seconds,
+    /// return the removed (or would be removed if dry_run) ones.
+    pub fn purge(&mut self, grace: u64, dry_run: bool) -> Result<Vec<CachedBinary>, Error> {
+        let now = now();
+        let mut purged = Vec::new();
+        for cb in self.list()? {
+            if !cb.get_ports().is_empty() || cb.get_last_used() as u64 + grace > now {
+                continue;
+            }
+            if !dry_run {
+                self.remove(cb.get_cache_type(), cb.get_version())?;
+            }
+            purged.push(cb);
+        }
+        if !dry_run {
+            self.save()?;
+        }
+        Ok(purged)
+    }
+
+    fn remove(&mut self, cache_type: &str, version: &str) -> Result<(), Error> {
+        // never remove a binary which is being installed
+        let _lock = lock_version(cache_type, version)?;
+        let mut dir = PathBuf::from(LIB_DIR);
+        dir.push(cache_type);
+        dir.push(version);
+        fs::remove_dir_all(&dir)?;
+        self.refs
+            .last_used
+            .remove(&format!("{}/{}", cache_type, version));
+        info!("purge unreferenced binary {:?}", dir);
+        Ok(())
+    }
+
+    // reference the binaries of the units which are not recorded, so the versions which are
+    // still used by old instances are never purged.
+    fn adopt_instances(&mut self) -> Result<(), Error> {
+        let now = now();
+        for unit in list_units()? {
+            let port = unit.port as usize;
+            if self.refs.insts.contains_key(&port) {
+                continue;
+            }
+            let content = match fs::read_to_string(Path::new(UNIT_DIR).join(&unit.name)) {
+                Ok(content) => content,
+                Err(err) => {
+                    warn!("fail to read unit {} due {}", unit.name, err);
+                    continue;
+                }
+            };
+            match unit_binary(&content) {
+                Some(key) => {
+                    info!("adopt binary {} of instance on port {}", key, port);
+                    self.refs.last_used.insert(key.clone(), now);
+                    self.refs.insts.insert(port, key);
+                }
+                None => warn!("unknown binary of unit {}", unit.name),
+            }
+        }
+        self.save()
+    }
+
+    // the binaries which were installed before refs file exists use the mtime of it's dir.
+    fn last_used(&self, key: &str) -> Result<u64, Error> {
+        if let Some(ts) = self.refs.last_used.get(key) {
+            return Ok(*ts);
+        }
+        let modified = fs::metadata(Path::new(LIB_DIR).join(key))?.modified()?;
+        Ok(modified.duration_since(time::UNIX_EPOCH)?.as_secs())
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(serde_json::to_string(&self.refs)?.as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+// the {type}/{version} of binary which the unit runs, it's found by the path of ExecStart
+// or the metadata of unit.
+fn unit_binary(content: &str) -> Option<String> {
+    let mut cache_type = None;
+    let mut version = None;
+    for line in content.lines() {
+        let mut kv = line.trim().splitn(2, '=');
+        match (kv.next().map(str::trim_end), kv.next()) {
+            (Some("ExecStart"), Some(value)) => {
+                // the prefixes like '-' and '@' change how the command is run
+                let exec = value.trim().trim_start_matches(|c| "-@+!:".contains(c));
+                // the quoted path may contain spaces, the quotes are not a part of it
+                let path = match exec.chars().next() {
+                    Some(quote) if quote == '"' || quote == '\'' => {
+                        exec[1..].find(quote).map(|end| &exec[1..=end])
+                    }
+                    _ => exec.split_whitespace().next(),
+                };
+                let binary = Path::new(path.unwrap_or(""));
+                if let Ok(rel) = binary.strip_prefix(LIB_DIR) {
+                    let parts: Vec<_> = rel.iter().map(|x| x.to_string_lossy()).collect();
+                    if parts.len() == 3 {
+                        return Some(format!("{}/{}", parts[0], parts[1]));
+                    }
+                }
+            }
+            (Some("X-Haste-CacheType"), Some(value)) => {
+                cache_type = parse_cache_type(value.trim()).ok();
+            }
+            (Some("X-Haste-Version"), Some(value)) => version = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    match (cache_type, version) {
+        (Some(cache_type), Some(version)) => {
+            Some(format!("{}/{}", cache_type_as_str(cache_type), version))
+        }
+        _ => None,
+    }
+}
+
+fn scan_lib_dir() -> Result<Vec<(String, String)>, Error> {
+    scan_dir(Path::new(LIB_DIR))
+}
+
+// list all (type, version) in root, hidden files like locks and staging and the dirs which
+// are not versions are skipped.
+fn scan_dir(root: &Path) -> Result<Vec<(String, String)>, Error> {
+    let mut binaries = Vec::new();
+    let types = match fs::read_dir(root) {
+        Ok(types) => types,
+        Err(err) => {
+            warn!("fail to read lib dir {:?} due {}", root, err);
+            return Ok(binaries);
+        }
+    };
+
+    for tentry in types {
+        let tentry = tentry?;
+        let cache_type = tentry.file_name().to_string_lossy().to_string();
+        if cache_type.starts_with('.') || !tentry.file_type()?.is_dir() {
+            continue;
+        }
+
+        for ventry in fs::read_dir(tentry.path())? {
+            let ventry = ventry?;
+            let version = ventry.file_name().to_string_lossy().to_string();
+            if version.starts_with('.') || !ventry.file_type()?.is_dir() {
+                continue;
+            }
+            if !is_version(&version) {
+                warn!("skip unknown dir {:?} in lib dir", ventry.path());
+                continue;
+            }
+            binaries.push((cache_type.clone(), version));
+        }
+    }
+    binaries.sort();
+    Ok(binaries)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::CacheType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn binary_of_exec_start() {
+        for exec in &[
+            "ExecStart=/data/haste/lib/redis/4.0.11/redis-server /data/haste/cache/7000/redis.conf",
+            "ExecStart=\"/data/haste/lib/redis/4.0.11/redis-server\" \"/data/haste/cache/7000/redis.conf\"",
+            "ExecStart='/data/haste/lib/redis/4.0.11/redis-server' /data/haste/cache/7000/redis.conf",
+            "ExecStart=-/data/haste/lib/redis/4.0.11/redis-server /data/haste/cache/7000/redis.conf",
+            "ExecStart=@\"/data/haste/lib/redis/4.0.11/redis-server\" redis-7000",
+            "  ExecStart = /data/haste/lib/redis/4.0.11/redis-server",
+        ] {
+            let unit = format!("[Unit]\nDescription=cache 7000\n\n[Service]\n{}\nRestart=always\n", exec);
+            assert_eq!(unit_binary(&unit).as_deref(), Some("redis/4.0.11"), "{}", exec);
+        }
+        let unit =
+            "[Service]\nExecStart=/data/haste/lib/memcache/1.5.12/memcached -p 11211 -m 1024\n";
+        assert_eq!(unit_binary(unit).as_deref(), Some("memcache/1.5.12"));
+    }
+
+    #[test]
+    fn binary_of_unit_metadata() {
+        let unit = "[Unit]\nX-Haste-CacheType=redis_cluster\nX-Haste-Version=4.0.11\n\n\
+                    [Service]\nExecStart=/usr/local/bin/redis-server /data/cache/7000/redis.conf\n";
+        assert_eq!(
+            unit_binary(unit).as_deref(),
+            Some(&*format!(
+                "{}/4.0.11",
+                cache_type_as_str(CacheType::RedisCluster)
+            ))
+        );
+        for unit in &[
+            "[Service]\nExecStart=/usr/local/bin/redis-server /data/cache/7000/redis.conf\n",
+            "[Service]\nExecStart=/data/haste/lib/redis/redis-server\n",
+            "[Service]\nExecStart=\"/data/haste/lib/redis/4.0.11/redis-server\n",
+            "[Service]\nExecStart=/data/haste/library/redis/4.0.11/redis-server\n",
+            "[Unit]\nX-Haste-Version=4.0.11\n",
+        ] {
+            assert_eq!(unit_binary(unit), None, "{}", unit);
+        }
+    }
+
+    #[test]
+    fn scan_versions() {
+        let root = TempDir::new().unwrap();
+        for dir in &[
+            "redis/4.0.11",
+            "redis/3.2.12",
+            "redis/.staging-4.0.12",
+            "redis/latest",
+            "memcache/1.5.12",
+            ".staging/4.0.11",
+        ] {
+            fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        fs::write(root.path().join("redis/.lock"), "").unwrap();
+        fs::write(root.path().join("redis/5.0.0"), "").unwrap();
+        fs::write(root.path().join("README"), "").unwrap();
+
+        let binaries = scan_dir(root.path()).unwrap();
+        let expected = vec![
+            ("memcache".to_string(), "1.5.12".to_string()),
+            ("redis".to_string(), "3.2.12".to_string()),
+            ("redis".to_string(), "4.0.11".to_string()),
+        ];
+        assert_eq!(binaries, expected);
+        assert!(scan_dir(&root.path().join("missing")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,427 @@
+//! host and process metrics sampled by agent.
+//!
+//! every `interval` seconds the agent samples the cpu, memory, network and load of host from
+//! /proc, the disk usage of data root by `df`, and each instance by the pid of it's unit. the
+//! latest `history` samples are kept in memory for the `Metrics` rpc, and the free cpu and memory
+//! are reported in the offer.
+
+use crate::deploy::status::proc_stat;
+use crate::offer::Usage;
+use crate::proto::{HostMetrics, MetricsSample, ProcessMetrics};
+use crate::supervisor::Supervisor;
+use crate::systemd::list_units;
+
+use failure::{format_err, Error};
+use log::warn;
+use serde_derive::{Deserialize, Serialize};
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{self, Instant, SystemTime};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    // seconds between samples
+    pub interval: u64,
+    // how many samples are kept
+    pub history: usize,
+    // prometheus metrics are served on it, disabled if empty
+    pub listen: String,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        MetricsConfig {
+            interval: 10,
+            history: 360,
+            listen: "0.0.0.0:7789".to_string(),
+        }
+    }
+}
+
+// the counters of last sample, the rates are calculated by them
+#[derive(Default)]
+struct Counters {
+    at: Option<Instant>,
+    cpu_busy: u64,
+    cpu_total: u64,
+    net_rx: u64,
+    net_tx: u64,
+    // pid -> cpu time in milliseconds
+    procs: HashMap<u32, u64>,
+}
+
+pub struct MetricsCollector {
+    data_root: String,
+    supervisor: Arc<dyn Supervisor>,
+    history: usize,
+    samples: VecDeque<MetricsSample>,
+    last: Counters,
+}
+
+impl MetricsCollector {
+    pub fn new(
+        config: &MetricsConfig,
+        data_root: &str,
+        supervisor: Arc<dyn Supervisor>,
+    ) -> MetricsCollector {
+        MetricsCollector {
+            data_root: data_root.to_string(),
+            supervisor,
+            history: config.history,
+            samples: VecDeque::with_capacity(config.history),
+            last: seed_counters(),
+        }
+    }
+
+    /// take a sample and push it into the ring buffer, the oldest one is dropped if full.
+    pub fn sample(&mut self) -> Result<MetricsSample, Error> {
+        let at = Instant::now();
+        let mut counters = Counters {
+            at: Some(at),
+            ..Counters::default()
+        };
+        // the rates are zero if the counters were not seeded
+        let elapsed_ms = self
+            .last
+            .at
+            .map(|last| at.duration_since(last).as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut host = HostMetrics::new();
+        let (busy, total, cpus) = read_cpu()?;
+        counters.cpu_busy = busy;
+        counters.cpu_total = total;
+        let busy_delta = busy.saturating_sub(self.last.cpu_busy);
+        let total_delta = total.saturating_sub(self.last.cpu_total);
+        if let (Some(_), Some(used)) = (
+            self.last.at,
+            (busy_delta * 100 * cpus).checked_div(total_delta),
+        ) {
+            host.set_cpu_used(used as i64);
+        }
+        host.set_cpu_total((cpus * 100) as i64);
+
+        let (mem_total, mem_available) = read_memory()?;
+        host.set_memory_total(mem_total as i64);
+        host.set_memory_used(mem_total.saturating_sub(mem_available) as i64);
+
+        match read_disk(&self.data_root) {
+            Ok((disk_total, disk_used)) => {
+                host.set_disk_total(disk_total as i64);
+                host.set_disk_used(disk_used as i64);
+            }
+            Err(err) => warn!("fail to sample disk of {} due {}", self.data_root, err),
+        }
+
+        let (rx, tx) = read_net()?;
+        counters.net_rx = rx;
+        counters.net_tx = tx;
+        host.set_net_rx(rate(rx.saturating_sub(self.last.net_rx) * 1000, elapsed_ms));
+        host.set_net_tx(rate(tx.saturating_sub(self.last.net_tx) * 1000, elapsed_ms));
+
+        let loads = read_loadavg()?;
+        host.set_load1(loads[0]);
+        host.set_load5(loads[1]);
+        host.set_load15(loads[2]);
+
+        let mut insts = Vec::new();
+        for unit in list_units()? {
+            let pid = match self.supervisor.status(&unit.name) {
+                Ok(status) if status.pid != 0 => status.pid,
+                Ok(_) => continue,
+                Err(err) => {
+                    warn!("fail to get status of unit {} due {}", unit.name, err);
+                    continue;
+                }
+            };
+            let stat = match proc_stat(pid) {
+                Ok(stat) => stat,
+                Err(err) => {
+                    warn!("fail to sample process {} due {}", pid, err);
+                    continue;
+                }
+            };
+
+            let mut pm = ProcessMetrics::new();
+            pm.set_port(unit.port);
+            pm.set_pid(i64::from(pid));
+            pm.set_rss(stat.rss as i64);
+            if let Some(last) = self.last.procs.get(&pid) {
+                pm.set_cpu(rate(stat.cpu_time.saturating_sub(*last) * 100, elapsed_ms));
+            }
+            counters.procs.insert(pid, stat.cpu_time);
+            insts.push(pm);
+        }
+
+        let mut sample = MetricsSample::new();
+        sample.set_timestamp(now() as i64);
+        sample.set_host(host);
+        sample.set_insts(insts.into());
+
+        self.last = counters;
+        if self.samples.len() >= self.history {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample.clone());
+        Ok(sample)
+    }
+
+    /// the samples taken after since.
+    pub fn since(&self, since: i64) -> Vec<MetricsSample> {
+        self.samples
+            .iter()
+            .filter(|x| x.get_timestamp() > since)
+            .cloned()
+            .collect()
+    }
+}
+
+/// the free resources of host in the units of offer.
+pub fn usage_of(sample: &MetricsSample) -> Usage {
+    let host = sample.get_host();
+    Usage {
+        timestamp: sample.get_timestamp() as u64,
+        cpu: (host.get_cpu_total() - host.get_cpu_used()).max(0) as usize,
+        memory: ((host.get_memory_total() - host.get_memory_used()).max(0) / 1024 / 1024) as usize,
+    }
+}
+
+// the counters when the collector was created, so the first sample has the rates since then
+// instead of the averages since boot.
+fn seed_counters() -> Counters {
+    match (read_cpu(), read_net()) {
+        (Ok((cpu_busy, cpu_total, _)), Ok((net_rx, net_tx))) => Counters {
+            at: Some(Instant::now()),
+            cpu_busy,
+            cpu_total,
+            net_rx,
+            net_tx,
+            procs: HashMap::new(),
+        },
+        (Err(err), _) | (_, Err(err)) => {
+            warn!("fail to seed metrics counters due {}", err);
+            Counters::default()
+        }
+    }
+}
+
+// value per elapsed milliseconds, 0 if nothing elapsed
+fn rate(value: u64, elapsed_ms: u64) -> i64 {
+    value.checked_div(elapsed_ms).unwrap_or(0) as i64
+}
+
+fn read_cpu() -> Result<(u64, u64, u64), Error> {
+    parse_cpu(&fs::read_to_string("/proc/stat")?)
+}
+
+// (busy, total) jiffies of all the cpus and the count of cpus from /proc/stat
+fn parse_cpu(stat: &str) -> Result<(u64, u64, u64), Error> {
+    let mut busy_total = None;
+    let mut cpus = 0;
+    for line in stat.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("cpu") => {
+                let values: Vec<u64> = fields.filter_map(|x| x.parse().ok()).collect();
+                // user nice system idle iowait irq softirq steal ...
+                if values.len() < 5 {
+                    return Err(format_err!("bad cpu line of /proc/stat: {}", line));
+                }
+                // guest times are counted in user and nice
+                let total: u64 = values.iter().take(8).sum();
+                let idle = values[3] + values[4];
+                busy_total = Some((total - idle, total));
+            }
+            Some(name) if name.starts_with("cpu") => cpus += 1,
+            _ => {}
+        }
+    }
+    let (busy, total) = busy_total.ok_or_else(|| format_err!("cpu not found in /proc/stat"))?;
+    Ok((busy, total, cpus.max(1)))
+}
+
+fn read_memory() -> Result<(u64, u64), Error> {
+    parse_memory(&fs::read_to_string("/proc/meminfo")?)
+}
+
+// (total, available) bytes from /proc/meminfo
+fn parse_memory(meminfo: &str) -> Result<(u64, u64), Error> {
+    let mut total = None;
+    let mut available = None;
+    for line in meminfo.lines() {
+        let mut fields = line.split_whitespace();
+        let (key, value) = match (fields.next(), fields.next()) {
+            (Some(key), Some(value)) => (key, value.parse::<u64>()? * 1024),
+            _ => continue,
+        };
+        match key {
+            "MemTotal:" => total = Some(value),
+            "MemAvailable:" => available = Some(value),
+            _ => {}
+        }
+    }
+    match (total, available) {
+        (Some(total), Some(available)) => Ok((total, available)),
+        _ => Err(format_err!(
+            "MemTotal or MemAvailable not found in /proc/meminfo"
+        )),
+    }
+}
+
+// (total, used) bytes of the filesystem of path by `df -Pk`
+fn read_disk(path: &str) -> Result<(u64, u64), Error> {
+    let output = Command::new("df").arg("-Pk").arg(path).output()?;
+    if !output.status.success() {
+        return Err(format_err!(
+            "df exit with {} and stderr: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    parse_df(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_df(stdout: &str) -> Result<(u64, u64), Error> {
+    // Filesystem 1024-blocks Used Available Capacity Mounted on
+    let fields: Vec<_> = stdout
+        .lines()
+        .nth(1)
+        .ok_or_else(|| format_err!("bad output of df: {}", stdout))?
+        .split_whitespace()
+        .collect();
+    if fields.len() < 3 {
+        return Err(format_err!("bad output of df: {}", stdout));
+    }
+    Ok((
+        fields[1].parse::<u64>()? * 1024,
+        fields[2].parse::<u64>()? * 1024,
+    ))
+}
+
+fn read_net() -> Result<(u64, u64), Error> {
+    Ok(parse_net(&fs::read_to_string("/proc/net/dev")?))
+}
+
+// received and transmitted bytes of all the interfaces except lo from /proc/net/dev
+fn parse_net(dev: &str) -> (u64, u64) {
+    let (mut rx, mut tx) = (0, 0);
+    // the first 2 lines are headers
+    for line in dev.lines().skip(2) {
+        let pos = match line.find(':') {
+            Some(pos) => pos,
+            None => continue,
+        };
+        if line[..pos].trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = line[pos + 1..]
+            .split_whitespace()
+            .filter_map(|x| x.parse().ok())
+            .collect();
+        if fields.len() >= 9 {
+            rx += fields[0];
+            tx += fields[8];
+        }
+    }
+    (rx, tx)
+}
+
+fn read_loadavg() -> Result<Vec<f64>, Error> {
+    parse_loadavg(&fs::read_to_string("/proc/loadavg")?)
+}
+
+fn parse_loadavg(loadavg: &str) -> Result<Vec<f64>, Error> {
+    let loads: Vec<f64> = loadavg
+        .split_whitespace()
+        .take(3)
+        .filter_map(|x| x.parse().ok())
+        .collect();
+    if loads.len() < 3 {
+        return Err(format_err!("bad /proc/loadavg: {}", loadavg));
+    }
+    Ok(loads)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_proc_stat() {
+        let stat = "cpu  10132153 290696 3084719 46828483 16683 0 25195 0 0 0\n\
+                    cpu0 1393280 32966 572056 13343292 6130 0 17875 0 0 0\n\
+                    cpu1 1335331 32893 547930 13386983 4220 0 1474 0 0 0\n\
+                    intr 199292308 45 0 0 0 0 0 0 0 1 0 0 0 0\n\
+                    ctxt 439227442\n\
+                    btime 1541484352\n\
+                    processes 2296384\n";
+        let total = 10132153 + 290696 + 3084719 + 46828483 + 16683 + 25195;
+        let busy = total - 46828483 - 16683;
+        assert_eq!(parse_cpu(stat).unwrap(), (busy, total, 2));
+
+        // the old kernels have no steal and guest times
+        assert_eq!(parse_cpu("cpu  10 0 10 70 10\n").unwrap(), (20, 100, 1));
+        assert!(parse_cpu("cpu  10 0 10\n").is_err());
+        assert!(parse_cpu("cpu0 10 0 10 70 10\n").is_err());
+    }
+
+    #[test]
+    fn parse_proc_meminfo() {
+        let meminfo = "MemTotal:       16318644 kB\n\
+                       MemFree:          553720 kB\n\
+                       MemAvailable:    9472536 kB\n\
+                       Buffers:          981532 kB\n\
+                       HugePages_Total:       0\n";
+        assert_eq!(
+            parse_memory(meminfo).unwrap(),
+            (16318644 * 1024, 9472536 * 1024)
+        );
+        assert!(parse_memory("MemTotal:       16318644 kB\n").is_err());
+        assert!(parse_memory("MemTotal:       many kB\n").is_err());
+    }
+
+    #[test]
+    fn parse_proc_net_dev() {
+        let dev = "Inter-|   Receive                                                |  Transmit\n \
+                   face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n    \
+                   lo: 2776770   11307    0    0    0     0          0         0  2776770   11307    0    0    0     0       0          0\n  \
+                   eth0: 1215645    2751    0    0    0     0          0         0  1782404    4324    0    0    0   427       0          0\n  \
+                   eth1:1000 10 0 0 0 0 0 0 2000 20 0 0 0 0 0 0\n";
+        assert_eq!(parse_net(dev), (1215645 + 1000, 1782404 + 2000));
+        assert_eq!(parse_net(""), (0, 0));
+    }
+
+    #[test]
+    fn parse_proc_loadavg() {
+        assert_eq!(
+            parse_loadavg("0.20 0.18 0.12 1/80 11206\n").unwrap(),
+            vec![0.20, 0.18, 0.12]
+        );
+        assert!(parse_loadavg("0.20 0.18\n").is_err());
+    }
+
+    #[test]
+    fn parse_df_output() {
+        let stdout = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/sdb1       1921802520 512034700 1312398240      29% /data\n";
+        assert_eq!(
+            parse_df(stdout).unwrap(),
+            (1921802520 * 1024, 512034700 * 1024)
+        );
+        assert!(
+            parse_df("Filesystem     1024-blocks      Used Available Capacity Mounted on\n")
+                .is_err()
+        );
+    }
+}
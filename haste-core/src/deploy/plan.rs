@@ -0,0 +1,196 @@
+//! deploy plan is the result of `DeployTask::plan`.
+//!
+//! It contains the computed chunks, the cache info (with rendered files) which would be sent to
+//! each agent and the etcd writes which would be done after deploy. No agent is contacted while
+//! planning, so operators may review it first and apply it later by `DeployTask::apply`.
+
+use crate::chunk::Chunks;
+use crate::deploy::server::{CacheInfos, DeployParm};
+use crate::proto::{CacheInfo, CacheType, File, Instance};
+
+use failure::{format_err, Error};
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeployPlan {
+    pub job_id: String,
+    pub param: DeployParm,
+    pub chunks: Chunks,
+    pub hosts: Vec<HostPlan>,
+    pub etcd_writes: Vec<EtcdWrite>,
+}
+
+impl DeployPlan {
+    pub fn new(
+        job_id: String,
+        param: DeployParm,
+        chunks: Chunks,
+        cache_infos: &CacheInfos,
+        etcd_writes: Vec<EtcdWrite>,
+    ) -> DeployPlan {
+        let mut hosts: Vec<_> = cache_infos
+            .iter()
+            .map(|(host, ci)| HostPlan::new(host, ci))
+            .collect();
+        hosts.sort_by(|x, y| x.host.cmp(&y.host));
+        DeployPlan {
+            job_id,
+            param,
+            chunks,
+            hosts,
+            etcd_writes,
+        }
+    }
+
+    /// rebuild the exactly cache infos which will be sent to agents.
+    pub fn cache_infos(&self) -> CacheInfos {
+        self.hosts
+            .iter()
+            .map(|hp| (hp.host.clone(), hp.as_cache_info()))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(data: &str) -> Result<DeployPlan, Error> {
+        Ok(serde_json::from_str(data)?)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HostPlan {
+    pub host: String,
+    pub job_id: String,
+    #[serde(with = "serde_cache_type")]
+    pub cache_type: CacheType,
+    pub cluster: String,
+    pub version: String,
+    pub file_server: String,
+    pub insts: Vec<InstancePlan>,
+}
+
+impl HostPlan {
+    pub fn new(host: &str, ci: &CacheInfo) -> HostPlan {
+        let insts = ci
+            .get_insts()
+            .iter()
+            .map(|inst| InstancePlan {
+                port: inst.get_port(),
+                files: inst
+                    .get_files()
+                    .iter()
+                    .map(|f| FilePlan {
+                        fpath: f.get_fpath().to_string(),
+                        content: f.get_content().to_string(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        HostPlan {
+            host: host.to_string(),
+            job_id: ci.get_job_id().to_string(),
+            cache_type: ci.get_cache_type(),
+            cluster: ci.get_cluster().to_string(),
+            version: ci.get_version().to_string(),
+            file_server: ci.get_file_server().to_string(),
+            insts,
+        }
+    }
+
+    pub fn as_cache_info(&self) -> CacheInfo {
+        let mut ci = CacheInfo::new();
+        ci.set_job_id(self.job_id.clone());
+        ci.set_cache_type(self.cache_type);
+        ci.set_cluster(self.cluster.clone());
+        ci.set_version(self.version.clone());
+        ci.set_file_server(self.file_server.clone());
+
+        let insts: Vec<_> = self
+            .insts
+            .iter()
+            .map(|ip| {
+                let files: Vec<_> = ip
+                    .files
+                    .iter()
+                    .map(|fp| {
+                        let mut file = File::new();
+                        file.set_fpath(fp.fpath.clone());
+                        file.set_content(fp.content.clone());
+                        file
+                    })
+                    .collect();
+                let mut inst = Instance::new();
+                inst.set_port(ip.port);
+                inst.set_files(files.into());
+                inst
+            })
+            .collect();
+        ci.set_insts(insts.into());
+        ci
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstancePlan {
+    pub port: i64,
+    pub files: Vec<FilePlan>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FilePlan {
+    pub fpath: String,
+    pub content: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EtcdWrite {
+    pub key: String,
+    pub value: String,
+}
+
+impl EtcdWrite {
+    pub fn new<K: Into<String>, V: Into<String>>(key: K, value: V) -> EtcdWrite {
+        EtcdWrite {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// the name of cache type which was saved in etcd and plans.
+pub fn cache_type_name(cache_type: CacheType) -> &'static str {
+    match cache_type {
+        CacheType::Redis => "redis",
+        CacheType::RedisCluster => "redis_cluster",
+        CacheType::Memcache => "memcache",
+    }
+}
+
+pub fn parse_cache_type(name: &str) -> Result<CacheType, Error> {
+    match name {
+        "redis" => Ok(CacheType::Redis),
+        "redis_cluster" => Ok(CacheType::RedisCluster),
+        "memcache" => Ok(CacheType::Memcache),
+        _ => Err(format_err!("unknown cache type {}", name)),
+    }
+}
+
+pub mod serde_cache_type {
+    use super::{cache_type_name, parse_cache_type};
+    use crate::proto::CacheType;
+
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(cache_type: &CacheType, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(cache_type_name(*cache_type))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<CacheType, D::Error> {
+        let name = String::deserialize(d)?;
+        parse_cache_type(&name).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,215 @@
+//! port allocator of the agent.
+//!
+//! allocations are saved in `PORTS_FILE`, so they survive restarts of the agent:
+//!   * `Leased` ports were handed out by `GetPorts` but not deployed yet, they are
+//!     expired after `LEASE_TTL` seconds.
+//!   * `Deployed` ports are held until the instance was removed, even if it's stopped.
+//!
+//! a port is only handed out when both itself and it's redis cluster bus port
+//! (port + 10000) can be bound.
+
+use crate::layout::InstanceLayout;
+
+use failure::{format_err, Error};
+use log::info;
+use serde_derive::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::time::{self, SystemTime};
+
+pub const PORTS_FILE: &str = "/data/haste/ports.json";
+/// the cluster bus port of redis cluster is always port + 10000
+pub const BUS_PORT_OFFSET: usize = 10000;
+// leased but never deployed ports are reclaimed after one hour
+const LEASE_TTL: u64 = 3600;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PortState {
+    Leased,
+    Deployed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Allocation {
+    pub state: PortState,
+    pub updated_at: u64,
+}
+
+impl Allocation {
+    fn new(state: PortState) -> Allocation {
+        Allocation {
+            state,
+            updated_at: now(),
+        }
+    }
+
+    fn is_expired(&self, now: u64) -> bool {
+        self.state == PortState::Leased && self.updated_at + LEASE_TTL < now
+    }
+}
+
+pub struct PortAllocator {
+    // ports in [begin, end) can be handed out
+    begin: usize,
+    end: usize,
+    path: PathBuf,
+    allocs: BTreeMap<usize, Allocation>,
+}
+
+impl PortAllocator {
+    /// load the allocations from path, the instances which were deployed before
+    /// the file exists are adopted as `Deployed`.
+    pub fn load<P: AsRef<Path>>(
+        begin: usize,
+        end: usize,
+        path: P,
+        layout: &InstanceLayout,
+    ) -> Result<PortAllocator, Error> {
+        if begin == 0 || begin >= end || end + BUS_PORT_OFFSET > 65536 {
+            return Err(format_err!(
+                "bad port range [{}, {}), bus port must be less than 65536",
+                begin,
+                end
+            ));
+        }
+
+        let path = path.as_ref().to_path_buf();
+        let allocs = if path.exists() {
+            let file = File::open(&path)?;
+            serde_json::from_reader(file)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut allocator = PortAllocator {
+            begin,
+            end,
+            path,
+            allocs,
+        };
+        allocator.adopt_instances(layout)?;
+        Ok(allocator)
+    }
+
+    /// hand out count ports which are really free and lease them.
+    pub fn acquire(&mut self, count: usize) -> Result<Vec<usize>, Error> {
+        self.expire();
+
+        let mut ports = Vec::with_capacity(count);
+        for port in self.begin..self.end {
+            if ports.len() == count {
+                break;
+            }
+            if self.is_held(port) || !is_bindable(port) || !is_bindable(port + BUS_PORT_OFFSET) {
+                continue;
+            }
+            self.allocs.insert(port, Allocation::new(PortState::Leased));
+            ports.push(port);
+        }
+
+        if ports.len() < count {
+            for port in &ports {
+                self.allocs.remove(port);
+            }
+            return Err(format_err!(
+                "only {} free ports left but {} required",
+                ports.len(),
+                count
+            ));
+        }
+
+        self.save()?;
+        info!("lease ports {:?}", ports);
+        Ok(ports)
+    }
+
+    /// hold the ports until they are released.
+    pub fn mark_deployed(&mut self, ports: &[usize]) -> Result<(), Error> {
+        for port in ports {
+            self.allocs
+                .insert(*port, Allocation::new(PortState::Deployed));
+        }
+        self.save()
+    }
+
+    pub fn release(&mut self, ports: &[usize]) -> Result<(), Error> {
+        for port in ports {
+            self.allocs.remove(port);
+        }
+        self.save()
+    }
+
+    /// the ports which can be bound now or are leased to an in-flight job. the deployed
+    /// ports are never offered, even if their instances are stopped.
+    pub fn offered(&self) -> Vec<usize> {
+        let now = now();
+        (self.begin..self.end)
+            .filter(|port| match self.allocs.get(port) {
+                Some(alloc) => alloc.state == PortState::Leased && !alloc.is_expired(now),
+                None => {
+                    !self.is_held(*port)
+                        && is_bindable(*port)
+                        && is_bindable(port + BUS_PORT_OFFSET)
+                }
+            })
+            .collect()
+    }
+
+    // port is held if itself or it's bus port conflicts with any allocation.
+    fn is_held(&self, port: usize) -> bool {
+        self.allocs.contains_key(&port)
+            || self.allocs.contains_key(&(port + BUS_PORT_OFFSET))
+            || (port >= BUS_PORT_OFFSET && self.allocs.contains_key(&(port - BUS_PORT_OFFSET)))
+    }
+
+    fn expire(&mut self) {
+        let now = now();
+        self.allocs.retain(|port, alloc| {
+            let expired = alloc.is_expired(now);
+            if expired {
+                info!("lease of port {} was expired", port);
+            }
+            !expired
+        });
+    }
+
+    fn adopt_instances(&mut self, layout: &InstanceLayout) -> Result<(), Error> {
+        for port in layout.instance_ports()? {
+            self.allocs.entry(port as usize).or_insert_with(|| {
+                info!("adopt deployed instance on port {}", port);
+                Allocation::new(PortState::Deployed)
+            });
+        }
+        self.save()
+    }
+
+    // write into a temporary file and rename it, so the file is never half written.
+    fn save(&self) -> Result<(), Error> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(serde_json::to_string(&self.allocs)?.as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn is_bindable(port: usize) -> bool {
+    TcpListener::bind(("0.0.0.0", port as u16)).is_ok()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
@@ -1,24 +1,41 @@
-use crate::chunk::{chunk_it, Chunks};
+use crate::chunk::{chunk_it, Chunks, Instance as ChunkInstance, ROLE_MASTER};
+use crate::deploy::binary::{
+    cache_type_as_binary_name, cache_type_as_str, fetch_checksum, is_version, Binary,
+};
+use crate::deploy::plan::{cache_type_name, DeployPlan, EtcdWrite};
+use crate::exporter::DEPLOY_FAILURES;
+use crate::job::gen_job_id;
+use crate::layout::InstanceLayout;
+use crate::ledger;
 use crate::myetcd::MyEtcd;
+use crate::mymemcache::MyMemcache;
 use crate::myredis::MyRedis;
-use crate::offer::fetch_offer;
-use crate::proto::{CacheInfo, CacheType, File, Instance};
+use crate::offer::{fetch_offer, get_capacity};
+use crate::proto::{Action, CacheInfo, CacheType, File, Instance, State};
 use crate::proto_grpc::AgentClient;
-use crate::systemd::service_name;
 
-use etcd::kv::{KeyValueInfo, Node};
-use etcd::Response;
-use failure::{format_err, Error};
+use failure::{format_err, Error, Fail};
 use grpcio::{ChannelBuilder, EnvBuilder};
 use log::{error, info, warn};
+use serde_derive::{Deserialize, Serialize};
 use tera::{Context, Tera};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-type CacheInfos = HashMap<String, CacheInfo>;
+pub type CacheInfos = HashMap<String, CacheInfo>;
+
+const DEFAULT_RETRY: usize = 3;
+const MAX_NAME_LEN: usize = 64;
+const MAX_REDIS_CPU_PERCENT: usize = 100;
+pub const FILE_SERVER_KEY: &str = "/haste/file_server";
+// the state of instance in etcd, it's stopped if operator stopped it by api
+pub const STATE_ONLINE: &str = "online";
+pub const STATE_STOPPED: &str = "stopped";
 
 // etcd path
 //  /haste/clusters/name/instances/{ip}:{port}/[state,role,slaveof,slots]
@@ -27,10 +44,14 @@ type CacheInfos = HashMap<String, CacheInfo>;
 //                      /audit/{task_id}/[checkpoint, state]
 //                      /feport
 //                      /config/[dial_timeout,fetch_interval]
+//                      /lock -> {kind} {lock_id} of the deploy, upgrade or reconfig job
 //  /haste/appids/{appid}/{cluster_name}/[config]/[dial_timeout,fetch_interval]
 //  /haste/templates/cache_type/name/
 //  /haste/agent/{ip} -> port
-#[derive(Clone, Debug)]
+//  /haste/offers/{ip} -> json of offer
+//  /haste/ledger/{ip} -> json of reservations
+//  /haste/file_server -> url of binaries
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DeployParm {
     pub name: String,
 
@@ -42,6 +63,7 @@ pub struct DeployParm {
     pub total_memory: usize,
     pub version: String,
     pub tpl_name: String,
+    #[serde(with = "crate::deploy::plan::serde_cache_type")]
     pub cache_type: CacheType,
     pub appids: String,
     pub group: String,
@@ -50,93 +72,374 @@ pub struct DeployParm {
     pub write_timeout: Option<u64>,
 }
 
+impl DeployParm {
+    /// check the param before any resource is reserved. all the problems are reported
+    /// together as an `InvalidParm`, the other errors are from etcd or file server.
+    pub fn validate(&self, myetcd: &MyEtcd) -> Result<(), Error> {
+        let mut problems = self.problems();
+        // the template and binary are only looked up by the valid names
+        if check_name("template", &self.tpl_name).is_ok() {
+            problems.extend(self.template_problem(myetcd)?);
+        }
+        if is_version(&self.version) {
+            problems.extend(version_problem(myetcd, self.cache_type, &self.version)?);
+        }
+        self.invalid(problems)
+    }
+
+    // all the problems as one `InvalidParm`, it's ok if there is none.
+    fn invalid(&self, problems: Vec<String>) -> Result<(), Error> {
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(InvalidParm {
+            name: self.name.clone(),
+            problems,
+        }
+        .into())
+    }
+
+    // the problems which are found without etcd.
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Err(problem) = check_name("cluster", &self.name) {
+            problems.push(problem);
+        }
+        if self.max_memory == 0 {
+            problems.push("max_memory must be greater than 0".to_string());
+        } else if self.total_memory < self.max_memory {
+            problems.push(format!(
+                "total_memory {} must not be less than max_memory {}",
+                self.total_memory, self.max_memory
+            ));
+        }
+        match self.cache_type {
+            CacheType::Memcache if self.cpu_percent == 0 => {
+                problems.push("cpu_percent must be set for memcache".to_string())
+            }
+            CacheType::Memcache => {}
+            _ if self.cpu_percent > MAX_REDIS_CPU_PERCENT => problems.push(format!(
+                "cpu_percent {} must not be greater than {} due redis is single threaded",
+                self.cpu_percent, MAX_REDIS_CPU_PERCENT
+            )),
+            _ => {}
+        }
+        problems.extend(appids_problems(&self.appids));
+        let timeouts = [
+            ("dial_timeout", self.dial_timeout),
+            ("read_timeout", self.read_timeout),
+            ("write_timeout", self.write_timeout),
+        ];
+        for (name, timeout) in &timeouts {
+            if *timeout == Some(0) {
+                problems.push(format!("{} must be greater than 0", name));
+            }
+        }
+        if let Err(problem) = check_name("template", &self.tpl_name) {
+            problems.push(problem);
+        }
+        if !is_version(&self.version) {
+            problems.push(format!("version {:?} must be like 4.0.11", self.version));
+        }
+        problems
+    }
+
+    fn template_problem(&self, myetcd: &MyEtcd) -> Result<Option<String>, Error> {
+        let files = template_files(self.cache_type);
+        let cache_type = cache_type_name(self.cache_type);
+        let mut missing = Vec::new();
+        for file in files {
+            let key = format!("/haste/templates/{}/{}/{}", cache_type, self.tpl_name, file);
+            if myetcd.get_value(&key)?.is_none() {
+                missing.push(*file);
+            }
+        }
+
+        if missing.len() == files.len() {
+            return Ok(Some(format!(
+                "template {} of {} is not found",
+                self.tpl_name, cache_type
+            )));
+        }
+        if !missing.is_empty() {
+            return Ok(Some(format!(
+                "template {} of {} misses {}",
+                self.tpl_name,
+                cache_type,
+                missing.join(", ")
+            )));
+        }
+        Ok(None)
+    }
+}
+
+/// all the problems of a `DeployParm`.
+#[derive(Debug, Fail)]
+pub struct InvalidParm {
+    pub name: String,
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for InvalidParm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid param of cluster {}: {}",
+            self.name,
+            self.problems.join("; ")
+        )
+    }
+}
+
 pub struct Template {
     tera: Tera,
 }
-#[allow(unused)]
+
+// template files of each cache type, saved in etcd as
+//   /haste/templates/{cache_type}/{tpl_name}/{file}
+const REDIS_TEMPLATES: &[&str] = &["redis.conf", "cache.service"];
+const MEMCACHE_TEMPLATES: &[&str] = &["cache.service"];
+
 impl Template {
-    //  * /etc/systemd/system/cache-{port}.service
+    pub fn load(myetcd: &MyEtcd, cache_type: CacheType, tpl_name: &str) -> Result<Template, Error> {
+        let mut tera = Tera::default();
+        for name in template_files(cache_type) {
+            let key = format!(
+                "/haste/templates/{}/{}/{}",
+                cache_type_name(cache_type),
+                tpl_name,
+                name
+            );
+            let content = myetcd
+                .get_value(&key)?
+                .ok_or_else(|| format_err!("template file {} not found", key))?;
+            tera.add_raw_template(name, &content)
+                .map_err(|err| format_err!("fail to parse template {} due {}", key, err))?;
+        }
+        Ok(Template { tera })
+    }
+
+    /// parse the template from the files of (name, content).
+    pub fn parse<'a, I>(files: I) -> Result<Template, Error>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut tera = Tera::default();
+        for (name, content) in files {
+            tera.add_raw_template(name, content)
+                .map_err(|err| format_err!("fail to parse template {} due {}", name, err))?;
+        }
+        Ok(Template { tera })
+    }
+
+    /// render the files of a sample instance, so the template which refers to any unknown
+    /// variable is found before it's saved.
+    pub fn check(&self, cache_type: CacheType) -> Result<(), Error> {
+        let param = DeployParm {
+            name: "sample".to_string(),
+            cpu_percent: 100,
+            max_memory: 1024,
+            total_memory: 2048,
+            version: "4.0.11".to_string(),
+            tpl_name: "sample".to_string(),
+            cache_type,
+            appids: String::new(),
+            group: String::new(),
+            dial_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+        };
+        let chunks = Chunks(vec![ChunkInstance {
+            host: "127.0.0.1".to_string(),
+            port: 7000,
+            role: ROLE_MASTER.to_string(),
+            slaveof: "-".to_string(),
+            runid: "0".repeat(40),
+            slots: Vec::new(),
+        }]);
+        let layout = InstanceLayout::default();
+        self.render(&layout, &chunks, "127.0.0.1", 7000, &param)
+            .map(|_| ())
+    }
+
+    /// render the files of the instance host:port by the cache type of param.
+    pub fn render(
+        &self,
+        layout: &InstanceLayout,
+        chunks: &Chunks,
+        host: &str,
+        port: usize,
+        param: &DeployParm,
+    ) -> Result<Vec<File>, Error> {
+        match param.cache_type {
+            CacheType::RedisCluster => self.render_cluster(layout, chunks, host, port, param),
+            CacheType::Redis => self.render_redis(layout, port, param),
+            CacheType::Memcache => self.render_memcache(layout, port, param),
+        }
+    }
+
+    //  * /etc/systemd/system/cache-{cluster}-{port}.service
+    //    ** cluster: String
     //    ** port: uszie
     //    ** version: uszie
     //    ** thread: uszie
     //    ** max_memory: uszie
-    fn render_memcache(&self, host: &str, port: usize, param: &DeployParm) -> Vec<File> {
+    //    ** dir: String
+    //    ** binary: String
+    fn render_memcache(
+        &self,
+        layout: &InstanceLayout,
+        port: usize,
+        param: &DeployParm,
+    ) -> Result<Vec<File>, Error> {
         let thread = (param.cpu_percent + 99) / 100;
-        let mut service_ctx = Context::new();
-        service_ctx.insert("port", &port);
-        service_ctx.insert("version", &param.version);
+        let mut service_ctx = self.service_context(layout, port, param);
         service_ctx.insert("max_memory", &param.max_memory);
         service_ctx.insert("thread", &thread);
 
-        let mut csf = File::new();
-        csf.set_fpath(format!("/etc/systemd/system/{}", service_name(port as i64)));
-        let content = self.tera.render("cache.service", &service_ctx).unwrap();
-        csf.set_content(content);
-        vec![csf]
+        Ok(vec![self.render_service(
+            layout,
+            port,
+            param,
+            &service_ctx,
+        )?])
     }
 
     // need render keys:
-    //  * /data/cache/{port}/redis.conf
-    //  * /etc/systemd/system/cache-{port}.service
-    fn render_redis(&self, host: &str, port: usize, param: &DeployParm) -> Vec<File> {
-        let mut port_ctx = Context::new();
-        port_ctx.insert("port", &port);
-
-        let mut rcf = File::new();
-        rcf.set_fpath(format!("/data/cache/{port}/redis.conf", port = port));
-        let redis_conf = self.tera.render("redis.conf", &port_ctx).unwrap();
-        rcf.set_content(redis_conf);
-
-        let mut service_ctx = Context::new();
-        service_ctx.insert("port", &port);
-        service_ctx.insert("version", &param.version);
-
-        let mut csf = File::new();
-        csf.set_fpath(format!("/etc/systemd/system/{}", service_name(port as i64)));
-        let content = self.tera.render("cache.service", &service_ctx).unwrap();
-        csf.set_content(content);
-        vec![rcf, csf]
+    //  * {data_root}/{port}/redis.conf
+    //    ** port: usize
+    //    ** dir: String
+    //  * /etc/systemd/system/cache-{cluster}-{port}.service
+    pub fn render_redis(
+        &self,
+        layout: &InstanceLayout,
+        port: usize,
+        param: &DeployParm,
+    ) -> Result<Vec<File>, Error> {
+        let rcf = self.render_redis_conf(layout, port)?;
+        let service_ctx = self.service_context(layout, port, param);
+        let csf = self.render_service(layout, port, param, &service_ctx)?;
+        Ok(vec![rcf, csf])
     }
 
     // need render keys:
-    //  * /data/cache/{port}/nodes.conf
-    //  * /data/cache/{port}/redis.conf
+    //  * {data_root}/{port}/nodes.conf
+    //  * {data_root}/{port}/redis.conf
     //    ** port: usize
-    //  * /etc/systemd/system/cache-{port}.service
+    //    ** dir: String
+    //  * /etc/systemd/system/cache-{cluster}-{port}.service
+    //    ** cluster: String
     //    ** port: usize
     //    ** version: String
+    //    ** dir: String
+    //    ** conf: String
+    //    ** binary: String
     fn render_cluster(
         &self,
+        layout: &InstanceLayout,
         chunks: &Chunks,
         host: &str,
         port: usize,
         param: &DeployParm,
-    ) -> Vec<File> {
+    ) -> Result<Vec<File>, Error> {
         let mut ncf = File::new();
-        ncf.set_fpath(format!("/data/cache/{port}/nodes.conf", port = port));
+        ncf.set_fpath(path_str(layout.nodes_conf(port as i64)));
         let nodes_conf = chunks.as_nodes_conf(host, port);
         ncf.set_content(nodes_conf);
 
+        let rcf = self.render_redis_conf(layout, port)?;
+        let service_ctx = self.service_context(layout, port, param);
+        let csf = self.render_service(layout, port, param, &service_ctx)?;
+        Ok(vec![ncf, rcf, csf])
+    }
+
+    fn render_redis_conf(&self, layout: &InstanceLayout, port: usize) -> Result<File, Error> {
         let mut port_ctx = Context::new();
         port_ctx.insert("port", &port);
+        port_ctx.insert("dir", &path_str(layout.instance_dir(port as i64)));
 
         let mut rcf = File::new();
-        rcf.set_fpath(format!("/data/cache/{port}/redis.conf", port = port));
-        let redis_conf = self.tera.render("redis.conf", &port_ctx).unwrap();
-        rcf.set_content(redis_conf);
+        rcf.set_fpath(path_str(layout.redis_conf(port as i64)));
+        rcf.set_content(self.render_file("redis.conf", &port_ctx)?);
+        Ok(rcf)
+    }
 
+    fn service_context(&self, layout: &InstanceLayout, port: usize, param: &DeployParm) -> Context {
+        let binary = layout.binary_path(
+            cache_type_as_str(param.cache_type),
+            &param.version,
+            cache_type_as_binary_name(param.cache_type),
+        );
         let mut service_ctx = Context::new();
+        service_ctx.insert("cluster", &param.name);
         service_ctx.insert("port", &port);
         service_ctx.insert("version", &param.version);
+        service_ctx.insert("dir", &path_str(layout.instance_dir(port as i64)));
+        service_ctx.insert("conf", &path_str(layout.redis_conf(port as i64)));
+        service_ctx.insert("binary", &path_str(binary));
+        service_ctx
+    }
 
+    fn render_service(
+        &self,
+        layout: &InstanceLayout,
+        port: usize,
+        param: &DeployParm,
+        ctx: &Context,
+    ) -> Result<File, Error> {
         let mut csf = File::new();
-        csf.set_fpath(format!("/etc/systemd/system/{}", service_name(port as i64)));
-        let content = self.tera.render("cache.service", &service_ctx).unwrap();
-        csf.set_content(content);
-        vec![ncf, rcf, csf]
+        csf.set_fpath(path_str(layout.unit_path(&param.name, port as i64)));
+        let content = self.render_file("cache.service", ctx)?;
+        let metadata = [
+            ("X-Haste-Cluster", param.name.clone()),
+            ("X-Haste-Port", format!("{}", port)),
+            (
+                "X-Haste-CacheType",
+                cache_type_name(param.cache_type).to_string(),
+            ),
+            ("X-Haste-Version", param.version.clone()),
+        ];
+        csf.set_content(with_unit_metadata(&content, &metadata));
+        Ok(csf)
+    }
+
+    fn render_file(&self, name: &str, ctx: &Context) -> Result<String, Error> {
+        self.tera
+            .render(name, ctx)
+            .map_err(|err| format_err!("fail to render template file {} due {}", name, err))
     }
 }
 
+// add the metadata into [Unit] section, systemd ignores the keys prefixed with X-.
+fn with_unit_metadata(content: &str, metadata: &[(&str, String)]) -> String {
+    let lines: Vec<_> = metadata
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    let mut out = Vec::new();
+    let mut inserted = false;
+    for line in content.lines() {
+        out.push(line.to_string());
+        if !inserted && line.trim() == "[Unit]" {
+            out.extend(lines.iter().cloned());
+            inserted = true;
+        }
+    }
+    if !inserted {
+        let mut head = vec!["[Unit]".to_string()];
+        head.extend(lines);
+        head.append(&mut out);
+        out = head;
+    }
+    let mut rendered = out.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+fn path_str(path: PathBuf) -> String {
+    path.to_string_lossy().to_string()
+}
+
 pub struct DeployTask {
     retry: usize,
     param: DeployParm,
@@ -145,33 +448,131 @@ pub struct DeployTask {
 }
 
 impl DeployTask {
+    pub fn new(param: DeployParm, myetcd: MyEtcd) -> DeployTask {
+        DeployTask {
+            retry: DEFAULT_RETRY,
+            param,
+            myredis: MyRedis::default(),
+            myetcd,
+        }
+    }
+
     pub fn deploy(&mut self) -> Result<(), Error> {
-        info!("start to deploy cluster with param {:?}", self.param);
+        let plan = self.plan()?;
+        self.apply(&plan)
+    }
+
+    /// plan computes chunks, renders files and collects etcd writes
+    /// without contacting any agent.
+    pub fn plan(&mut self) -> Result<DeployPlan, Error> {
+        info!("start to plan cluster with param {:?}", self.param);
+        self.param.validate(&self.myetcd)?;
 
+        let job_id = gen_job_id(&self.param.name);
         let chunks = self.create_chunks()?;
-        self.myredis.set_chunks(&chunks);
+        ledger::reserve(&self.myetcd, &job_id, &self.param, &chunks)?;
+
+        let template = match self.load_template() {
+            Ok(template) => template,
+            Err(err) => {
+                self.rollback_ledger(&job_id, &chunks);
+                return Err(err);
+            }
+        };
+        let cache_infos = match self.chunks_as_cache_infos(&job_id, &chunks, &template) {
+            Ok(cache_infos) => cache_infos,
+            Err(err) => {
+                self.rollback_ledger(&job_id, &chunks);
+                return Err(err);
+            }
+        };
+        let etcd_writes = self.etcd_writes(&job_id, &chunks);
 
-        let template = self.load_template()?;
-        let cache_infos = self.chunks_as_cache_infos(&chunks, &template);
+        Ok(DeployPlan::new(
+            job_id,
+            self.param.clone(),
+            chunks,
+            &cache_infos,
+            etcd_writes,
+        ))
+    }
+
+    /// apply deploys the plan exactly as it was shown.
+    pub fn apply(&mut self, plan: &DeployPlan) -> Result<(), Error> {
+        info!(
+            "start to apply plan {} of cluster {}",
+            plan.job_id, plan.param.name
+        );
+        self.param = plan.param.clone();
+        self.myredis.set_chunks(&plan.chunks)?;
+
+        // the reservation may be expired if the plan is too old.
+        if let Err(err) = ledger::reserve(&self.myetcd, &plan.job_id, &plan.param, &plan.chunks) {
+            self.rollback_ledger(&plan.job_id, &plan.chunks);
+            return Err(failed("reserve", err));
+        }
 
-        self.retry_deploy(&cache_infos)?;
+        let cache_infos = plan.cache_infos();
+        if let Err(err) = self.retry_deploy(&cache_infos) {
+            self.rollback_ledger(&plan.job_id, &plan.chunks);
+            return Err(failed("deploy", err));
+        }
 
         thread::sleep(Duration::from_secs(1));
 
         if let Err(err) = self.check_all_done(&cache_infos) {
             warn!("fail to check all cluster done due {}", err);
-            return self.send_clean();
+            // the instances are left running until they can be cleaned, so their
+            // reservations are kept
+            self.commit_ledger(&plan.job_id, &plan.chunks);
+            return Err(failed("check", err));
         }
 
+        // the instances are running from here, so their reservations are committed
+        // even if the cluster can't be balanced or saved.
         if let CacheType::RedisCluster = self.param.cache_type {
-            self.balance()?;
+            if let Err(err) = self.balance() {
+                self.commit_ledger(&plan.job_id, &plan.chunks);
+                return Err(failed("balance", err));
+            }
         }
 
-        self.save_into_etcd(&chunks, &template)?;
+        if let Err(err) = self.save_into_etcd(&plan.etcd_writes) {
+            self.commit_ledger(&plan.job_id, &plan.chunks);
+            return Err(failed("save", err));
+        }
+        ledger::commit(&self.myetcd, &plan.job_id, &plan.chunks)
+            .map_err(|err| failed("commit", err))?;
         Ok(())
     }
 
-    fn chunks_as_cache_infos(&mut self, chunks: &Chunks, template: &Template) -> CacheInfos {
+    /// discard the plan which will never be applied and release it's reservations.
+    pub fn discard(&mut self, plan: &DeployPlan) -> Result<(), Error> {
+        ledger::rollback(&self.myetcd, &plan.job_id, &plan.chunks)
+    }
+
+    fn commit_ledger(&self, job_id: &str, chunks: &Chunks) {
+        if let Err(err) = ledger::commit(&self.myetcd, job_id, chunks) {
+            error!("fail to commit reservations of job {} due {}", job_id, err);
+        }
+    }
+
+    fn rollback_ledger(&self, job_id: &str, chunks: &Chunks) {
+        if let Err(err) = ledger::rollback(&self.myetcd, job_id, chunks) {
+            error!(
+                "fail to rollback reservations of job {} due {}",
+                job_id, err
+            );
+        }
+    }
+
+    fn chunks_as_cache_infos(
+        &mut self,
+        job_id: &str,
+        chunks: &Chunks,
+        template: &Template,
+    ) -> Result<CacheInfos, Error> {
+        let file_server = self.myetcd.get_value(FILE_SERVER_KEY)?.unwrap_or_default();
         let mut inst_map = HashMap::new();
         for inst in chunks.0.iter() {
             let handle = inst_map
@@ -182,32 +583,45 @@ impl DeployTask {
         inst_map
             .into_iter()
             .map(|(host, insts)| {
+                let layout = self.layout_of(&host)?;
                 let mut info = CacheInfo::new();
+                info.set_job_id(job_id.to_string());
                 info.set_cache_type(self.param.cache_type);
                 info.set_cluster(self.param.name.clone());
                 info.set_version(self.param.version.clone());
+                info.set_file_server(file_server.clone());
 
                 let mut instances = Vec::new();
                 for i in &insts[..] {
-                    let files = match self.param.cache_type {
-                        CacheType::RedisCluster => {
-                            template.render_cluster(chunks, &i.host, i.port, &self.param)
-                        }
-                        _ => unreachable!(),
-                    };
+                    let files = template.render(&layout, chunks, &i.host, i.port, &self.param)?;
                     let mut instance = Instance::new();
                     instance.set_port(i.port as i64);
                     instance.set_files(files.into());
                     instances.push(instance);
                 }
                 info.set_insts(instances.into());
-                (host, info)
+                Ok((host, info))
             })
             .collect()
     }
 
+    // the layout reported by the offer of agent
+    fn layout_of(&self, host: &str) -> Result<InstanceLayout, Error> {
+        let layout = get_capacity(&self.myetcd, host)?
+            .map(|offer| InstanceLayout::new(&offer.data_root))
+            .unwrap_or_default();
+        Ok(layout)
+    }
+
     fn retry_deploy(&mut self, cache_infos: &CacheInfos) -> Result<(), Error> {
+        let mut cache_infos = cache_infos.clone();
         for i in 1..=self.retry {
+            if i > 1 {
+                // never wipe the instances which were deployed in the last try
+                for ci in cache_infos.values_mut() {
+                    ci.set_converge(true);
+                }
+            }
             if let Err(err) = self.send_deploy(cache_infos.clone()) {
                 warn!(
                     "fail to create cluster {:?} in retry {} due to {}",
@@ -230,6 +644,7 @@ impl DeployTask {
     }
 
     fn check_all_done(&mut self, cache_infos: &CacheInfos) -> Result<(), Error> {
+        let cache_type = self.param.cache_type;
         let ths: Vec<_> = cache_infos
             .iter()
             .map(|(host, ci)| {
@@ -237,7 +652,13 @@ impl DeployTask {
                 for inst in ci.get_insts().into_iter() {
                     let host = host.to_string();
                     let port = inst.get_port();
-                    let th = thread::spawn(move || check_redis(&format!("{}:{}", host, port)));
+                    let th = thread::spawn(move || {
+                        let addr = format!("{}:{}", host, port);
+                        match cache_type {
+                            CacheType::Memcache => check_memcache(&addr),
+                            _ => check_redis(&addr),
+                        }
+                    });
                     ths.push(th);
                 }
                 ths
@@ -254,7 +675,10 @@ impl DeployTask {
             })
             .all(|rslt| rslt.is_ok())
         {
-            return Err(format_err!("check redis fail finally due"));
+            return Err(format_err!(
+                "check {} fail finally due",
+                cache_type_name(cache_type)
+            ));
         }
         Ok(())
     }
@@ -289,21 +713,26 @@ impl DeployTask {
     fn send_deploy(&self, cache_infos: CacheInfos) -> Result<(), Error> {
         let mut ths = Vec::new();
         for (host, cache_info) in cache_infos.into_iter() {
-            let addr = self.get_grpc_addr(&host)?.unwrap();
-            let th = thread::spawn(move || {
-                let env = Arc::new(EnvBuilder::new().build());
-                let ch = ChannelBuilder::new(env).connect(&addr);
-                let client = AgentClient::new(ch);
-                client.deploy(&cache_info)
-            });
-            ths.push(th);
+            let client = connect_agent(&self.myetcd, &host)?;
+            let th = thread::spawn(move || client.deploy(&cache_info));
+            ths.push((host, th));
         }
 
-        for th in ths {
-            let reply = th.join().unwrap();
-            reply.expect("fail to send rpc");
+        // join all the threads before return, the failures of agents are collected
+        let mut errs = Vec::new();
+        for (host, th) in ths {
+            match th.join().unwrap() {
+                Ok(ref state) if state.get_state() == State::Error => {
+                    errs.push(format!("{}: {}", host, state.get_msg()))
+                }
+                Ok(_) => {}
+                Err(err) => errs.push(format!("{}: fail to send rpc due {}", host, err)),
+            }
         }
 
+        if !errs.is_empty() {
+            return Err(format_err!("fail to deploy to agents, {}", errs.join("; ")));
+        }
         Ok(())
     }
 
@@ -313,26 +742,13 @@ impl DeployTask {
     }
 
     fn create_chunks(&self) -> Result<Chunks, Error> {
-        let offers = fetch_offer();
+        let offers = fetch_offer(&self.myetcd)?;
         let num = (self.param.total_memory / self.param.max_memory + 1) / 2 * 2;
         info!("chunk_it with num {}", num);
         let chunks = chunk_it(num, self.param.cpu_percent, self.param.max_memory, &offers)?;
         Ok(chunks)
     }
 
-    fn get_grpc_addr(&self, host: &str) -> Result<Option<String>, Error> {
-        let Response {
-            data:
-                KeyValueInfo {
-                    node: Node { value, .. },
-                    ..
-                },
-            ..
-        } = self.myetcd.get(&format!("/haste/agent/{}", host))?;
-
-        Ok(value)
-    }
-
     //                      /appids/{appids}
     //                      /cache_type -> {redis, redis_cluster, memcache}
     //                      /audit/{task_id}/[checkpoint, state]
@@ -346,20 +762,426 @@ impl DeployTask {
     //  3. write cache_type
     //  4. write configs
     //  5. write audit log as create new items with time key
-    fn save_into_etcd(&mut self, _chunks: &Chunks, _tempate: &Template) -> Result<(), Error> {
-        unimplemented!()
+    fn etcd_writes(&self, job_id: &str, chunks: &Chunks) -> Vec<EtcdWrite> {
+        let base = format!("/haste/clusters/{}", self.param.name);
+        let mut writes = Vec::new();
+
+        for inst in &chunks.0[..] {
+            let prefix = format!("{}/instances/{}:{}", base, inst.host, inst.port);
+            let slots: Vec<_> = inst.slots.iter().map(|x| format!("{}", x)).collect();
+            writes.push(EtcdWrite::new(
+                format!("{}/role", prefix),
+                inst.role.clone(),
+            ));
+            writes.push(EtcdWrite::new(
+                format!("{}/slaveof", prefix),
+                inst.slaveof.clone(),
+            ));
+            writes.push(EtcdWrite::new(format!("{}/slots", prefix), slots.join(" ")));
+            writes.push(EtcdWrite::new(format!("{}/state", prefix), STATE_ONLINE));
+        }
+
+        writes.push(EtcdWrite::new(
+            format!("{}/cache_type", base),
+            cache_type_name(self.param.cache_type),
+        ));
+        writes.push(EtcdWrite::new(
+            format!("{}/version", base),
+            self.param.version.clone(),
+        ));
+        writes.push(EtcdWrite::new(
+            format!("{}/template", base),
+            self.param.tpl_name.clone(),
+        ));
+
+        writes.extend(config_writes(&self.param));
+
+        writes.push(EtcdWrite::new(
+            format!("{}/audit/{}/state", base, job_id),
+            "done",
+        ));
+        writes
+    }
+
+    fn save_into_etcd(&mut self, writes: &[EtcdWrite]) -> Result<(), Error> {
+        for write in writes {
+            self.myetcd.set(&write.key, &write.value)?;
+        }
+        Ok(())
     }
 
     fn load_template(&self) -> Result<Template, Error> {
-        unimplemented!()
+        Template::load(&self.myetcd, self.param.cache_type, &self.param.tpl_name)
     }
 }
 
 pub struct Dist {}
 
+/// the writes of configs and appids of cluster, which are read by proxies.
+pub fn config_writes(param: &DeployParm) -> Vec<EtcdWrite> {
+    let base = format!("/haste/clusters/{}", param.name);
+    let mut writes = Vec::new();
+
+    let configs: Vec<_> = vec![
+        ("dial_timeout", param.dial_timeout),
+        ("read_timeout", param.read_timeout),
+        ("write_timeout", param.write_timeout),
+    ]
+    .into_iter()
+    .filter_map(|(name, val)| val.map(|v| (name, format!("{}", v))))
+    .collect();
+    for (name, val) in &configs {
+        writes.push(EtcdWrite::new(
+            format!("{}/config/{}", base, name),
+            val.clone(),
+        ));
+    }
+
+    let appids = param
+        .appids
+        .split(',')
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty());
+    for appid in appids {
+        writes.push(EtcdWrite::new(format!("{}/appids/{}", base, appid), ""));
+        for (name, val) in &configs {
+            writes.push(EtcdWrite::new(
+                format!("/haste/appids/{}/{}/config/{}", appid, param.name, name),
+                val.clone(),
+            ));
+        }
+    }
+    writes
+}
+
+/// the name must be 1 to 64 chars of [a-zA-Z0-9_-], since it's used in etcd keys and paths.
+pub fn check_name(kind: &str, name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!("{} name must be 1 to {} chars", kind, MAX_NAME_LEN));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "{} name {} must only contain [a-zA-Z0-9_-]",
+            kind, name
+        ));
+    }
+    Ok(())
+}
+
+/// the problem of version, which must be dotted numbers like 4.0.11 and the binary of it must
+/// be in the manifest of file server. it's not checked in file server if there is no file
+/// server, since the agents may build it from source.
+pub fn version_problem(
+    myetcd: &MyEtcd,
+    cache_type: CacheType,
+    version: &str,
+) -> Result<Option<String>, Error> {
+    if !is_version(version) {
+        return Ok(Some(format!("version {:?} must be like 4.0.11", version)));
+    }
+    let file_server = match myetcd.get_value(FILE_SERVER_KEY)? {
+        Some(file_server) => file_server,
+        None => return Ok(None),
+    };
+    let name = Binary::new(cache_type, version)?.file_name();
+    Ok(fetch_checksum(&file_server, &name)
+        .err()
+        .map(|err| format!("version {} is not available due {}", version, err)))
+}
+
+// appids are separated by ',' and each of them is like `account.session`
+fn appids_problems(appids: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    if appids.trim().is_empty() {
+        return problems;
+    }
+    let mut seen = HashSet::new();
+    for appid in appids.split(',').map(|x| x.trim()) {
+        if appid.is_empty() {
+            problems.push(format!("appids {:?} has an empty appid", appids));
+        } else if !appid
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+        {
+            problems.push(format!("appid {} must only contain [a-zA-Z0-9._-]", appid));
+        } else if !seen.insert(appid) {
+            problems.push(format!("appid {} is duplicated", appid));
+        }
+    }
+    problems
+}
+
+/// the files which a template of cache type must have.
+pub fn template_files(cache_type: CacheType) -> &'static [&'static str] {
+    match cache_type {
+        CacheType::Memcache => MEMCACHE_TEMPLATES,
+        _ => REDIS_TEMPLATES,
+    }
+}
+
+/// connect to the agent of host by it's grpc address in `/haste/agent/{ip}`.
+pub fn connect_agent(myetcd: &MyEtcd, host: &str) -> Result<AgentClient, Error> {
+    let addr = myetcd
+        .get_value(&format!("/haste/agent/{}", host))?
+        .ok_or_else(|| format_err!("agent of {} is not registered", host))?;
+    let env = Arc::new(EnvBuilder::new().build());
+    let ch = ChannelBuilder::new(env).connect(&addr);
+    Ok(AgentClient::new(ch))
+}
+
+/// send the action to the agent of host, the failure reported by agent is an error.
+pub fn send_action(myetcd: &MyEtcd, host: &str, action: &Action) -> Result<(), Error> {
+    let state = connect_agent(myetcd, host)?.do_action(action)?;
+    if state.get_state() == State::Error {
+        return Err(format_err!(
+            "fail to {:?} on {} due {}",
+            action.get_action(),
+            host,
+            state.get_msg()
+        ));
+    }
+    Ok(())
+}
+
+// count the failure of deploy by the stage where it happens.
+fn failed(reason: &str, err: Error) -> Error {
+    DEPLOY_FAILURES.with_label_values(&[reason]).inc();
+    err
+}
+
 fn check_redis(addr: &str) -> Result<(), Error> {
     let client = redis::Client::open(addr)?;
     let conn = client.get_connection()?;
     let _: () = redis::cmd("PING").query(&conn)?;
     Ok(())
 }
+
+fn check_memcache(addr: &str) -> Result<(), Error> {
+    let stats = MyMemcache::open(addr)?.stats()?;
+    info!(
+        "memcache {} is up with version {} and {} threads",
+        addr, stats.version, stats.threads
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> Template {
+        let mut tera = Tera::default();
+        tera.add_raw_template("redis.conf", "port {{ port }}\ndir {{ dir }}\n")
+            .unwrap();
+        tera.add_raw_template(
+            "cache.service",
+            "[Unit]\n[Service]\nExecStart={{ binary }} {{ port }}\n",
+        )
+        .unwrap();
+        Template { tera }
+    }
+
+    fn param(cache_type: CacheType) -> DeployParm {
+        DeployParm {
+            name: "mycluster".to_string(),
+            cpu_percent: 100,
+            max_memory: 1024,
+            total_memory: 4096,
+            version: "4.0.11".to_string(),
+            tpl_name: "default".to_string(),
+            cache_type,
+            appids: String::new(),
+            group: String::new(),
+            dial_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+        }
+    }
+
+    #[test]
+    fn render_every_cache_type() {
+        let chunks = Chunks(vec![ChunkInstance {
+            host: "127.0.0.1".to_string(),
+            port: 7000,
+            role: "master".to_string(),
+            slaveof: "-".to_string(),
+            runid: "0".repeat(40),
+            slots: Vec::new(),
+        }]);
+        let layout = InstanceLayout::default();
+        let template = template();
+        for (cache_type, count) in &[
+            (CacheType::RedisCluster, 3),
+            (CacheType::Redis, 2),
+            (CacheType::Memcache, 1),
+        ] {
+            let files = template
+                .render(&layout, &chunks, "127.0.0.1", 7000, &param(*cache_type))
+                .unwrap();
+            assert_eq!(files.len(), *count, "{:?}", cache_type);
+            let unit = files.last().unwrap();
+            assert_eq!(
+                unit.get_fpath(),
+                path_str(layout.unit_path("mycluster", 7000))
+            );
+            assert!(unit.get_content().contains("X-Haste-Port=7000"));
+        }
+    }
+
+    #[test]
+    fn check_template_by_rendering() {
+        for cache_type in &[
+            CacheType::RedisCluster,
+            CacheType::Redis,
+            CacheType::Memcache,
+        ] {
+            assert!(template().check(*cache_type).is_ok(), "{:?}", cache_type);
+        }
+
+        let bad = Template::parse(vec![
+            (
+                "redis.conf",
+                "port {{ port }}\nmaxmemory {{ max_memroy }}\n",
+            ),
+            ("cache.service", "[Service]\nExecStart={{ binary }}\n"),
+        ])
+        .unwrap();
+        assert!(bad.check(CacheType::RedisCluster).is_err());
+        assert!(Template::parse(vec![("redis.conf", "port {{ port")]).is_err());
+    }
+
+    fn problems_of<F: FnOnce(&mut DeployParm)>(cache_type: CacheType, change: F) -> Vec<String> {
+        let mut param = param(cache_type);
+        change(&mut param);
+        param.problems()
+    }
+
+    #[test]
+    fn valid_param() {
+        for cache_type in &[
+            CacheType::Redis,
+            CacheType::RedisCluster,
+            CacheType::Memcache,
+        ] {
+            assert!(param(*cache_type).problems().is_empty(), "{:?}", cache_type);
+        }
+        let problems = problems_of(CacheType::Redis, |x| {
+            x.appids = " account.session, account.user ".to_string();
+            x.dial_timeout = Some(1000);
+            x.cpu_percent = 0;
+        });
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[test]
+    fn memory_problems() {
+        let problems = problems_of(CacheType::Redis, |x| x.max_memory = 0);
+        assert_eq!(problems, vec!["max_memory must be greater than 0"]);
+        let problems = problems_of(CacheType::Redis, |x| x.total_memory = 512);
+        assert_eq!(
+            problems,
+            vec!["total_memory 512 must not be less than max_memory 1024"]
+        );
+    }
+
+    #[test]
+    fn cpu_percent_problems() {
+        let problems = problems_of(CacheType::Memcache, |x| x.cpu_percent = 0);
+        assert_eq!(problems, vec!["cpu_percent must be set for memcache"]);
+        assert!(problems_of(CacheType::Memcache, |x| x.cpu_percent = 400).is_empty());
+        for cache_type in &[CacheType::Redis, CacheType::RedisCluster] {
+            let problems = problems_of(*cache_type, |x| x.cpu_percent = 101);
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("cpu_percent 101 must not be greater than 100"));
+        }
+    }
+
+    #[test]
+    fn name_problems() {
+        for name in &["", "my.cluster", "my cluster", "../etc", &"a".repeat(65)] {
+            let problems = problems_of(CacheType::Redis, |x| x.name = name.to_string());
+            assert_eq!(problems.len(), 1, "{:?}", name);
+        }
+        assert!(problems_of(CacheType::Redis, |x| x.name = "my_cluster-1".to_string()).is_empty());
+        let problems = problems_of(CacheType::Redis, |x| x.tpl_name = "de/fault".to_string());
+        assert_eq!(
+            problems,
+            vec!["template name de/fault must only contain [a-zA-Z0-9_-]"]
+        );
+    }
+
+    #[test]
+    fn appid_problems() {
+        assert!(appids_problems("").is_empty());
+        assert!(appids_problems("  ").is_empty());
+        assert!(appids_problems("account.session,account-user_1").is_empty());
+        assert_eq!(
+            appids_problems("account.session,,account.user"),
+            vec![r#"appids "account.session,,account.user" has an empty appid"#]
+        );
+        assert_eq!(
+            appids_problems("account.session, account.session"),
+            vec!["appid account.session is duplicated"]
+        );
+        assert_eq!(
+            appids_problems("account/session"),
+            vec!["appid account/session must only contain [a-zA-Z0-9._-]"]
+        );
+    }
+
+    #[test]
+    fn timeout_problems() {
+        let problems = problems_of(CacheType::Redis, |x| {
+            x.dial_timeout = Some(0);
+            x.read_timeout = Some(0);
+            x.write_timeout = Some(1);
+        });
+        assert_eq!(
+            problems,
+            vec![
+                "dial_timeout must be greater than 0",
+                "read_timeout must be greater than 0"
+            ]
+        );
+    }
+
+    #[test]
+    fn version_problems() {
+        for version in &[
+            "",
+            "latest",
+            "4",
+            "4..11",
+            "4.0.11-rc1",
+            "../4.0.11",
+            "4.0.11/x",
+        ] {
+            let problems = problems_of(CacheType::Redis, |x| x.version = version.to_string());
+            assert_eq!(
+                problems,
+                vec![format!("version {:?} must be like 4.0.11", version)]
+            );
+        }
+    }
+
+    #[test]
+    fn problems_together() {
+        let param = DeployParm {
+            name: "my.cluster".to_string(),
+            max_memory: 0,
+            cpu_percent: 200,
+            appids: "a,,a/b".to_string(),
+            read_timeout: Some(0),
+            version: "latest".to_string(),
+            ..param(CacheType::RedisCluster)
+        };
+        let err = param.invalid(param.problems()).unwrap_err();
+        let invalid = err.downcast::<InvalidParm>().unwrap();
+        assert_eq!(invalid.name, "my.cluster");
+        assert_eq!(invalid.problems.len(), 7, "{:?}", invalid.problems);
+        assert!(param.invalid(Vec::new()).is_ok());
+    }
+}
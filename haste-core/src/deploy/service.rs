@@ -0,0 +1,420 @@
+//! grpc service of agent.
+
+use crate::deploy::agent::{migrate, restore, AgentEnv, CacheDeployer};
+use crate::deploy::binary::Binary;
+use crate::deploy::config::AgentConfig;
+use crate::deploy::files::FilePolicy;
+use crate::deploy::gc::BinaryRefs;
+use crate::deploy::metrics::{usage_of, MetricsCollector};
+use crate::deploy::ports::PortAllocator;
+use crate::deploy::status::instance_status;
+use crate::exporter::{spawn_exporter, AGENT_ACTIONS, INSTANCES};
+use crate::layout::InstanceLayout;
+use crate::myetcd::MyEtcd;
+use crate::offer::{register_offer, Offer};
+use crate::proto::{
+    Action, Binaries, BinaryPurge, BinaryQuery, CacheInfo, CacheState, Instance, InstanceChange,
+    InstanceStatuses, MetricsQuery, MetricsSamples, PortAcquire, Ports, RestoreRequest, State,
+    StatusQuery, SystemdAction,
+};
+use crate::proto_grpc::{create_agent, Agent};
+use crate::supervisor::{new_supervisor, Supervisor};
+use crate::systemd::{cluster_ports, list_units};
+
+use failure::{format_err, Error};
+use futures::Future;
+use grpcio::{Environment, RpcContext, RpcStatus, RpcStatusCode, ServerBuilder, UnarySink};
+use log::{error, info};
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const GC_INTERVAL: u64 = 3600;
+
+#[derive(Clone)]
+pub struct AgentService {
+    env: AgentEnv,
+    ports: Arc<Mutex<PortAllocator>>,
+    refs: Arc<Mutex<BinaryRefs>>,
+    // grace seconds of unreferenced binaries
+    grace: u64,
+    metrics: Arc<Mutex<MetricsCollector>>,
+}
+
+impl AgentService {
+    pub fn new(
+        env: AgentEnv,
+        ports: PortAllocator,
+        refs: BinaryRefs,
+        grace: u64,
+        metrics: MetricsCollector,
+    ) -> AgentService {
+        AgentService {
+            env,
+            ports: Arc::new(Mutex::new(ports)),
+            refs: Arc::new(Mutex::new(refs)),
+            grace,
+            metrics: Arc::new(Mutex::new(metrics)),
+        }
+    }
+
+    fn deploy_cache(&self, ci: CacheInfo) -> Result<Vec<InstanceChange>, Error> {
+        let ports = insts_ports(ci.get_insts());
+        // reference the binary first so it never be purged while deploying
+        let binary = Binary::new(ci.get_cache_type(), ci.get_version())?;
+        let prev = self.refs.lock().unwrap().reference(&binary, &ports)?;
+
+        let mut deployer = CacheDeployer::new(ci, self.env.clone());
+        let changes = match deployer.deploy() {
+            Ok(changes) => changes,
+            Err(err) => {
+                // the failed instances may still run the old binaries
+                if let Err(err) = self.refs.lock().unwrap().restore(&prev) {
+                    error!("fail to restore binary refs of {:?} due {}", ports, err);
+                }
+                return Err(err);
+            }
+        };
+        self.ports.lock().unwrap().mark_deployed(&ports)?;
+        Ok(changes)
+    }
+
+    fn execute(&self, action: &Action) -> Result<(), Error> {
+        let act = action.get_action();
+        if let SystemdAction::Setup = act {
+            return self.env.do_action(act, -1);
+        }
+
+        let ports: Vec<_> = if action.get_insts().is_empty() && !action.get_cluster().is_empty() {
+            cluster_ports(action.get_cluster())?
+        } else {
+            action.get_insts().iter().map(|x| x.get_port()).collect()
+        };
+        for port in &ports {
+            self.env.do_action(act, *port)?;
+        }
+
+        if let SystemdAction::Remove = act {
+            let ports: Vec<_> = ports.iter().map(|x| *x as usize).collect();
+            self.ports.lock().unwrap().release(&ports)?;
+            self.refs.lock().unwrap().release(&ports)?;
+        }
+        Ok(())
+    }
+
+    fn purge_binaries(&self, req: &BinaryPurge) -> Result<Binaries, Error> {
+        let grace = if req.get_force() { 0 } else { self.grace };
+        let purged = self.refs.lock().unwrap().purge(grace, req.get_dry_run())?;
+        let mut binaries = Binaries::new();
+        binaries.set_binaries(purged.into());
+        Ok(binaries)
+    }
+
+    fn statuses(&self, req: &StatusQuery) -> Result<InstanceStatuses, Error> {
+        let mut ports = if !req.get_ports().is_empty() {
+            req.get_ports().to_vec()
+        } else if !req.get_cluster().is_empty() {
+            cluster_ports(req.get_cluster())?
+        } else {
+            list_units()?.into_iter().map(|x| x.port).collect()
+        };
+        ports.sort_unstable();
+        ports.dedup();
+
+        let statuses: Vec<_> = ports
+            .into_iter()
+            .map(|port| instance_status(self.env.supervisor.as_ref(), port))
+            .collect();
+        let mut reply = InstanceStatuses::new();
+        reply.set_statuses(statuses.into());
+        Ok(reply)
+    }
+
+    // sample metrics every interval seconds and report the free resources in offer.
+    fn spawn_metrics(&self, interval: u64, myetcd: MyEtcd, mut offer: Offer) {
+        let metrics = self.metrics.clone();
+        let ports = self.ports.clone();
+        let supervisor = self.env.supervisor.clone();
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_secs(interval.max(1)));
+            let sample = match metrics.lock().unwrap().sample() {
+                Ok(sample) => sample,
+                Err(err) => {
+                    error!("fail to sample metrics due {}", err);
+                    continue;
+                }
+            };
+            count_instances(supervisor.as_ref());
+            offer.ports = ports.lock().unwrap().offered();
+            offer.usage = Some(usage_of(&sample));
+            if let Err(err) = register_offer(&myetcd, &offer) {
+                error!("fail to report usage in offer due {}", err);
+            }
+        });
+    }
+
+    fn spawn_gc(&self) {
+        let refs = self.refs.clone();
+        let grace = self.grace;
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_secs(GC_INTERVAL));
+            match refs.lock().unwrap().purge(grace, false) {
+                Ok(purged) => info!("gc purge {} binaries", purged.len()),
+                Err(err) => error!("fail to gc binaries due {}", err),
+            }
+        });
+    }
+}
+
+impl Agent for AgentService {
+    fn deploy(&mut self, ctx: RpcContext, req: CacheInfo, sink: UnarySink<CacheState>) {
+        let start = Instant::now();
+        let job_id = req.get_job_id().to_string();
+        let state = match self.deploy_cache(req) {
+            Ok(changes) => {
+                let mut state = as_cache_state(Ok(()));
+                state.set_changes(changes.into());
+                state
+            }
+            Err(err) => as_cache_state(Err(err)),
+        };
+        info!("deploy job {} done with state {:?}", job_id, state);
+        observe_action("deploy", &state, start);
+        ctx.spawn(
+            sink.success(state)
+                .map_err(|err| error!("fail to reply deploy due {:?}", err)),
+        );
+    }
+
+    fn do_action(&mut self, ctx: RpcContext, req: Action, sink: UnarySink<CacheState>) {
+        let start = Instant::now();
+        let state = as_cache_state(self.execute(&req));
+        let action = format!("{:?}", req.get_action()).to_lowercase();
+        observe_action(&action, &state, start);
+        ctx.spawn(
+            sink.success(state)
+                .map_err(|err| error!("fail to reply action due {:?}", err)),
+        );
+    }
+
+    fn get_ports(&mut self, ctx: RpcContext, req: PortAcquire, sink: UnarySink<Ports>) {
+        let count = req.get_count();
+        let rslt = if count < 0 {
+            Err(format_err!("bad port count {}", count))
+        } else {
+            self.ports.lock().unwrap().acquire(count as usize)
+        };
+
+        let reply = match rslt {
+            Ok(ports) => {
+                let mut reply = Ports::new();
+                reply.set_ports(ports.into_iter().map(|x| x as i64).collect());
+                sink.success(reply)
+            }
+            Err(err) => {
+                error!("fail to acquire {} ports due {}", count, err);
+                sink.fail(RpcStatus::new(
+                    RpcStatusCode::ResourceExhausted,
+                    Some(format!("{}", err)),
+                ))
+            }
+        };
+        ctx.spawn(reply.map_err(|err| error!("fail to reply ports due {:?}", err)));
+    }
+
+    fn restore(&mut self, ctx: RpcContext, req: RestoreRequest, sink: UnarySink<CacheState>) {
+        let mut changes = Vec::new();
+        let mut rslt = Ok(());
+        for port in req.get_ports() {
+            match restore(*port, &self.env) {
+                Ok(change) => changes.push(change),
+                Err(err) => {
+                    rslt = Err(err);
+                    break;
+                }
+            }
+        }
+
+        let mut state = as_cache_state(rslt);
+        state.set_changes(changes.into());
+        ctx.spawn(
+            sink.success(state)
+                .map_err(|err| error!("fail to reply restore due {:?}", err)),
+        );
+    }
+
+    fn list_binaries(&mut self, ctx: RpcContext, _req: BinaryQuery, sink: UnarySink<Binaries>) {
+        let rslt = self.refs.lock().unwrap().list().map(|list| {
+            let mut binaries = Binaries::new();
+            binaries.set_binaries(list.into());
+            binaries
+        });
+        reply_binaries(ctx, sink, rslt);
+    }
+
+    fn purge_binaries(&mut self, ctx: RpcContext, req: BinaryPurge, sink: UnarySink<Binaries>) {
+        let rslt = AgentService::purge_binaries(self, &req);
+        reply_binaries(ctx, sink, rslt);
+    }
+
+    fn status(&mut self, ctx: RpcContext, req: StatusQuery, sink: UnarySink<InstanceStatuses>) {
+        let reply = match self.statuses(&req) {
+            Ok(statuses) => sink.success(statuses),
+            Err(err) => {
+                error!("fail to get status due {}", err);
+                sink.fail(RpcStatus::new(
+                    RpcStatusCode::Internal,
+                    Some(format!("{}", err)),
+                ))
+            }
+        };
+        ctx.spawn(reply.map_err(|err| error!("fail to reply status due {:?}", err)));
+    }
+
+    fn metrics(&mut self, ctx: RpcContext, req: MetricsQuery, sink: UnarySink<MetricsSamples>) {
+        let samples = self.metrics.lock().unwrap().since(req.get_since());
+        let mut reply = MetricsSamples::new();
+        reply.set_samples(samples.into());
+        ctx.spawn(
+            sink.success(reply)
+                .map_err(|err| error!("fail to reply metrics due {:?}", err)),
+        );
+    }
+}
+
+/// run the agent until it was killed:
+///   1. migrate legacy instances and load port allocations
+///   2. register offer and grpc address into etcd
+///   3. spawn gc of binaries and sampling of metrics
+///   4. serve prometheus metrics if `metrics.listen` is set
+///   5. serve grpc
+pub fn serve(config: AgentConfig) -> Result<(), Error> {
+    let layout = InstanceLayout::new(&config.data_root);
+    let mut roots = layout.roots();
+    roots.extend(config.allowed_roots.iter().cloned());
+    let env = AgentEnv {
+        locker: Arc::new(Mutex::new(())),
+        build: config.build.clone(),
+        policy: FilePolicy::new(&roots),
+        layout: layout.clone(),
+        supervisor: new_supervisor(&config.supervisor)?,
+    };
+
+    let migrated = migrate(&env);
+    if !migrated.is_empty() {
+        info!("migrate instances {:?} into {}", migrated, layout.data_root);
+    }
+
+    let ports = PortAllocator::load(
+        config.port_begin,
+        config.port_end,
+        &config.ports_file,
+        &layout,
+    )?;
+    let offer = Offer {
+        host: config.host.clone(),
+        cpu: config.cpu,
+        memory: config.memory,
+        ports: ports.offered(),
+        data_root: layout.data_root.clone(),
+        usage: None,
+    };
+
+    let myetcd = MyEtcd::open(&config.etcd)?;
+    register_offer(&myetcd, &offer)?;
+    myetcd.set(
+        &format!("/haste/agent/{}", config.host),
+        &format!("{}:{}", config.host, config.port),
+    )?;
+    info!(
+        "register offer of {} with {} ports",
+        offer.host,
+        offer.ports.len()
+    );
+
+    let refs = BinaryRefs::load(&config.refs_file)?;
+    let metrics = MetricsCollector::new(&config.metrics, &layout.data_root, env.supervisor.clone());
+    let agent = AgentService::new(env, ports, refs, config.binary_grace, metrics);
+    agent.spawn_gc();
+    agent.spawn_metrics(config.metrics.interval, myetcd, offer);
+    if !config.metrics.listen.is_empty() {
+        spawn_exporter(&config.metrics.listen)?;
+    }
+
+    let grpc_env = Arc::new(Environment::new(1));
+    let service = create_agent(agent);
+    let mut server = ServerBuilder::new(grpc_env)
+        .register_service(service)
+        .bind(config.listen.clone(), config.port)
+        .build()?;
+    server.start();
+    info!("agent listen on {}:{}", config.listen, config.port);
+
+    loop {
+        thread::park();
+    }
+}
+
+fn reply_binaries(ctx: RpcContext, sink: UnarySink<Binaries>, rslt: Result<Binaries, Error>) {
+    let reply = match rslt {
+        Ok(binaries) => sink.success(binaries),
+        Err(err) => {
+            error!("fail to handle binaries due {}", err);
+            sink.fail(RpcStatus::new(
+                RpcStatusCode::Internal,
+                Some(format!("{}", err)),
+            ))
+        }
+    };
+    ctx.spawn(reply.map_err(|err| error!("fail to reply binaries due {:?}", err)));
+}
+
+fn observe_action(action: &str, state: &CacheState, start: Instant) {
+    let state = format!("{:?}", state.get_state()).to_lowercase();
+    AGENT_ACTIONS
+        .with_label_values(&[action, &state])
+        .observe(start.elapsed().as_millis() as f64 / 1000.0);
+}
+
+// publish the count of instances by the state of their units.
+fn count_instances(supervisor: &dyn Supervisor) {
+    let units = match list_units() {
+        Ok(units) => units,
+        Err(err) => {
+            error!("fail to list units due {}", err);
+            return;
+        }
+    };
+    let mut counts = HashMap::new();
+    for unit in units {
+        let state = match supervisor.status(&unit.name) {
+            Ok(status) => format!("{:?}", status.state).to_lowercase(),
+            Err(_) => "unknown".to_string(),
+        };
+        *counts.entry(state).or_insert(0) += 1;
+    }
+
+    INSTANCES.reset();
+    for (state, count) in counts {
+        INSTANCES.with_label_values(&[&state]).set(count);
+    }
+}
+
+fn insts_ports(insts: &[Instance]) -> Vec<usize> {
+    insts.iter().map(|x| x.get_port() as usize).collect()
+}
+
+fn as_cache_state(rslt: Result<(), Error>) -> CacheState {
+    let mut state = CacheState::new();
+    match rslt {
+        Ok(()) => state.set_state(State::Done),
+        Err(err) => {
+            error!("execute fail due {}", err);
+            state.set_state(State::Error);
+            state.set_msg(format!("{}", err));
+        }
+    }
+    state
+}
@@ -0,0 +1,187 @@
+//! status of the instances on agent.
+//!
+//! the state, pid and restart count come from the supervisor, the uptime, rss and cpu time of
+//! the process are read from `/proc/{pid}`, and the port is checked by a tcp connect.
+
+use crate::proto::{InstanceStatus, UnitState};
+use crate::supervisor::Supervisor;
+use crate::systemd::unit_of;
+
+use failure::{format_err, Error};
+
+use std::convert::TryFrom;
+use std::fs;
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+// USER_HZ, the unit of times in /proc/{pid}/stat, which is 100 on all the supported platforms
+const CLOCK_TICKS: u64 = 100;
+
+pub struct ProcStat {
+    // seconds since the process started
+    pub uptime: u64,
+    // in bytes
+    pub rss: u64,
+    // user and system cpu time in milliseconds
+    pub cpu_time: u64,
+}
+
+/// the status of instance on port, the failures are reported in it's msg.
+pub fn instance_status(supervisor: &dyn Supervisor, port: i64) -> InstanceStatus {
+    let mut status = InstanceStatus::new();
+    status.set_port(port);
+    match is_accepting(port) {
+        Ok(accepting) => status.set_accepting(accepting),
+        Err(err) => {
+            status.set_state(UnitState::Inactive);
+            status.set_msg(format!("{}", err));
+            return status;
+        }
+    }
+
+    let unit = match unit_of(port) {
+        Ok(unit) => unit,
+        Err(err) => {
+            status.set_state(UnitState::Inactive);
+            status.set_msg(format!("{}", err));
+            return status;
+        }
+    };
+    status.set_unit(unit.clone());
+
+    let unit_status = match supervisor.status(&unit) {
+        Ok(unit_status) => unit_status,
+        Err(err) => {
+            status.set_msg(format!("fail to get status of unit {} due {}", unit, err));
+            return status;
+        }
+    };
+    status.set_state(unit_status.state);
+    status.set_pid(i64::from(unit_status.pid));
+    status.set_restarts(unit_status.restarts as i64);
+
+    if unit_status.pid != 0 {
+        match proc_stat(unit_status.pid) {
+            Ok(stat) => {
+                status.set_uptime(stat.uptime as i64);
+                status.set_rss(stat.rss as i64);
+                status.set_cpu_time(stat.cpu_time as i64);
+            }
+            Err(err) => status.set_msg(format!(
+                "fail to read /proc of {} due {}",
+                unit_status.pid, err
+            )),
+        }
+    }
+    status
+}
+
+pub fn proc_stat(pid: u32) -> Result<ProcStat, Error> {
+    let (cpu_ticks, start_ticks) = parse_stat(&fs::read_to_string(format!("/proc/{}/stat", pid))?)
+        .map_err(|err| format_err!("bad stat of {} due {}", pid, err))?;
+    let boot_uptime = parse_uptime(&fs::read_to_string("/proc/uptime")?)?;
+    let rss = parse_vm_rss(&fs::read_to_string(format!("/proc/{}/status", pid))?)?;
+
+    Ok(ProcStat {
+        uptime: (boot_uptime as u64).saturating_sub(start_ticks / CLOCK_TICKS),
+        rss,
+        cpu_time: cpu_ticks * 1000 / CLOCK_TICKS,
+    })
+}
+
+// the (utime + stime, starttime) in ticks of /proc/{pid}/stat.
+fn parse_stat(stat: &str) -> Result<(u64, u64), Error> {
+    // comm may contain spaces and parentheses, so the fields are counted after the last ')'
+    let pos = stat
+        .rfind(')')
+        .ok_or_else(|| format_err!("no comm in {:?}", stat))?;
+    let fields: Vec<_> = stat[pos + 1..].split_whitespace().collect();
+    // the first one is the 3rd field `state`, see proc(5)
+    let field = |n: usize| -> Result<u64, Error> {
+        let value = fields
+            .get(n - 3)
+            .ok_or_else(|| format_err!("field {} not found", n))?;
+        Ok(value.parse()?)
+    };
+    Ok((field(14)? + field(15)?, field(22)?))
+}
+
+// seconds since boot of /proc/uptime
+fn parse_uptime(uptime: &str) -> Result<f64, Error> {
+    Ok(uptime
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| format_err!("bad /proc/uptime"))?
+        .parse()?)
+}
+
+// VmRSS of /proc/{pid}/status in bytes
+fn parse_vm_rss(status: &str) -> Result<u64, Error> {
+    for line in status.lines() {
+        if let Some(value) = line.strip_prefix("VmRSS:") {
+            let kb: u64 = value.trim().trim_end_matches("kB").trim().parse()?;
+            return Ok(kb * 1024);
+        }
+    }
+    // kernel threads and zombies have no VmRSS
+    Ok(0)
+}
+
+fn is_accepting(port: i64) -> Result<bool, Error> {
+    let port = u16::try_from(port).map_err(|_| format_err!("bad port {}", port))?;
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    Ok(TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // captured from a redis-server, utime 1234 stime 567 starttime 89012
+    const STAT: &str = "4242 (redis-server) S 1 4242 4242 0 -1 4194560 3180 0 0 0 1234 567 0 0 \
+                        20 0 4 0 89012 56102912 2417 18446744073709551615 1 1 0 0 0 0 0 4097 \
+                        17610 0 0 0 17 3 0 0 0 0 0 0 0 0 0 0 0 0 0\n";
+
+    #[test]
+    fn parse_stat_line() {
+        assert_eq!(parse_stat(STAT).unwrap(), (1801, 89012));
+        for comm in &["(redis server)", "(a) b)", "((sd-pam))", "(x 1 2 3)"] {
+            let stat = STAT.replacen("(redis-server)", comm, 1);
+            assert_eq!(parse_stat(&stat).unwrap(), (1801, 89012), "{}", comm);
+        }
+    }
+
+    #[test]
+    fn parse_bad_stat() {
+        assert!(parse_stat("4242 redis-server S 1").is_err());
+        assert!(parse_stat("4242 (redis-server) S 1 4242").is_err());
+        let stat = STAT.replacen(" 1234 ", " x ", 1);
+        assert!(parse_stat(&stat).is_err());
+    }
+
+    #[test]
+    fn parse_uptime_and_rss() {
+        assert_eq!(parse_uptime("350735.47 234388.90\n").unwrap(), 350735.47);
+        assert!(parse_uptime("").is_err());
+
+        let status = "Name:\tredis-server\nState:\tS (sleeping)\nVmPeak:\t   56104 kB\n\
+                      VmRSS:\t    9668 kB\nThreads:\t4\n";
+        assert_eq!(parse_vm_rss(status).unwrap(), 9668 * 1024);
+        assert_eq!(
+            parse_vm_rss("Name:\tkthreadd\nState:\tS (sleeping)\n").unwrap(),
+            0
+        );
+        assert!(parse_vm_rss("VmRSS:\t many kB\n").is_err());
+    }
+
+    #[test]
+    fn reject_bad_ports() {
+        for port in &[-1, 65536, i64::max_value()] {
+            assert_eq!(
+                is_accepting(*port).unwrap_err().to_string(),
+                format!("bad port {}", port)
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! prometheus metrics of leader and agent, which are served as text by `GET /metrics`.
+//!
+//! leader:
+//!   haste_jobs_total{kind, state}                jobs by the kind and final state
+//!   haste_jobs_running{kind}                     jobs being run
+//!   haste_job_duration_seconds{kind, state}      duration of jobs
+//!   haste_deploy_failures_total{reason}          failed stages of deploy jobs
+//!   haste_cluster_up{cluster}                    1 if all the instances are reachable
+//!   haste_cluster_instances{cluster, state}      reachable or unreachable instances
+//!   haste_cluster_ops_per_sec{cluster}           ops of all the instances
+//!   haste_cluster_hit_ratio{cluster}             hit ratio of all the instances
+//!   haste_cluster_repl_lag_bytes{cluster}        the max bytes of slaves behind masters
+//!   haste_spec_drifts{cluster}                   drifts between the spec and it's cluster
+//!
+//! agent:
+//!   haste_agent_action_duration_seconds{action, state}   deploy and systemd actions
+//!   haste_agent_download_bytes_total                     bytes of downloaded binaries
+//!   haste_agent_download_failures_total                  failed downloads of binaries
+//!   haste_agent_instances{state}                         instances by the state of unit
+
+use failure::{format_err, Error};
+use futures::Future;
+use hyper::service::service_fn_ok;
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use lazy_static::lazy_static;
+use log::{error, info};
+use prometheus::{
+    exponential_buckets, Encoder, GaugeVec, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec,
+    TextEncoder,
+};
+
+use std::net::SocketAddr;
+use std::thread;
+
+lazy_static! {
+    pub static ref JOBS: IntCounterVec = register_int_counter_vec!(
+        "haste_jobs_total",
+        "jobs by the kind and final state",
+        &["kind", "state"]
+    )
+    .unwrap();
+    pub static ref JOBS_RUNNING: IntGaugeVec =
+        register_int_gauge_vec!("haste_jobs_running", "jobs being run", &["kind"]).unwrap();
+    // from 1s to about 1h
+    pub static ref JOB_DURATION: HistogramVec = register_histogram_vec!(
+        "haste_job_duration_seconds",
+        "duration of jobs",
+        &["kind", "state"],
+        exponential_buckets(1.0, 2.0, 12).unwrap()
+    )
+    .unwrap();
+    pub static ref DEPLOY_FAILURES: IntCounterVec = register_int_counter_vec!(
+        "haste_deploy_failures_total",
+        "failed stages of deploy jobs",
+        &["reason"]
+    )
+    .unwrap();
+    pub static ref CLUSTER_UP: IntGaugeVec = register_int_gauge_vec!(
+        "haste_cluster_up",
+        "1 if all the instances of cluster are reachable",
+        &["cluster"]
+    )
+    .unwrap();
+    pub static ref CLUSTER_INSTANCES: IntGaugeVec = register_int_gauge_vec!(
+        "haste_cluster_instances",
+        "reachable or unreachable instances of cluster",
+        &["cluster", "state"]
+    )
+    .unwrap();
+    pub static ref CLUSTER_OPS: IntGaugeVec = register_int_gauge_vec!(
+        "haste_cluster_ops_per_sec",
+        "ops of all the instances of cluster",
+        &["cluster"]
+    )
+    .unwrap();
+    pub static ref CLUSTER_HIT_RATIO: GaugeVec = register_gauge_vec!(
+        "haste_cluster_hit_ratio",
+        "hit ratio of all the instances of cluster",
+        &["cluster"]
+    )
+    .unwrap();
+    pub static ref CLUSTER_REPL_LAG: IntGaugeVec = register_int_gauge_vec!(
+        "haste_cluster_repl_lag_bytes",
+        "the max bytes of slaves behind their masters",
+        &["cluster"]
+    )
+    .unwrap();
+    pub static ref SPEC_DRIFTS: IntGaugeVec = register_int_gauge_vec!(
+        "haste_spec_drifts",
+        "differences between the spec and it's cluster",
+        &["cluster"]
+    )
+    .unwrap();
+    // from 10ms to about 10min
+    pub static ref AGENT_ACTIONS: HistogramVec = register_histogram_vec!(
+        "haste_agent_action_duration_seconds",
+        "duration of deploy and systemd actions on agent",
+        &["action", "state"],
+        exponential_buckets(0.01, 2.0, 16).unwrap()
+    )
+    .unwrap();
+    pub static ref DOWNLOAD_BYTES: IntCounter = register_int_counter!(
+        "haste_agent_download_bytes_total",
+        "bytes of downloaded binaries"
+    )
+    .unwrap();
+    pub static ref DOWNLOAD_FAILURES: IntCounter = register_int_counter!(
+        "haste_agent_download_failures_total",
+        "failed downloads of binaries"
+    )
+    .unwrap();
+    pub static ref INSTANCES: IntGaugeVec = register_int_gauge_vec!(
+        "haste_agent_instances",
+        "instances on agent by the state of unit",
+        &["state"]
+    )
+    .unwrap();
+}
+
+/// serve `GET /metrics` on addr as `{ip}:{port}` in background.
+pub fn spawn_exporter(addr: &str) -> Result<(), Error> {
+    let addr: SocketAddr = addr
+        .parse()
+        .map_err(|err| format_err!("bad metrics addr {} due {}", addr, err))?;
+    let server = Server::try_bind(&addr)?
+        .serve(|| service_fn_ok(handle))
+        .map_err(|err| error!("metrics server exit due {}", err));
+    info!("serve metrics on http://{}/metrics", addr);
+    thread::spawn(move || hyper::rt::run(server));
+    Ok(())
+}
+
+fn handle(req: Request<Body>) -> Response<Body> {
+    if req.method() != Method::GET || req.uri().path() != "/metrics" {
+        return reply(StatusCode::NOT_FOUND, "text/plain", b"not found\n".to_vec());
+    }
+
+    let encoder = TextEncoder::new();
+    let mut buf = Vec::new();
+    if let Err(err) = encoder.encode(&prometheus::gather(), &mut buf) {
+        error!("fail to encode metrics due {}", err);
+        return reply(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "text/plain",
+            format!("{}\n", err).into_bytes(),
+        );
+    }
+    reply(StatusCode::OK, encoder.format_type(), buf)
+}
+
+fn reply(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap()
+}
@@ -0,0 +1,445 @@
+//! jobs of leader, saved in etcd as
+//!   /haste/jobs/{job_id}/[kind, cluster, state, msg, start, done, latest_update]
+//!
+//! and each job is appended into the audit log of it's cluster as
+//!   /haste/clusters/{name}/audit/{job_id}/[kind, state]
+//!
+//! jobs run in their own threads, the state is `running` until the job returns or panics.
+
+use crate::deploy::plan::DeployPlan;
+use crate::deploy::server::{check_name, DeployParm, DeployTask};
+use crate::exporter::{JOBS, JOBS_RUNNING, JOB_DURATION};
+use crate::myetcd::MyEtcd;
+
+use failure::{format_err, Error, Fail};
+use log::{error, info, warn};
+use serde_derive::{Deserialize, Serialize};
+
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{self, Duration, Instant, SystemTime};
+
+const JOBS_DIR: &str = "/haste/jobs";
+// seconds of the cluster lock to live without refresh
+const LOCK_TTL: u64 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Done,
+    Error,
+}
+
+impl JobState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobState::Running => "running",
+            JobState::Done => "done",
+            JobState::Error => "error",
+        }
+    }
+
+    fn parse(state: &str) -> Result<JobState, Error> {
+        match state {
+            "running" => Ok(JobState::Running),
+            "done" => Ok(JobState::Done),
+            "error" => Ok(JobState::Error),
+            _ => Err(format_err!("bad job state {}", state)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    // deploy, remove, restart ...
+    pub kind: String,
+    pub cluster: String,
+    pub state: JobState,
+    // the error of failed job
+    pub msg: String,
+    pub start: u64,
+    pub done: Option<u64>,
+    pub latest_update: u64,
+}
+
+impl Job {
+    pub fn new(id: &str, kind: &str, cluster: &str) -> Job {
+        let now = now();
+        Job {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            cluster: cluster.to_string(),
+            state: JobState::Running,
+            msg: String::new(),
+            start: now,
+            done: None,
+            latest_update: now,
+        }
+    }
+
+    /// load the job, `None` if it's not exists.
+    pub fn load(myetcd: &MyEtcd, id: &str) -> Result<Option<Job>, Error> {
+        let fields: HashMap<_, _> = myetcd
+            .list(&job_dir(id))?
+            .into_iter()
+            .filter_map(|(key, value)| key.rsplit('/').next().map(|x| (x.to_string(), value)))
+            .collect();
+        if fields.is_empty() {
+            return Ok(None);
+        }
+
+        let get = |name: &str| fields.get(name).cloned().unwrap_or_default();
+        let get_u64 = |name: &str| fields.get(name).and_then(|x| x.parse().ok());
+        Ok(Some(Job {
+            id: id.to_string(),
+            kind: get("kind"),
+            cluster: get("cluster"),
+            state: JobState::parse(&get("state"))?,
+            msg: get("msg"),
+            start: get_u64("start").unwrap_or(0),
+            done: get_u64("done"),
+            latest_update: get_u64("latest_update").unwrap_or(0),
+        }))
+    }
+
+    /// save the job and append it into the audit log of cluster.
+    pub fn save(&self, myetcd: &MyEtcd) -> Result<(), Error> {
+        let dir = job_dir(&self.id);
+        myetcd.set(&format!("{}/kind", dir), &self.kind)?;
+        myetcd.set(&format!("{}/cluster", dir), &self.cluster)?;
+        myetcd.set(&format!("{}/state", dir), self.state.as_str())?;
+        myetcd.set(&format!("{}/msg", dir), &self.msg)?;
+        myetcd.set(&format!("{}/start", dir), &format!("{}", self.start))?;
+        if let Some(done) = self.done {
+            myetcd.set(&format!("{}/done", dir), &format!("{}", done))?;
+        }
+        myetcd.set(
+            &format!("{}/latest_update", dir),
+            &format!("{}", self.latest_update),
+        )?;
+
+        if !self.cluster.is_empty() {
+            let audit = format!("/haste/clusters/{}/audit/{}", self.cluster, self.id);
+            myetcd.set(&format!("{}/kind", audit), &self.kind)?;
+            myetcd.set(&format!("{}/state", audit), self.state.as_str())?;
+        }
+        Ok(())
+    }
+
+    /// mark the job as done or error by the result.
+    pub fn finish(&mut self, rslt: &Result<(), Error>) {
+        let now = now();
+        match rslt {
+            Ok(()) => self.state = JobState::Done,
+            Err(err) => {
+                self.state = JobState::Error;
+                self.msg = format!("{}", err);
+            }
+        }
+        self.done = Some(now);
+        self.latest_update = now;
+    }
+}
+
+/// save the job as running and run it in background, the job is updated when the work
+/// returns. the etcd of addr is opened in the thread since `MyEtcd` can't be shared.
+pub fn spawn_job<F>(myetcd: &MyEtcd, etcd_addr: &str, mut job: Job, work: F) -> Result<Job, Error>
+where
+    F: FnOnce() -> Result<(), Error> + Send + 'static,
+{
+    job.save(myetcd)?;
+    info!(
+        "start {} job {} of cluster {}",
+        job.kind, job.id, job.cluster
+    );
+
+    let etcd_addr = etcd_addr.to_string();
+    let running = job.clone();
+    thread::spawn(move || {
+        let start = Instant::now();
+        JOBS_RUNNING.with_label_values(&[&job.kind]).inc();
+        // a panic fails the job, the values owned by work like locks are dropped by then
+        let rslt = panic::catch_unwind(AssertUnwindSafe(work))
+            .unwrap_or_else(|cause| Err(format_err!("job panicked: {}", panic_msg(&*cause))));
+        job.finish(&rslt);
+        JOBS_RUNNING.with_label_values(&[&job.kind]).dec();
+        JOBS.with_label_values(&[&job.kind, job.state.as_str()])
+            .inc();
+        JOB_DURATION
+            .with_label_values(&[&job.kind, job.state.as_str()])
+            .observe(start.elapsed().as_millis() as f64 / 1000.0);
+        info!(
+            "{} job {} finished as {}",
+            job.kind,
+            job.id,
+            job.state.as_str()
+        );
+        if let Err(err) = MyEtcd::open(&etcd_addr).and_then(|myetcd| job.save(&myetcd)) {
+            error!("fail to save job {} due {}", job.id, err);
+        }
+    });
+    Ok(running)
+}
+
+fn panic_msg(cause: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = cause.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = cause.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown"
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum DeployConflict {
+    #[fail(display = "cluster {} is already exists", _0)]
+    Exists(String),
+}
+
+/// the cluster is locked by another job.
+#[derive(Debug, Fail)]
+#[fail(display = "cluster {} is busy with {}", name, holder)]
+pub struct ClusterBusy {
+    pub name: String,
+    pub holder: String,
+}
+
+/// the lock of cluster, so only one of deploy, upgrade, reconfig, actions and removes can run
+/// on it at a time.
+/// it's saved as `/haste/clusters/{name}/lock` -> `{kind} {lock_id}` with `LOCK_TTL`, which is
+/// refreshed in background until it's released, so the lock of a crashed leader expires.
+/// the lock is released when it's dropped, so move it into the job to hold it until the job
+/// is finished.
+pub struct ClusterLock {
+    etcd: String,
+    key: String,
+    holder: String,
+    released: Arc<AtomicBool>,
+}
+
+impl ClusterLock {
+    /// lock the cluster for the job of kind, it's a `ClusterBusy` if it's locked.
+    pub fn acquire(
+        myetcd: &MyEtcd,
+        etcd: &str,
+        name: &str,
+        kind: &str,
+    ) -> Result<ClusterLock, Error> {
+        let key = lock_key(name);
+        let holder = format!("{} {}", kind, gen_job_id(name));
+        if !myetcd.create_ttl(&key, &holder, LOCK_TTL)? {
+            let holder = myetcd.get_value(&key)?.unwrap_or_default();
+            return Err(ClusterBusy {
+                name: name.to_string(),
+                holder,
+            }
+            .into());
+        }
+
+        let lock = ClusterLock {
+            etcd: etcd.to_string(),
+            key,
+            holder,
+            released: Arc::new(AtomicBool::new(false)),
+        };
+        lock.keep_alive();
+        Ok(lock)
+    }
+
+    // delete the lock if it's still held by us.
+    fn release(&self) {
+        if self.released.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let rslt = MyEtcd::open(&self.etcd)
+            .and_then(|myetcd| myetcd.compare_and_delete(&self.key, &self.holder));
+        match rslt {
+            Ok(true) => {}
+            Ok(false) => warn!("lock {} of {} was already lost", self.key, self.holder),
+            Err(err) => error!(
+                "fail to delete lock {} of {} due {}",
+                self.key, self.holder, err
+            ),
+        }
+    }
+
+    fn keep_alive(&self) {
+        let (etcd, key, holder) = (self.etcd.clone(), self.key.clone(), self.holder.clone());
+        let released = self.released.clone();
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_secs(LOCK_TTL / 3));
+            if released.load(Ordering::SeqCst) {
+                return;
+            }
+            let rslt =
+                MyEtcd::open(&etcd).and_then(|myetcd| myetcd.refresh(&key, &holder, LOCK_TTL));
+            match rslt {
+                Ok(true) => {}
+                Ok(false) => {
+                    error!("lock {} of {} was lost", key, holder);
+                    return;
+                }
+                // try again before it's expired
+                Err(err) => warn!("fail to refresh lock {} due {}", key, err),
+            }
+        });
+    }
+}
+
+impl Drop for ClusterLock {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// the holder of lock of cluster, `None` if it's not locked.
+pub fn cluster_lock_holder(myetcd: &MyEtcd, name: &str) -> Result<Option<String>, Error> {
+    myetcd.get_value(&lock_key(name))
+}
+
+pub enum DeployStart {
+    // the plan of dry run, which was discarded
+    Planned(DeployPlan),
+    Started(Job),
+}
+
+/// plan the deploy of cluster and apply it as a job with the id of plan. the cluster is locked
+/// until the job is finished, so it's a `DeployConflict` if the cluster is exists or a
+/// `ClusterBusy` if another job is running on it.
+pub fn spawn_deploy(etcd: &str, param: &DeployParm, dry_run: bool) -> Result<DeployStart, Error> {
+    let myetcd = MyEtcd::open(etcd)?;
+    let name = param.name.clone();
+    if myetcd
+        .get_value(&format!("/haste/clusters/{}/cache_type", name))?
+        .is_some()
+    {
+        return Err(DeployConflict::Exists(name).into());
+    }
+    let lock = ClusterLock::acquire(&myetcd, etcd, &name, "deploy")?;
+
+    let mut task = DeployTask::new(param.clone(), MyEtcd::open(etcd)?);
+    let plan = task.plan()?;
+    if dry_run {
+        return task.discard(&plan).map(|_| DeployStart::Planned(plan));
+    }
+
+    let job = Job::new(&plan.job_id, "deploy", &name);
+    let addr = etcd.to_string();
+    let job_plan = plan.clone();
+    let rslt = spawn_job(&myetcd, etcd, job, move || {
+        let _lock = lock;
+        MyEtcd::open(&addr)
+            .and_then(|myetcd| DeployTask::new(job_plan.param.clone(), myetcd).apply(&job_plan))
+    });
+    match rslt {
+        Ok(job) => Ok(DeployStart::Started(job)),
+        Err(err) => {
+            if let Err(err) = task.discard(&plan) {
+                error!("fail to discard plan {} due {}", plan.job_id, err);
+            }
+            Err(err)
+        }
+    }
+}
+
+/// all the jobs, the latest first.
+pub fn list_jobs(myetcd: &MyEtcd) -> Result<Vec<Job>, Error> {
+    let ids: Vec<_> = myetcd
+        .list_keys(JOBS_DIR)?
+        .into_iter()
+        .filter_map(|key| key.rsplit('/').next().map(|x| x.to_string()))
+        .collect();
+    load_jobs(myetcd, &ids)
+}
+
+/// the jobs in the audit log of cluster, the latest first.
+pub fn audit_log(myetcd: &MyEtcd, cluster: &str) -> Result<Vec<Job>, Error> {
+    let dir = format!("/haste/clusters/{}/audit", cluster);
+    let ids: Vec<_> = myetcd
+        .list_keys(&dir)?
+        .into_iter()
+        .filter_map(|key| key.rsplit('/').next().map(|x| x.to_string()))
+        .collect();
+    load_jobs(myetcd, &ids)
+}
+
+/// the job id of `{name}-{timestamp in millis}`.
+pub fn gen_job_id(name: &str) -> String {
+    let now = SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap();
+    format!("{}-{}{:03}", name, now.as_secs(), now.subsec_millis())
+}
+
+/// the id must be made by `gen_job_id`, so it's safe in etcd keys and file paths.
+pub fn check_job_id(id: &str) -> Result<(), String> {
+    let (name, millis) = match id.rfind('-') {
+        Some(pos) => (&id[..pos], &id[pos + 1..]),
+        None => return Err(format!("bad job id {}", id)),
+    };
+    check_name("job", name)?;
+    if millis.is_empty() || !millis.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("bad job id {}", id));
+    }
+    Ok(())
+}
+
+fn load_jobs(myetcd: &MyEtcd, ids: &[String]) -> Result<Vec<Job>, Error> {
+    let mut jobs = Vec::new();
+    for id in ids {
+        // the audit of deploys before the job store has no job
+        if let Some(job) = Job::load(myetcd, id)? {
+            jobs.push(job);
+        }
+    }
+    jobs.sort_by_key(|x| Reverse(x.start));
+    Ok(jobs)
+}
+
+fn lock_key(name: &str) -> String {
+    format!("/haste/clusters/{}/lock", name)
+}
+
+fn job_dir(id: &str) -> String {
+    format!("{}/{}", JOBS_DIR, id)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_of_panic() {
+        let cause = panic::catch_unwind(|| panic!("boom")).unwrap_err();
+        assert_eq!(panic_msg(&*cause), "boom");
+        let cause = panic::catch_unwind(|| panic!("bad port {}", 7000)).unwrap_err();
+        assert_eq!(panic_msg(&*cause), "bad port 7000");
+        let cause = panic::catch_unwind(|| panic::panic_any(7000)).unwrap_err();
+        assert_eq!(panic_msg(&*cause), "unknown");
+    }
+
+    #[test]
+    fn only_generated_job_ids() {
+        assert!(check_job_id(&gen_job_id("mycluster")).is_ok());
+        assert!(check_job_id(&gen_job_id(&"a".repeat(64))).is_ok());
+        assert!(check_job_id("mycluster").is_err());
+        assert!(check_job_id("mycluster-").is_err());
+        assert!(check_job_id("-1571472000000").is_err());
+        assert!(check_job_id("../..-1571472000000").is_err());
+        assert!(check_job_id("/etc/passwd-1").is_err());
+        assert!(check_job_id("mycluster-15714x").is_err());
+    }
+}
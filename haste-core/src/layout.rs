@@ -0,0 +1,100 @@
+//! the layout of instance files, shared by leader and agent.
+//!
+//!   {data_root}/{port}/                                -> data dir of instance
+//!   {data_root}/{port}/redis.conf
+//!   {data_root}/{port}/nodes.conf
+//!   /etc/systemd/system/cache-{cluster}-{port}.service -> systemd unit
+//!   /data/haste/lib/{type}/{version}/{bin}             -> binary
+//!
+//! `data_root` is configured by each agent and reported in it's offer, so the leader
+//! renders the paths of each host by it's own layout.
+
+use crate::systemd::service_name;
+
+use failure::Error;
+use serde_derive::{Deserialize, Serialize};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_DATA_ROOT: &str = "/data/cache";
+pub const UNIT_DIR: &str = "/etc/systemd/system";
+pub const LIB_DIR: &str = "/data/haste/lib";
+
+/// the data roots used by old versions, instances under them are migrated.
+pub const LEGACY_DATA_ROOTS: &[&str] = &["/data/cache", "/data/haste/instance"];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstanceLayout {
+    pub data_root: String,
+}
+
+impl Default for InstanceLayout {
+    fn default() -> Self {
+        InstanceLayout::new(DEFAULT_DATA_ROOT)
+    }
+}
+
+impl InstanceLayout {
+    pub fn new(data_root: &str) -> InstanceLayout {
+        InstanceLayout {
+            data_root: data_root.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn instance_dir(&self, port: i64) -> PathBuf {
+        Path::new(&self.data_root).join(format!("{}", port))
+    }
+
+    pub fn redis_conf(&self, port: i64) -> PathBuf {
+        self.instance_dir(port).join("redis.conf")
+    }
+
+    pub fn nodes_conf(&self, port: i64) -> PathBuf {
+        self.instance_dir(port).join("nodes.conf")
+    }
+
+    pub fn unit_path(&self, cluster: &str, port: i64) -> PathBuf {
+        Path::new(UNIT_DIR).join(service_name(cluster, port))
+    }
+
+    pub fn binary_path(&self, cache_type: &str, version: &str, bin: &str) -> PathBuf {
+        let mut pb = PathBuf::from(LIB_DIR);
+        pb.push(cache_type);
+        pb.push(version);
+        pb.push(bin);
+        pb
+    }
+
+    /// the dirs which rendered files can be written into, the units of instances in
+    /// `UNIT_DIR` are allowed by their names.
+    pub fn roots(&self) -> Vec<String> {
+        vec![self.data_root.clone()]
+    }
+
+    /// ports of all the instances which have data dir.
+    pub fn instance_ports(&self) -> Result<Vec<i64>, Error> {
+        ports_under(&self.data_root)
+    }
+}
+
+/// ports of the instance dirs under root, an empty list if root not exists.
+pub fn ports_under(root: &str) -> Result<Vec<i64>, Error> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut ports = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Ok(port) = entry.file_name().to_string_lossy().parse::<i64>() {
+            ports.push(port);
+        }
+    }
+    ports.sort();
+    Ok(ports)
+}
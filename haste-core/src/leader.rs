@@ -0,0 +1,191 @@
+//! the leader process, which serves the api and metrics, reconciles the specs and checks the
+//! health of clusters.
+
+use crate::api::spawn_api;
+use crate::deploy::plan::{cache_type_name, parse_cache_type};
+use crate::exporter::{
+    spawn_exporter, CLUSTER_HIT_RATIO, CLUSTER_INSTANCES, CLUSTER_OPS, CLUSTER_REPL_LAG, CLUSTER_UP,
+};
+use crate::myetcd::MyEtcd;
+use crate::mymemcache::MyMemcache;
+use crate::proto::CacheType;
+use crate::reconcile::spawn_reconcile;
+use crate::stats::{cluster_instances, scrape_cluster};
+
+use failure::Error;
+use log::{info, warn};
+use serde_derive::{Deserialize, Serialize};
+
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+pub const LEADER_CONFIG_FILE: &str = "/etc/haste/leader.toml";
+
+/// config of leader, loaded from toml file like:
+///
+/// ```toml
+/// etcd = "http://etcd.example.com:2379"
+/// listen = "0.0.0.0:7791"
+/// metrics_listen = "0.0.0.0:7790"
+/// health_interval = 30
+/// reconcile_interval = 60
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeaderConfig {
+    pub etcd: String,
+    // the http api is served on it
+    pub listen: String,
+    // `GET /metrics` is served on it
+    pub metrics_listen: String,
+    // seconds between health checks of all the clusters
+    pub health_interval: u64,
+    // seconds between reconciles of all the specs
+    pub reconcile_interval: u64,
+}
+
+impl Default for LeaderConfig {
+    fn default() -> Self {
+        LeaderConfig {
+            etcd: "http://127.0.0.1:2379".to_string(),
+            listen: "0.0.0.0:7791".to_string(),
+            metrics_listen: "0.0.0.0:7790".to_string(),
+            health_interval: 30,
+            reconcile_interval: 60,
+        }
+    }
+}
+
+impl LeaderConfig {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<LeaderConfig, Error> {
+        let data = fs::read_to_string(path)?;
+        Ok(toml::from_str(&data)?)
+    }
+}
+
+// the health of a cluster in one check
+#[derive(Default)]
+struct ClusterHealth {
+    reachable: usize,
+    unreachable: usize,
+    ops_per_sec: u64,
+    hit_ratio: f64,
+    repl_lag: u64,
+}
+
+/// run the leader until it was killed:
+///   1. serve api and metrics
+///   2. reconcile the specs in background
+///   3. check the health of all the clusters every interval
+pub fn serve(config: LeaderConfig) -> Result<(), Error> {
+    let myetcd = MyEtcd::open(&config.etcd)?;
+    spawn_api(&config.listen, &config.etcd)?;
+    spawn_exporter(&config.metrics_listen)?;
+    spawn_reconcile(&config.etcd, config.reconcile_interval);
+
+    loop {
+        if let Err(err) = check_clusters(&myetcd) {
+            warn!("fail to check health of clusters due {}", err);
+        }
+        thread::sleep(Duration::from_secs(config.health_interval.max(1)));
+    }
+}
+
+// check all the clusters in `/haste/clusters` and publish their health, the gauges of
+// removed clusters are dropped.
+fn check_clusters(myetcd: &MyEtcd) -> Result<(), Error> {
+    let names: Vec<_> = myetcd
+        .list_keys("/haste/clusters")?
+        .into_iter()
+        .filter_map(|key| key.rsplit('/').next().map(|x| x.to_string()))
+        .collect();
+
+    let mut healths = Vec::new();
+    for name in names {
+        match check_cluster(myetcd, &name) {
+            Ok(health) => healths.push((name, health)),
+            Err(err) => warn!("fail to check health of cluster {} due {}", name, err),
+        }
+    }
+
+    CLUSTER_UP.reset();
+    CLUSTER_INSTANCES.reset();
+    CLUSTER_OPS.reset();
+    CLUSTER_HIT_RATIO.reset();
+    CLUSTER_REPL_LAG.reset();
+    for (name, health) in &healths {
+        let name = name.as_str();
+        CLUSTER_UP
+            .with_label_values(&[name])
+            .set((health.unreachable == 0) as i64);
+        CLUSTER_INSTANCES
+            .with_label_values(&[name, "reachable"])
+            .set(health.reachable as i64);
+        CLUSTER_INSTANCES
+            .with_label_values(&[name, "unreachable"])
+            .set(health.unreachable as i64);
+        CLUSTER_OPS
+            .with_label_values(&[name])
+            .set(health.ops_per_sec as i64);
+        CLUSTER_HIT_RATIO
+            .with_label_values(&[name])
+            .set(health.hit_ratio);
+        CLUSTER_REPL_LAG
+            .with_label_values(&[name])
+            .set(health.repl_lag as i64);
+    }
+    info!("check health of {} clusters done", healths.len());
+    Ok(())
+}
+
+fn check_cluster(myetcd: &MyEtcd, name: &str) -> Result<ClusterHealth, Error> {
+    let cache_type = match myetcd.get_value(&format!("/haste/clusters/{}/cache_type", name))? {
+        Some(cache_type) => parse_cache_type(&cache_type)?,
+        None => CacheType::Redis,
+    };
+    if let CacheType::Memcache = cache_type {
+        return check_memcache_cluster(myetcd, name);
+    }
+
+    let stats = scrape_cluster(myetcd, name)?;
+    info!(
+        "{} cluster {} has {} unreachable instances",
+        cache_type_name(cache_type),
+        name,
+        stats.unreachable
+    );
+    Ok(ClusterHealth {
+        reachable: stats.instances.len() - stats.unreachable,
+        unreachable: stats.unreachable,
+        ops_per_sec: stats.ops_per_sec,
+        hit_ratio: stats.hit_ratio(),
+        repl_lag: stats.repl_lag,
+    })
+}
+
+fn check_memcache_cluster(myetcd: &MyEtcd, name: &str) -> Result<ClusterHealth, Error> {
+    let mut health = ClusterHealth::default();
+    let (mut hits, mut misses) = (0, 0);
+    for addr in cluster_instances(myetcd, name)? {
+        match MyMemcache::open(&addr).and_then(|mut x| x.stats()) {
+            Ok(stats) => {
+                health.reachable += 1;
+                hits += stats.get_hits;
+                misses += stats.get_misses;
+            }
+            Err(err) => {
+                warn!(
+                    "memcache {} of cluster {} is unreachable due {}",
+                    addr, name, err
+                );
+                health.unreachable += 1;
+            }
+        }
+    }
+    if hits + misses != 0 {
+        health.hit_ratio = hits as f64 / (hits + misses) as f64;
+    }
+    Ok(health)
+}
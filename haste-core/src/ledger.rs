@@ -0,0 +1,371 @@
+//! reservation ledger of each agent, saved in etcd as
+//!   /haste/ledger/{ip} -> json of Ledger
+//!
+//! every deployed instance holds a reservation of it's port with the cpu and memory it uses.
+//! reservations are created as `Planned` when a deploy was planned, turned into `Committed`
+//! when the deploy is done and removed when the deploy was rolled back.
+//!
+//! the ledger was updated by compare and swap, so concurrent deploys never over-commit a host.
+
+use crate::chunk::Chunks;
+use crate::deploy::server::DeployParm;
+use crate::myetcd::MyEtcd;
+use crate::offer::{get_capacity, Offer};
+
+use failure::{format_err, Error};
+use log::{debug, info, warn};
+use serde_derive::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{self, SystemTime};
+
+const LEDGER_DIR: &str = "/haste/ledger";
+const MAX_CAS_RETRY: usize = 16;
+// planned but never applied reservations are expired after one day
+const PLANNED_TTL: u64 = 24 * 3600;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReserveState {
+    Planned,
+    Committed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Reservation {
+    pub job_id: String,
+    pub cluster: String,
+    pub cpu: usize,
+    pub memory: usize,
+    pub state: ReserveState,
+    pub updated_at: u64,
+}
+
+impl Reservation {
+    fn is_expired(&self, now: u64) -> bool {
+        self.state == ReserveState::Planned && self.updated_at + PLANNED_TTL < now
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    // port -> reservation
+    pub reservations: BTreeMap<usize, Reservation>,
+}
+
+impl Ledger {
+    /// load the ledger of the host with it's modified index.
+    pub fn load(myetcd: &MyEtcd, host: &str) -> Result<(Ledger, Option<u64>), Error> {
+        match myetcd.get_indexed(&ledger_key(host))? {
+            Some((value, index)) => Ok((serde_json::from_str(&value)?, Some(index))),
+            None => Ok((Ledger::default(), None)),
+        }
+    }
+
+    /// the capacity of offer minus all the alive reservations, which is also limited by the
+    /// actual free resources of host if the agent reported them recently.
+    pub fn available(&self, offer: &Offer) -> Offer {
+        let now = now();
+        let mut cpu = 0;
+        let mut memory = 0;
+        let mut ports = HashSet::new();
+        for (port, rsv) in self.reservations.iter() {
+            if rsv.is_expired(now) {
+                continue;
+            }
+            cpu += rsv.cpu;
+            memory += rsv.memory;
+            ports.insert(*port);
+        }
+
+        let mut cpu = offer.cpu.saturating_sub(cpu);
+        let mut memory = offer.memory.saturating_sub(memory);
+        if let Some(usage) = offer.usage.as_ref().filter(|x| x.is_fresh(now)) {
+            cpu = cpu.min(usage.cpu);
+            memory = memory.min(usage.memory);
+        }
+
+        Offer {
+            host: offer.host.clone(),
+            cpu,
+            memory,
+            ports: offer
+                .ports
+                .iter()
+                .cloned()
+                .filter(|x| !ports.contains(x))
+                .collect(),
+            data_root: offer.data_root.clone(),
+            usage: offer.usage.clone(),
+        }
+    }
+
+    fn prune(&mut self) {
+        let now = now();
+        self.reservations.retain(|_, rsv| !rsv.is_expired(now));
+    }
+}
+
+/// reserve the resources of all the instances in chunks as `Planned`.
+///
+/// ports which were already reserved by the same job are kept, so it's safe to
+/// reserve a plan again before apply it.
+pub fn reserve(
+    myetcd: &MyEtcd,
+    job_id: &str,
+    param: &DeployParm,
+    chunks: &Chunks,
+) -> Result<(), Error> {
+    let mut done: Vec<String> = Vec::new();
+    for (host, ports) in group_by_host(chunks) {
+        if let Err(err) = reserve_host(myetcd, &host, job_id, param, &ports) {
+            for host in &done {
+                if let Err(err) = update(myetcd, host, |ledger| remove_job(ledger, job_id)) {
+                    warn!("fail to rollback reservation of {} due {}", host, err);
+                }
+            }
+            return Err(err);
+        }
+        done.push(host);
+    }
+    info!("reserve resources for job {} done", job_id);
+    Ok(())
+}
+
+/// mark all the reservations of job as `Committed`.
+pub fn commit(myetcd: &MyEtcd, job_id: &str, chunks: &Chunks) -> Result<(), Error> {
+    for host in group_by_host(chunks).keys() {
+        update(myetcd, host, |ledger| {
+            let now = now();
+            for rsv in ledger.reservations.values_mut() {
+                if rsv.job_id == job_id {
+                    rsv.state = ReserveState::Committed;
+                    rsv.updated_at = now;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    info!("commit reservations of job {} done", job_id);
+    Ok(())
+}
+
+/// remove all the reservations of job.
+pub fn rollback(myetcd: &MyEtcd, job_id: &str, chunks: &Chunks) -> Result<(), Error> {
+    for host in group_by_host(chunks).keys() {
+        update(myetcd, host, |ledger| remove_job(ledger, job_id))?;
+    }
+    info!("rollback reservations of job {} done", job_id);
+    Ok(())
+}
+
+/// release the reservation of one removed instance.
+pub fn release(myetcd: &MyEtcd, host: &str, port: usize) -> Result<(), Error> {
+    update(myetcd, host, |ledger| {
+        ledger.reservations.remove(&port);
+        Ok(())
+    })
+}
+
+fn reserve_host(
+    myetcd: &MyEtcd,
+    host: &str,
+    job_id: &str,
+    param: &DeployParm,
+    ports: &[usize],
+) -> Result<(), Error> {
+    let capacity = get_capacity(myetcd, host)?
+        .ok_or_else(|| format_err!("agent {} has no offer registered", host))?;
+
+    update(myetcd, host, |ledger| {
+        ledger.prune();
+
+        let mut need = Vec::new();
+        for port in ports {
+            match ledger.reservations.get(port) {
+                Some(rsv) if rsv.job_id == job_id => {}
+                Some(rsv) => {
+                    return Err(format_err!(
+                        "port {}:{} was already reserved by job {}",
+                        host,
+                        port,
+                        rsv.job_id
+                    ));
+                }
+                None => need.push(*port),
+            }
+        }
+
+        let avail = ledger.available(&capacity);
+        if let Some(port) = need.iter().find(|x| !avail.ports.contains(x)) {
+            return Err(format_err!("port {}:{} is not offered", host, port));
+        }
+        if avail.cpu < param.cpu_percent * need.len()
+            || avail.memory < param.max_memory * need.len()
+        {
+            return Err(format_err!(
+                "not enough resource in {}, available cpu {} memory {}",
+                host,
+                avail.cpu,
+                avail.memory
+            ));
+        }
+
+        let now = now();
+        for port in need {
+            ledger.reservations.insert(
+                port,
+                Reservation {
+                    job_id: job_id.to_string(),
+                    cluster: param.name.clone(),
+                    cpu: param.cpu_percent,
+                    memory: param.max_memory,
+                    state: ReserveState::Planned,
+                    updated_at: now,
+                },
+            );
+        }
+        Ok(())
+    })
+}
+
+fn remove_job(ledger: &mut Ledger, job_id: &str) -> Result<(), Error> {
+    ledger.reservations.retain(|_, rsv| rsv.job_id != job_id);
+    Ok(())
+}
+
+// read-modify-write the ledger of host by compare and swap
+fn update<F>(myetcd: &MyEtcd, host: &str, mut f: F) -> Result<(), Error>
+where
+    F: FnMut(&mut Ledger) -> Result<(), Error>,
+{
+    let key = ledger_key(host);
+    for _ in 0..MAX_CAS_RETRY {
+        let (mut ledger, index) = Ledger::load(myetcd, host)?;
+        f(&mut ledger)?;
+
+        let value = serde_json::to_string(&ledger)?;
+        let swapped = match index {
+            Some(index) => myetcd.compare_and_swap(&key, &value, index)?,
+            None => myetcd.create(&key, &value)?,
+        };
+        if swapped {
+            return Ok(());
+        }
+        debug!("ledger of {} was changed by others, retry", host);
+    }
+    Err(format_err!(
+        "fail to update ledger of {} due too many conflicts",
+        host
+    ))
+}
+
+fn group_by_host(chunks: &Chunks) -> HashMap<String, Vec<usize>> {
+    let mut hosts = HashMap::new();
+    for inst in &chunks.0[..] {
+        hosts
+            .entry(inst.host.clone())
+            .or_insert_with(Vec::new)
+            .push(inst.port);
+    }
+    hosts
+}
+
+fn ledger_key(host: &str) -> String {
+    format!("{}/{}", LEDGER_DIR, host)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::offer::Usage;
+
+    fn reservation(state: ReserveState, cpu: usize, memory: usize, updated_at: u64) -> Reservation {
+        Reservation {
+            job_id: "job".to_string(),
+            cluster: "mycluster".to_string(),
+            cpu,
+            memory,
+            state,
+            updated_at,
+        }
+    }
+
+    fn offer(usage: Option<Usage>) -> Offer {
+        Offer {
+            host: "10.0.0.1".to_string(),
+            cpu: 800,
+            memory: 64 * 1024,
+            ports: vec![7000, 7001, 7002, 7003],
+            data_root: "/data/cache".to_string(),
+            usage,
+        }
+    }
+
+    #[test]
+    fn available_minus_reservations() {
+        let now = now();
+        let mut ledger = Ledger::default();
+        ledger.reservations.insert(
+            7000,
+            reservation(ReserveState::Committed, 100, 4096, now - PLANNED_TTL * 2),
+        );
+        ledger
+            .reservations
+            .insert(7001, reservation(ReserveState::Planned, 100, 4096, now));
+        // expired, so it's free again
+        ledger.reservations.insert(
+            7002,
+            reservation(ReserveState::Planned, 100, 4096, now - PLANNED_TTL - 1),
+        );
+
+        let available = ledger.available(&offer(None));
+        assert_eq!(available.cpu, 600);
+        assert_eq!(available.memory, 56 * 1024);
+        assert_eq!(available.ports, vec![7002, 7003]);
+    }
+
+    #[test]
+    fn available_limited_by_fresh_usage() {
+        let now = now();
+        let mut ledger = Ledger::default();
+        ledger
+            .reservations
+            .insert(7000, reservation(ReserveState::Committed, 100, 4096, now));
+
+        let usage = Usage {
+            timestamp: now,
+            cpu: 200,
+            memory: 80 * 1024,
+        };
+        let available = ledger.available(&offer(Some(usage.clone())));
+        assert_eq!(available.cpu, 200);
+        assert_eq!(available.memory, 60 * 1024);
+
+        let stale = Usage {
+            timestamp: 0,
+            ..usage
+        };
+        let available = ledger.available(&offer(Some(stale)));
+        assert_eq!(available.cpu, 700);
+        assert_eq!(available.memory, 60 * 1024);
+    }
+
+    #[test]
+    fn never_below_zero() {
+        let mut ledger = Ledger::default();
+        ledger.reservations.insert(
+            7000,
+            reservation(ReserveState::Committed, 1000, 128 * 1024, now()),
+        );
+        let available = ledger.available(&offer(None));
+        assert_eq!(available.cpu, 0);
+        assert_eq!(available.memory, 0);
+    }
+}
@@ -48,16 +48,31 @@
 
 #[macro_use]
 extern crate tera;
+#[macro_use]
+extern crate prometheus;
 
 use haste_info::say;
 
+pub mod api;
 pub mod chunk;
 pub mod deploy;
+pub mod exporter;
+pub mod job;
+pub mod layout;
+pub mod leader;
+pub mod ledger;
 pub mod myetcd;
+pub mod mymemcache;
 pub mod myredis;
 pub mod offer;
 mod protos;
+pub mod reconcile;
+pub mod reconfig;
+pub mod spec;
+pub mod stats;
+pub mod supervisor;
 pub mod systemd;
+pub mod upgrade;
 
 pub use self::protos::agent::agent as proto;
 pub use self::protos::agent::agent_grpc as proto_grpc;
@@ -1,11 +1,16 @@
 use etcd::kv::{self, GetOptions, KeyValueInfo};
 use etcd::{Client, Response};
-use failure::Error;
+use failure::{format_err, Error};
 use futures::Future;
 use hyper::client::HttpConnector;
 use log::debug;
 use tokio::runtime::Runtime;
 
+// etcd v2 error codes
+const ERR_KEY_NOT_FOUND: u64 = 100;
+const ERR_TEST_FAILED: u64 = 101;
+const ERR_NODE_EXIST: u64 = 105;
+
 pub struct MyEtcd {
     client: Client<HttpConnector>,
 }
@@ -23,8 +28,7 @@ impl MyEtcd {
             debug!("set response as {:?}", response);
             Ok(response)
         });
-        let value = Runtime::new().unwrap().block_on(work).unwrap();
-        Ok(value)
+        block_on(work)
     }
 
     pub fn setnx(&self, key: &str, val: &str, ttl: u64) -> Result<Response<KeyValueInfo>, Error> {
@@ -33,8 +37,7 @@ impl MyEtcd {
             debug!("set response as {:?}", response);
             Ok(response)
         });
-        let value = Runtime::new().unwrap().block_on(work).unwrap();
-        Ok(value)
+        block_on(work)
     }
 
     pub fn get(&self, key: &str) -> Result<Response<KeyValueInfo>, Error> {
@@ -42,8 +45,153 @@ impl MyEtcd {
             debug!("get response as {:?}", response);
             Ok(response)
         });
-        let value = Runtime::new().unwrap().block_on(work).unwrap();
-        Ok(value)
+        block_on(work)
+    }
+
+    /// get the value of the key, `None` if the key is not exists.
+    pub fn get_value(&self, key: &str) -> Result<Option<String>, Error> {
+        let work = kv::get(&self.client, key, GetOptions::default()).then(|rslt| match rslt {
+            Ok(response) => Ok(response.data.node.value),
+            Err(ref errs) if has_code(errs, ERR_KEY_NOT_FOUND) => Ok(None),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
+    }
+
+    /// get the value with it's modified index, which can be used by `compare_and_swap`.
+    pub fn get_indexed(&self, key: &str) -> Result<Option<(String, u64)>, Error> {
+        let work = kv::get(&self.client, key, GetOptions::default()).then(|rslt| match rslt {
+            Ok(response) => {
+                let node = response.data.node;
+                let index = node.modified_index;
+                Ok(node.value.and_then(|val| index.map(|idx| (val, idx))))
+            }
+            Err(ref errs) if has_code(errs, ERR_KEY_NOT_FOUND) => Ok(None),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
+    }
+
+    /// list all the direct children of the dir as (key, value).
+    pub fn list(&self, dir: &str) -> Result<Vec<(String, String)>, Error> {
+        let opts = GetOptions {
+            sort: true,
+            ..GetOptions::default()
+        };
+        let work = kv::get(&self.client, dir, opts).then(|rslt| match rslt {
+            Ok(response) => Ok(response
+                .data
+                .node
+                .nodes
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|node| match (node.key, node.value) {
+                    (Some(key), Some(value)) => Some((key, value)),
+                    _ => None,
+                })
+                .collect()),
+            Err(ref errs) if has_code(errs, ERR_KEY_NOT_FOUND) => Ok(Vec::new()),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
+    }
+
+    /// keys of all the children of dir, include the sub dirs.
+    pub fn list_keys(&self, dir: &str) -> Result<Vec<String>, Error> {
+        let opts = GetOptions {
+            sort: true,
+            ..GetOptions::default()
+        };
+        let work = kv::get(&self.client, dir, opts).then(|rslt| match rslt {
+            Ok(response) => Ok(response
+                .data
+                .node
+                .nodes
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|node| node.key)
+                .collect()),
+            Err(ref errs) if has_code(errs, ERR_KEY_NOT_FOUND) => Ok(Vec::new()),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
+    }
+
+    /// create the key only if it's not exists, return false if it was exists.
+    pub fn create(&self, key: &str, val: &str) -> Result<bool, Error> {
+        let work = kv::create(&self.client, key, val, None).then(|rslt| match rslt {
+            Ok(_) => Ok(true),
+            Err(ref errs) if has_code(errs, ERR_NODE_EXIST) => Ok(false),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
+    }
+
+    /// create the key with ttl seconds only if it's not exists, return false if it was exists.
+    pub fn create_ttl(&self, key: &str, val: &str, ttl: u64) -> Result<bool, Error> {
+        let work = kv::create(&self.client, key, val, Some(ttl)).then(|rslt| match rslt {
+            Ok(_) => Ok(true),
+            Err(ref errs) if has_code(errs, ERR_NODE_EXIST) => Ok(false),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
+    }
+
+    /// reset the ttl of key only if it's value is still `val`,
+    /// return false if it was expired or changed by someone else.
+    pub fn refresh(&self, key: &str, val: &str, ttl: u64) -> Result<bool, Error> {
+        let work =
+            kv::compare_and_swap(&self.client, key, val, Some(ttl), Some(val), None).then(|rslt| {
+                match rslt {
+                    Ok(_) => Ok(true),
+                    Err(ref errs)
+                        if has_code(errs, ERR_TEST_FAILED) || has_code(errs, ERR_KEY_NOT_FOUND) =>
+                    {
+                        Ok(false)
+                    }
+                    Err(errs) => Err(errs),
+                }
+            });
+        block_on(work)
+    }
+
+    /// delete the key only if it's value is still `val`, return false if it's not.
+    pub fn compare_and_delete(&self, key: &str, val: &str) -> Result<bool, Error> {
+        let work =
+            kv::compare_and_delete(&self.client, key, Some(val), None).then(|rslt| match rslt {
+                Ok(_) => Ok(true),
+                Err(ref errs)
+                    if has_code(errs, ERR_TEST_FAILED) || has_code(errs, ERR_KEY_NOT_FOUND) =>
+                {
+                    Ok(false)
+                }
+                Err(errs) => Err(errs),
+            });
+        block_on(work)
+    }
+
+    /// set the key only if it's modified index is still `index`,
+    /// return false if someone else had changed it.
+    pub fn compare_and_swap(&self, key: &str, val: &str, index: u64) -> Result<bool, Error> {
+        let work =
+            kv::compare_and_swap(&self.client, key, val, None, None, Some(index)).then(|rslt| {
+                match rslt {
+                    Ok(_) => Ok(true),
+                    Err(ref errs) if has_code(errs, ERR_TEST_FAILED) => Ok(false),
+                    Err(errs) => Err(errs),
+                }
+            });
+        block_on(work)
+    }
+
+    /// delete the dir with all it's children, it's ok if the dir is not exists.
+    pub fn delete_dir(&self, dir: &str) -> Result<(), Error> {
+        let work = kv::delete(&self.client, dir, true).then(|rslt| match rslt {
+            Ok(_) => Ok(()),
+            Err(ref errs) if has_code(errs, ERR_KEY_NOT_FOUND) => Ok(()),
+            Err(errs) => Err(errs),
+        });
+        block_on(work)
     }
 
     pub fn delete(&self, key: &str) -> Result<Response<KeyValueInfo>, Error> {
@@ -51,7 +199,25 @@ impl MyEtcd {
             debug!("get response as {:?}", response);
             Ok(response)
         });
-        let value = Runtime::new().unwrap().block_on(work).unwrap();
-        Ok(value)
+        block_on(work)
     }
 }
+
+fn has_code(errs: &[etcd::Error], code: u64) -> bool {
+    errs.iter().any(|err| match err {
+        etcd::Error::Api(ref api) => api.error_code == code,
+        _ => false,
+    })
+}
+
+fn block_on<T, F>(work: F) -> Result<T, Error>
+where
+    F: Future<Item = T, Error = Vec<etcd::Error>> + Send + 'static,
+    T: Send + 'static,
+{
+    let mut rt = Runtime::new()?;
+    rt.block_on(work).map_err(|errs| {
+        let msgs: Vec<_> = errs.iter().map(|x| format!("{}", x)).collect();
+        format_err!("etcd request fail due {}", msgs.join(", "))
+    })
+}
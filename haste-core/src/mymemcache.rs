@@ -0,0 +1,338 @@
+//! client of the memcached text protocol, which only runs the `stats` commands.
+//!
+//!   stats        -> STAT {key} {value}
+//!   stats slabs  -> STAT {slab}:{key} {value}
+//!   stats items  -> STAT items:{slab}:{key} {value}
+//!
+//! each response ends with `END`.
+
+use failure::{format_err, Error};
+use serde_derive::Serialize;
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MemcacheStats {
+    pub version: String,
+    pub uptime: u64,
+    pub threads: u64,
+    pub curr_connections: u64,
+    pub cmd_get: u64,
+    pub cmd_set: u64,
+    pub get_hits: u64,
+    pub get_misses: u64,
+    pub evictions: u64,
+    pub curr_items: u64,
+    pub total_items: u64,
+    pub bytes: u64,
+    pub limit_maxbytes: u64,
+}
+
+impl MemcacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        if self.get_hits + self.get_misses == 0 {
+            return 0.0;
+        }
+        self.get_hits as f64 / (self.get_hits + self.get_misses) as f64
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SlabStats {
+    pub id: u64,
+    pub chunk_size: u64,
+    pub chunks_per_page: u64,
+    pub total_pages: u64,
+    pub total_chunks: u64,
+    pub used_chunks: u64,
+    pub free_chunks: u64,
+    pub mem_requested: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ItemStats {
+    pub id: u64,
+    pub number: u64,
+    // seconds of the oldest item
+    pub age: u64,
+    pub evicted: u64,
+    pub outofmemory: u64,
+}
+
+pub struct MyMemcache {
+    addr: String,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl MyMemcache {
+    /// connect to the instance of addr as `{host}:{port}`.
+    pub fn open(addr: &str) -> Result<MyMemcache, Error> {
+        let sock = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| format_err!("bad memcache addr {}", addr))?;
+        let stream = TcpStream::connect_timeout(&sock, TIMEOUT)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        Ok(MyMemcache {
+            addr: addr.to_string(),
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    /// the `STAT` lines of `stats {sub}` as (key, value).
+    pub fn stats_raw(&mut self, sub: &str) -> Result<Vec<(String, String)>, Error> {
+        let cmd = if sub.is_empty() {
+            "stats\r\n".to_string()
+        } else {
+            format!("stats {}\r\n", sub)
+        };
+        self.writer.write_all(cmd.as_bytes())?;
+        read_stats(&mut self.reader)
+            .map_err(|err| format_err!("{} of {} fail due {}", cmd.trim(), self.addr, err))
+    }
+
+    pub fn stats(&mut self) -> Result<MemcacheStats, Error> {
+        Ok(parse_stats(self.stats_raw("")?))
+    }
+
+    pub fn stats_slabs(&mut self) -> Result<Vec<SlabStats>, Error> {
+        Ok(parse_slabs(self.stats_raw("slabs")?))
+    }
+
+    pub fn stats_items(&mut self) -> Result<Vec<ItemStats>, Error> {
+        Ok(parse_items(self.stats_raw("items")?))
+    }
+}
+
+// read the `STAT` lines until `END`.
+fn read_stats<R: BufRead>(reader: &mut R) -> Result<Vec<(String, String)>, Error> {
+    let mut stats = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(format_err!("connection was closed"));
+        }
+        let line = line.trim_end();
+        if line == "END" {
+            break;
+        }
+        if line == "ERROR" || line.starts_with("CLIENT_ERROR") || line.starts_with("SERVER_ERROR") {
+            return Err(format_err!("{}", line));
+        }
+
+        let mut items = line.splitn(3, ' ');
+        match (items.next(), items.next(), items.next()) {
+            (Some("STAT"), Some(key), Some(value)) => {
+                stats.push((key.to_string(), value.to_string()))
+            }
+            _ => return Err(format_err!("bad stats line {}", line)),
+        }
+    }
+    Ok(stats)
+}
+
+fn parse_stats(stats: Vec<(String, String)>) -> MemcacheStats {
+    let fields: HashMap<_, _> = stats.into_iter().collect();
+    let get = |key: &str| -> u64 { fields.get(key).and_then(|x| x.parse().ok()).unwrap_or(0) };
+    MemcacheStats {
+        version: fields.get("version").cloned().unwrap_or_default(),
+        uptime: get("uptime"),
+        threads: get("threads"),
+        curr_connections: get("curr_connections"),
+        cmd_get: get("cmd_get"),
+        cmd_set: get("cmd_set"),
+        get_hits: get("get_hits"),
+        get_misses: get("get_misses"),
+        evictions: get("evictions"),
+        curr_items: get("curr_items"),
+        total_items: get("total_items"),
+        bytes: get("bytes"),
+        limit_maxbytes: get("limit_maxbytes"),
+    }
+}
+
+fn parse_slabs(stats: Vec<(String, String)>) -> Vec<SlabStats> {
+    let mut slabs: BTreeMap<u64, SlabStats> = BTreeMap::new();
+    // the global ones like active_slabs are skipped
+    for (key, value) in stats {
+        let (id, name) = match split_slab_key(&key) {
+            Some(x) => x,
+            None => continue,
+        };
+        let value = value.parse().unwrap_or(0);
+        let slab = slabs.entry(id).or_insert_with(|| SlabStats {
+            id,
+            ..SlabStats::default()
+        });
+        match name {
+            "chunk_size" => slab.chunk_size = value,
+            "chunks_per_page" => slab.chunks_per_page = value,
+            "total_pages" => slab.total_pages = value,
+            "total_chunks" => slab.total_chunks = value,
+            "used_chunks" => slab.used_chunks = value,
+            "free_chunks" => slab.free_chunks = value,
+            "mem_requested" => slab.mem_requested = value,
+            _ => {}
+        }
+    }
+    slabs.into_values().collect()
+}
+
+fn parse_items(stats: Vec<(String, String)>) -> Vec<ItemStats> {
+    let mut items: BTreeMap<u64, ItemStats> = BTreeMap::new();
+    for (key, value) in stats {
+        let (id, name) = match split_slab_key(key.trim_start_matches("items:")) {
+            Some(x) => x,
+            None => continue,
+        };
+        let value = value.parse().unwrap_or(0);
+        let item = items.entry(id).or_insert_with(|| ItemStats {
+            id,
+            ..ItemStats::default()
+        });
+        match name {
+            "number" => item.number = value,
+            "age" => item.age = value,
+            "evicted" => item.evicted = value,
+            "outofmemory" => item.outofmemory = value,
+            _ => {}
+        }
+    }
+    items.into_values().collect()
+}
+
+// {slab}:{name} -> (slab, name)
+fn split_slab_key(key: &str) -> Option<(u64, &str)> {
+    let mut kv = key.splitn(2, ':');
+    match (kv.next(), kv.next()) {
+        (Some(id), Some(name)) => id.parse().ok().map(|id| (id, name)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // captured from memcached 1.5.22, some lines are omitted
+    const STATS: &str = "STAT pid 2331\r
+STAT uptime 86543\r
+STAT time 1571472000\r
+STAT version 1.5.22\r
+STAT libevent 2.1.8-stable\r
+STAT pointer_size 64\r
+STAT rusage_user 12.345678\r
+STAT curr_connections 10\r
+STAT total_connections 342\r
+STAT cmd_get 15000\r
+STAT cmd_set 4200\r
+STAT get_hits 12000\r
+STAT get_misses 3000\r
+STAT evictions 17\r
+STAT threads 4\r
+STAT bytes 1048576\r
+STAT curr_items 3021\r
+STAT total_items 4200\r
+STAT limit_maxbytes 67108864\r
+END\r
+";
+
+    const STATS_SLABS: &str = "STAT 1:chunk_size 96\r
+STAT 1:chunks_per_page 10922\r
+STAT 1:total_pages 1\r
+STAT 1:total_chunks 10922\r
+STAT 1:used_chunks 3000\r
+STAT 1:free_chunks 7922\r
+STAT 1:free_chunks_end 0\r
+STAT 1:mem_requested 250000\r
+STAT 1:get_hits 9000\r
+STAT 5:chunk_size 240\r
+STAT 5:chunks_per_page 4369\r
+STAT 5:total_pages 2\r
+STAT 5:total_chunks 8738\r
+STAT 5:used_chunks 21\r
+STAT 5:free_chunks 8717\r
+STAT 5:mem_requested 4800\r
+STAT active_slabs 2\r
+STAT total_malloced 3145728\r
+END\r
+";
+
+    const STATS_ITEMS: &str = "STAT items:1:number 3000\r
+STAT items:1:number_hot 0\r
+STAT items:1:age 7213\r
+STAT items:1:evicted 17\r
+STAT items:1:evicted_nonzero 0\r
+STAT items:1:outofmemory 0\r
+STAT items:5:number 21\r
+STAT items:5:age 310\r
+STAT items:5:evicted 0\r
+STAT items:5:outofmemory 2\r
+END\r
+";
+
+    fn read(text: &str) -> Vec<(String, String)> {
+        read_stats(&mut text.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn parse_general_stats() {
+        let stats = parse_stats(read(STATS));
+        assert_eq!(stats.version, "1.5.22");
+        assert_eq!(stats.uptime, 86543);
+        assert_eq!(stats.threads, 4);
+        assert_eq!(stats.curr_connections, 10);
+        assert_eq!(stats.cmd_get, 15000);
+        assert_eq!(stats.cmd_set, 4200);
+        assert_eq!(stats.evictions, 17);
+        assert_eq!(stats.curr_items, 3021);
+        assert_eq!(stats.total_items, 4200);
+        assert_eq!(stats.bytes, 1048576);
+        assert_eq!(stats.limit_maxbytes, 67108864);
+        assert!((stats.hit_ratio() - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_slab_stats() {
+        let slabs = parse_slabs(read(STATS_SLABS));
+        assert_eq!(slabs.len(), 2);
+        assert_eq!(slabs[0].id, 1);
+        assert_eq!(slabs[0].chunk_size, 96);
+        assert_eq!(slabs[0].chunks_per_page, 10922);
+        assert_eq!(slabs[0].total_chunks, 10922);
+        assert_eq!(slabs[0].used_chunks, 3000);
+        assert_eq!(slabs[0].free_chunks, 7922);
+        assert_eq!(slabs[0].mem_requested, 250000);
+        assert_eq!(slabs[1].id, 5);
+        assert_eq!(slabs[1].total_pages, 2);
+    }
+
+    #[test]
+    fn parse_item_stats() {
+        let items = parse_items(read(STATS_ITEMS));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, 1);
+        assert_eq!(items[0].number, 3000);
+        assert_eq!(items[0].age, 7213);
+        assert_eq!(items[0].evicted, 17);
+        assert_eq!(items[1].id, 5);
+        assert_eq!(items[1].outofmemory, 2);
+    }
+
+    #[test]
+    fn read_bad_responses() {
+        assert!(read_stats(&mut "ERROR\r\n".as_bytes()).is_err());
+        assert!(read_stats(&mut "CLIENT_ERROR bad command line format\r\n".as_bytes()).is_err());
+        assert!(read_stats(&mut "STAT pid 2331\r\n".as_bytes()).is_err());
+        assert!(read_stats(&mut "VALUE foo 0 3\r\nEND\r\n".as_bytes()).is_err());
+        assert!(read(&"END\r\n").is_empty());
+    }
+}
@@ -1,14 +1,22 @@
 use crate::chunk::{Chunks, ROLE_MASTER, ROLE_SLAVE};
+use crate::stats::{ClusterInfo, ClusterStats, RedisInfo, RedisStats};
 
-use failure::Error;
+use failure::{format_err, Error};
 use redis::{self, Client, Connection, FromRedisValue};
 
 use std::borrow::Borrow;
-use std::collections::hash_map::DefaultHasher;
+use std::collections::hash_map::{DefaultHasher, Entry};
 use std::collections::HashMap;
 use std::hash::Hasher;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
 use std::u64;
 
+// redis-rs has no connect timeout, so the instance is probed by a tcp connect first
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct MyRedis {
     nodes: HashMap<String, Node>,
 }
@@ -35,13 +43,25 @@ impl Default for MyRedis {
 }
 
 impl MyRedis {
-    pub fn set_chunks(&mut self, chunks: &Chunks) {
+    /// connect to the instances of addrs as `{host}:{port}`.
+    pub fn open(addrs: &[String]) -> Result<MyRedis, Error> {
+        let mut nodes = HashMap::new();
+        for addr in addrs {
+            let uri = format!("redis://{}", addr);
+            let node = Node::open(&uri)?;
+            nodes.insert(uri, node);
+        }
+        Ok(MyRedis { nodes })
+    }
+
+    pub fn set_chunks(&mut self, chunks: &Chunks) -> Result<(), Error> {
         self.nodes.clear();
         for inst in &chunks.0[..] {
             let uri = format!("redis://{}:{}", inst.host, inst.port);
-            let node = Node::open(&uri).expect("fail to open node connection");
+            let node = Node::open(&uri)?;
             self.nodes.insert(uri, node);
         }
+        Ok(())
     }
 
     pub fn execute<T, C>(&mut self, to: &str, cmd: C) -> Result<T, Error>
@@ -49,17 +69,37 @@ impl MyRedis {
         T: FromRedisValue,
         C: Borrow<str>,
     {
+        self.node(to)?.execute(cmd)
+    }
+
+    /// the value of directive by `CONFIG GET`, `None` if it's unknown to the instance.
+    pub fn config_get(&mut self, to: &str, name: &str) -> Result<Option<String>, Error> {
+        let values: Vec<String> = self
+            .node(to)?
+            .query(redis::cmd("CONFIG").arg("GET").arg(name))?;
+        Ok(values.into_iter().nth(1))
+    }
+
+    /// `CONFIG SET` the directive, the value may have spaces like `900 1 300 10` of save.
+    pub fn config_set(&mut self, to: &str, name: &str, value: &str) -> Result<(), Error> {
+        self.node(to)?
+            .query(redis::cmd("CONFIG").arg("SET").arg(name).arg(value))
+    }
+
+    fn node(&mut self, to: &str) -> Result<&mut Node, Error> {
         let addr = if to.starts_with("redis://") {
             to.to_string()
         } else {
             format!("redis://{}", to)
         };
-
-        let node = self
-            .nodes
-            .entry(addr.clone())
-            .or_insert_with(|| Node::open(&addr).expect("fail to open conenction"));
-        node.execute(cmd)
+        let node = match self.nodes.entry(addr) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let node = Node::open(entry.key())?;
+                entry.insert(node)
+            }
+        };
+        Ok(node)
     }
 
     pub fn execute_with<T, C>(&mut self, host: &str, port: usize, cmd: C) -> Result<T, Error>
@@ -106,6 +146,35 @@ impl MyRedis {
         Ok(true)
     }
 
+    /// scrape `INFO ALL` and `CLUSTER INFO` of all the nodes in parallel, the unreachable ones
+    /// are kept with their errors.
+    pub fn scrape(&mut self) -> ClusterStats {
+        let ths: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|(uri, node)| {
+                let addr = uri.trim_start_matches("redis://").to_string();
+                let mut node = node.clone();
+                let th = thread::spawn({
+                    let addr = addr.clone();
+                    move || node.scrape(&addr)
+                });
+                (addr, th)
+            })
+            .collect();
+        let stats = ths
+            .into_iter()
+            .map(|(addr, th)| {
+                th.join().unwrap_or_else(|_| RedisStats {
+                    error: Some("the scrape thread panicked".to_string()),
+                    addr,
+                    ..RedisStats::default()
+                })
+            })
+            .collect();
+        ClusterStats::aggregate(stats)
+    }
+
     pub fn bumpepoch(&mut self) -> Result<(), Error> {
         for (_, node) in self.nodes.iter_mut() {
             if node.role == ROLE_MASTER {
@@ -132,8 +201,11 @@ impl MyRedis {
     }
 }
 
+#[derive(Clone)]
 struct Node {
     client: Client,
+    // {host}:{port}
+    addr: String,
     role: String,
 }
 
@@ -142,7 +214,7 @@ impl Node {
         if self.role == ROLE_MASTER {
             return Ok(());
         }
-        let conn = self.client.get_connection()?;
+        let conn = self.connect()?;
         if self.check_role_with_conn(&conn)? {
             return Ok(());
         }
@@ -164,24 +236,68 @@ impl Node {
         if self.role == "" {
             return Ok(true);
         }
-        let conn = self.client.get_connection()?;
+        let conn = self.connect()?;
         self.check_role_with_conn(&conn)
     }
 
-    fn open(addr: &str) -> Result<Node, Error> {
-        let client = Client::open(addr)?;
+    fn scrape(&mut self, addr: &str) -> RedisStats {
+        let mut stats = RedisStats {
+            addr: addr.to_string(),
+            ..RedisStats::default()
+        };
+        let info = match self.execute::<String, _>("INFO ALL") {
+            Ok(text) => RedisInfo::parse(&text),
+            Err(err) => {
+                stats.error = Some(format!("{}", err));
+                return stats;
+            }
+        };
+
+        if info.cluster_enabled {
+            match self.execute::<String, _>("CLUSTER INFO") {
+                Ok(text) => stats.cluster = Some(ClusterInfo::parse(&text)),
+                Err(err) => stats.error = Some(format!("{}", err)),
+            }
+        }
+        stats.info = Some(info);
+        stats
+    }
+
+    fn open(uri: &str) -> Result<Node, Error> {
+        let client = Client::open(uri)?;
         Ok(Node {
             role: ROLE_MASTER.to_string(),
+            addr: uri.trim_start_matches("redis://").to_string(),
             client,
         })
     }
 
+    // connect with timeouts, so a black-holed host never blocks the caller for long.
+    fn connect(&self) -> Result<Connection, Error> {
+        let sock = self
+            .addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| format_err!("bad redis addr {}", self.addr))?;
+        TcpStream::connect_timeout(&sock, CONNECT_TIMEOUT)
+            .map_err(|err| format_err!("fail to connect {} due {}", self.addr, err))?;
+        let conn = self.client.get_connection()?;
+        conn.set_read_timeout(Some(IO_TIMEOUT))?;
+        conn.set_write_timeout(Some(IO_TIMEOUT))?;
+        Ok(conn)
+    }
+
+    fn query<T: FromRedisValue>(&mut self, cmd: &redis::Cmd) -> Result<T, Error> {
+        let conn = self.connect()?;
+        Ok(cmd.query(&conn)?)
+    }
+
     pub fn execute<T, C>(&mut self, cmd: C) -> Result<T, Error>
     where
         T: FromRedisValue,
         C: Borrow<str>,
     {
-        let conn = self.client.get_connection()?;
+        let conn = self.connect()?;
         let mut cmds = cmd.borrow().split(' ');
         let cmd = cmds.next().expect("must get commands");
         let mut command = redis::cmd(cmd);
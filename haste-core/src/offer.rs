@@ -1,16 +1,85 @@
+use crate::layout::DEFAULT_DATA_ROOT;
+use crate::ledger::Ledger;
+use crate::myetcd::MyEtcd;
 
+use failure::Error;
+use log::warn;
+use serde_derive::{Deserialize, Serialize};
 
-pub fn fetch_offer() -> Vec<Offer> {
-    unimplemented!()
+// the total capacity of each agent, registered by the agent itself.
+//  /haste/offers/{ip} -> json of Offer
+const OFFERS_DIR: &str = "/haste/offers";
+// the usage sampled before it in seconds is ignored, the agent may be dead
+const USAGE_TTL: u64 = 300;
+
+/// fetch the capacity of all the agents minus the reserved resources of the ledger.
+pub fn fetch_offer(myetcd: &MyEtcd) -> Result<Vec<Offer>, Error> {
+    let mut offers = Vec::new();
+    for (key, value) in myetcd.list(OFFERS_DIR)? {
+        let offer: Offer = match serde_json::from_str(&value) {
+            Ok(offer) => offer,
+            Err(err) => {
+                warn!("skip bad offer {} due {}", key, err);
+                continue;
+            }
+        };
+        let (ledger, _) = Ledger::load(myetcd, &offer.host)?;
+        offers.push(ledger.available(&offer));
+    }
+    Ok(offers)
+}
+
+pub fn get_capacity(myetcd: &MyEtcd, host: &str) -> Result<Option<Offer>, Error> {
+    match myetcd.get_value(&offer_key(host))? {
+        Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+        None => Ok(None),
+    }
+}
+
+pub fn register_offer(myetcd: &MyEtcd, offer: &Offer) -> Result<(), Error> {
+    let value = serde_json::to_string(offer)?;
+    myetcd.set(&offer_key(&offer.host), &value)?;
+    Ok(())
+}
+
+fn offer_key(host: &str) -> String {
+    format!("{}/{}", OFFERS_DIR, host)
 }
 
 /// server acquire resource -> by using offer
 /// agent report Offer by using offer
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Offer {
     pub host: String,
     // CPU is the a percentage value.
     pub cpu: usize,
     pub memory: usize,
     pub ports: Vec<usize>,
+    // the data root of instance layout of agent
+    #[serde(default = "default_data_root")]
+    pub data_root: String,
+    // the actual free resources, updated by agent periodically
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// the free resources of host sampled by agent, which include the usage out of haste.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Usage {
+    // unix timestamp of the sample
+    pub timestamp: u64,
+    // percentage like the capacity
+    pub cpu: usize,
+    // in MB like the capacity
+    pub memory: usize,
+}
+
+impl Usage {
+    pub fn is_fresh(&self, now: u64) -> bool {
+        self.timestamp + USAGE_TTL > now
+    }
+}
+
+fn default_data_root() -> String {
+    DEFAULT_DATA_ROOT.to_string()
 }
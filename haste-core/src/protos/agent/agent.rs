@@ -339,11 +339,3147 @@ impl ::protobuf::reflect::ProtobufValue for Ports {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct BinaryQuery {
+    // message fields
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl BinaryQuery {
+    pub fn new() -> BinaryQuery {
+        ::std::default::Default::default()
+    }
+}
+
+impl ::protobuf::Message for BinaryQuery {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> BinaryQuery {
+        BinaryQuery::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let fields = ::std::vec::Vec::new();
+                ::protobuf::reflect::MessageDescriptor::new::<BinaryQuery>(
+                    "BinaryQuery",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static BinaryQuery {
+        static mut instance: ::protobuf::lazy::Lazy<BinaryQuery> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const BinaryQuery,
+        };
+        unsafe {
+            instance.get(BinaryQuery::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for BinaryQuery {
+    fn clear(&mut self) {
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BinaryQuery {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BinaryQuery {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct BinaryPurge {
+    // message fields
+    pub force: bool,
+    pub dry_run: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl BinaryPurge {
+    pub fn new() -> BinaryPurge {
+        ::std::default::Default::default()
+    }
+
+    // bool force = 1;
+
+    pub fn clear_force(&mut self) {
+        self.force = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_force(&mut self, v: bool) {
+        self.force = v;
+    }
+
+    pub fn get_force(&self) -> bool {
+        self.force
+    }
+
+    // bool dry_run = 2;
+
+    pub fn clear_dry_run(&mut self) {
+        self.dry_run = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_dry_run(&mut self, v: bool) {
+        self.dry_run = v;
+    }
+
+    pub fn get_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+impl ::protobuf::Message for BinaryPurge {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.force = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.dry_run = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.force != false {
+            my_size += 2;
+        }
+        if self.dry_run != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.force != false {
+            os.write_bool(1, self.force)?;
+        }
+        if self.dry_run != false {
+            os.write_bool(2, self.dry_run)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> BinaryPurge {
+        BinaryPurge::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "force",
+                    |m: &BinaryPurge| { &m.force },
+                    |m: &mut BinaryPurge| { &mut m.force },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "dry_run",
+                    |m: &BinaryPurge| { &m.dry_run },
+                    |m: &mut BinaryPurge| { &mut m.dry_run },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<BinaryPurge>(
+                    "BinaryPurge",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static BinaryPurge {
+        static mut instance: ::protobuf::lazy::Lazy<BinaryPurge> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const BinaryPurge,
+        };
+        unsafe {
+            instance.get(BinaryPurge::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for BinaryPurge {
+    fn clear(&mut self) {
+        self.clear_force();
+        self.clear_dry_run();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BinaryPurge {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BinaryPurge {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct CachedBinary {
+    // message fields
+    pub cache_type: ::std::string::String,
+    pub version: ::std::string::String,
+    pub ports: ::std::vec::Vec<i64>,
+    pub last_used: i64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl CachedBinary {
+    pub fn new() -> CachedBinary {
+        ::std::default::Default::default()
+    }
+
+    // string cache_type = 1;
+
+    pub fn clear_cache_type(&mut self) {
+        self.cache_type.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cache_type(&mut self, v: ::std::string::String) {
+        self.cache_type = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_cache_type(&mut self) -> &mut ::std::string::String {
+        &mut self.cache_type
+    }
+
+    // Take field
+    pub fn take_cache_type(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.cache_type, ::std::string::String::new())
+    }
+
+    pub fn get_cache_type(&self) -> &str {
+        &self.cache_type
+    }
+
+    // string version = 2;
+
+    pub fn clear_version(&mut self) {
+        self.version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
+    }
+
+    // Take field
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
+    }
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    // repeated int64 ports = 3;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::vec::Vec<i64>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::vec::Vec<i64> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::vec::Vec<i64> {
+        ::std::mem::replace(&mut self.ports, ::std::vec::Vec::new())
+    }
+
+    pub fn get_ports(&self) -> &[i64] {
+        &self.ports
+    }
+
+    // int64 last_used = 4;
+
+    pub fn clear_last_used(&mut self) {
+        self.last_used = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_last_used(&mut self, v: i64) {
+        self.last_used = v;
+    }
+
+    pub fn get_last_used(&self) -> i64 {
+        self.last_used
+    }
+}
+
+impl ::protobuf::Message for CachedBinary {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.cache_type)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_int64_into(wire_type, is, &mut self.ports)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.last_used = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.cache_type.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.cache_type);
+        }
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.version);
+        }
+        for value in &self.ports {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if self.last_used != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.last_used, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.cache_type.is_empty() {
+            os.write_string(1, &self.cache_type)?;
+        }
+        if !self.version.is_empty() {
+            os.write_string(2, &self.version)?;
+        }
+        for v in &self.ports {
+            os.write_int64(3, *v)?;
+        };
+        if self.last_used != 0 {
+            os.write_int64(4, self.last_used)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> CachedBinary {
+        CachedBinary::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "cache_type",
+                    |m: &CachedBinary| { &m.cache_type },
+                    |m: &mut CachedBinary| { &mut m.cache_type },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "version",
+                    |m: &CachedBinary| { &m.version },
+                    |m: &mut CachedBinary| { &mut m.version },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "ports",
+                    |m: &CachedBinary| { &m.ports },
+                    |m: &mut CachedBinary| { &mut m.ports },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "last_used",
+                    |m: &CachedBinary| { &m.last_used },
+                    |m: &mut CachedBinary| { &mut m.last_used },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<CachedBinary>(
+                    "CachedBinary",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static CachedBinary {
+        static mut instance: ::protobuf::lazy::Lazy<CachedBinary> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const CachedBinary,
+        };
+        unsafe {
+            instance.get(CachedBinary::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for CachedBinary {
+    fn clear(&mut self) {
+        self.clear_cache_type();
+        self.clear_version();
+        self.clear_ports();
+        self.clear_last_used();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for CachedBinary {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CachedBinary {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Binaries {
+    // message fields
+    pub binaries: ::protobuf::RepeatedField<CachedBinary>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl Binaries {
+    pub fn new() -> Binaries {
+        ::std::default::Default::default()
+    }
+
+    // repeated .agent.CachedBinary binaries = 1;
+
+    pub fn clear_binaries(&mut self) {
+        self.binaries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_binaries(&mut self, v: ::protobuf::RepeatedField<CachedBinary>) {
+        self.binaries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_binaries(&mut self) -> &mut ::protobuf::RepeatedField<CachedBinary> {
+        &mut self.binaries
+    }
+
+    // Take field
+    pub fn take_binaries(&mut self) -> ::protobuf::RepeatedField<CachedBinary> {
+        ::std::mem::replace(&mut self.binaries, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_binaries(&self) -> &[CachedBinary] {
+        &self.binaries
+    }
+}
+
+impl ::protobuf::Message for Binaries {
+    fn is_initialized(&self) -> bool {
+        for v in &self.binaries {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.binaries)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.binaries {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.binaries {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Binaries {
+        Binaries::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<CachedBinary>>(
+                    "binaries",
+                    |m: &Binaries| { &m.binaries },
+                    |m: &mut Binaries| { &mut m.binaries },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Binaries>(
+                    "Binaries",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Binaries {
+        static mut instance: ::protobuf::lazy::Lazy<Binaries> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Binaries,
+        };
+        unsafe {
+            instance.get(Binaries::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Binaries {
+    fn clear(&mut self) {
+        self.clear_binaries();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Binaries {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Binaries {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct RestoreRequest {
+    // message fields
+    pub ports: ::std::vec::Vec<i64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl RestoreRequest {
+    pub fn new() -> RestoreRequest {
+        ::std::default::Default::default()
+    }
+
+    // repeated int64 ports = 1;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::vec::Vec<i64>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::vec::Vec<i64> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::vec::Vec<i64> {
+        ::std::mem::replace(&mut self.ports, ::std::vec::Vec::new())
+    }
+
+    pub fn get_ports(&self) -> &[i64] {
+        &self.ports
+    }
+}
+
+impl ::protobuf::Message for RestoreRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_int64_into(wire_type, is, &mut self.ports)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.ports {
+            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.ports {
+            os.write_int64(1, *v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> RestoreRequest {
+        RestoreRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "ports",
+                    |m: &RestoreRequest| { &m.ports },
+                    |m: &mut RestoreRequest| { &mut m.ports },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<RestoreRequest>(
+                    "RestoreRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static RestoreRequest {
+        static mut instance: ::protobuf::lazy::Lazy<RestoreRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const RestoreRequest,
+        };
+        unsafe {
+            instance.get(RestoreRequest::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for RestoreRequest {
+    fn clear(&mut self) {
+        self.clear_ports();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for RestoreRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RestoreRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct StatusQuery {
+    // message fields
+    pub ports: ::std::vec::Vec<i64>,
+    pub cluster: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl StatusQuery {
+    pub fn new() -> StatusQuery {
+        ::std::default::Default::default()
+    }
+
+    // repeated int64 ports = 1;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::vec::Vec<i64>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::vec::Vec<i64> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::vec::Vec<i64> {
+        ::std::mem::replace(&mut self.ports, ::std::vec::Vec::new())
+    }
+
+    pub fn get_ports(&self) -> &[i64] {
+        &self.ports
+    }
+
+    // string cluster = 2;
+
+    pub fn clear_cluster(&mut self) {
+        self.cluster.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cluster(&mut self, v: ::std::string::String) {
+        self.cluster = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_cluster(&mut self) -> &mut ::std::string::String {
+        &mut self.cluster
+    }
+
+    // Take field
+    pub fn take_cluster(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.cluster, ::std::string::String::new())
+    }
+
+    pub fn get_cluster(&self) -> &str {
+        &self.cluster
+    }
+}
+
+impl ::protobuf::Message for StatusQuery {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_int64_into(wire_type, is, &mut self.ports)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.cluster)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.ports {
+            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if !self.cluster.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.cluster);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.ports {
+            os.write_int64(1, *v)?;
+        };
+        if !self.cluster.is_empty() {
+            os.write_string(2, &self.cluster)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> StatusQuery {
+        StatusQuery::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "ports",
+                    |m: &StatusQuery| { &m.ports },
+                    |m: &mut StatusQuery| { &mut m.ports },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "cluster",
+                    |m: &StatusQuery| { &m.cluster },
+                    |m: &mut StatusQuery| { &mut m.cluster },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<StatusQuery>(
+                    "StatusQuery",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static StatusQuery {
+        static mut instance: ::protobuf::lazy::Lazy<StatusQuery> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const StatusQuery,
+        };
+        unsafe {
+            instance.get(StatusQuery::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for StatusQuery {
+    fn clear(&mut self) {
+        self.clear_ports();
+        self.clear_cluster();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for StatusQuery {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for StatusQuery {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct InstanceStatus {
+    // message fields
+    pub port: i64,
+    pub unit: ::std::string::String,
+    pub state: UnitState,
+    pub pid: i64,
+    pub uptime: i64,
+    pub restarts: i64,
+    pub rss: i64,
+    pub cpu_time: i64,
+    pub accepting: bool,
+    pub msg: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl InstanceStatus {
+    pub fn new() -> InstanceStatus {
+        ::std::default::Default::default()
+    }
+
+    // int64 port = 1;
+
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: i64) {
+        self.port = v;
+    }
+
+    pub fn get_port(&self) -> i64 {
+        self.port
+    }
+
+    // string unit = 2;
+
+    pub fn clear_unit(&mut self) {
+        self.unit.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_unit(&mut self, v: ::std::string::String) {
+        self.unit = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_unit(&mut self) -> &mut ::std::string::String {
+        &mut self.unit
+    }
+
+    // Take field
+    pub fn take_unit(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.unit, ::std::string::String::new())
+    }
+
+    pub fn get_unit(&self) -> &str {
+        &self.unit
+    }
+
+    // .agent.UnitState state = 3;
+
+    pub fn clear_state(&mut self) {
+        self.state = UnitState::Unknown;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_state(&mut self, v: UnitState) {
+        self.state = v;
+    }
+
+    pub fn get_state(&self) -> UnitState {
+        self.state
+    }
+
+    // int64 pid = 4;
+
+    pub fn clear_pid(&mut self) {
+        self.pid = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pid(&mut self, v: i64) {
+        self.pid = v;
+    }
+
+    pub fn get_pid(&self) -> i64 {
+        self.pid
+    }
+
+    // int64 uptime = 5;
+
+    pub fn clear_uptime(&mut self) {
+        self.uptime = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_uptime(&mut self, v: i64) {
+        self.uptime = v;
+    }
+
+    pub fn get_uptime(&self) -> i64 {
+        self.uptime
+    }
+
+    // int64 restarts = 6;
+
+    pub fn clear_restarts(&mut self) {
+        self.restarts = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_restarts(&mut self, v: i64) {
+        self.restarts = v;
+    }
+
+    pub fn get_restarts(&self) -> i64 {
+        self.restarts
+    }
+
+    // int64 rss = 7;
+
+    pub fn clear_rss(&mut self) {
+        self.rss = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rss(&mut self, v: i64) {
+        self.rss = v;
+    }
+
+    pub fn get_rss(&self) -> i64 {
+        self.rss
+    }
+
+    // int64 cpu_time = 8;
+
+    pub fn clear_cpu_time(&mut self) {
+        self.cpu_time = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_time(&mut self, v: i64) {
+        self.cpu_time = v;
+    }
+
+    pub fn get_cpu_time(&self) -> i64 {
+        self.cpu_time
+    }
+
+    // bool accepting = 9;
+
+    pub fn clear_accepting(&mut self) {
+        self.accepting = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_accepting(&mut self, v: bool) {
+        self.accepting = v;
+    }
+
+    pub fn get_accepting(&self) -> bool {
+        self.accepting
+    }
+
+    // string msg = 10;
+
+    pub fn clear_msg(&mut self) {
+        self.msg.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_msg(&mut self, v: ::std::string::String) {
+        self.msg = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_msg(&mut self) -> &mut ::std::string::String {
+        &mut self.msg
+    }
+
+    // Take field
+    pub fn take_msg(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.msg, ::std::string::String::new())
+    }
+
+    pub fn get_msg(&self) -> &str {
+        &self.msg
+    }
+}
+
+impl ::protobuf::Message for InstanceStatus {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.port = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.unit)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.state, 3, &mut self.unknown_fields)?
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.pid = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.uptime = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.restarts = tmp;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.rss = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.cpu_time = tmp;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.accepting = tmp;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.msg)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.unit.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.unit);
+        }
+        if self.state != UnitState::Unknown {
+            my_size += ::protobuf::rt::enum_size(3, self.state);
+        }
+        if self.pid != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.pid, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.uptime != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.uptime, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.restarts != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.restarts, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.rss != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.rss, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.cpu_time != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.cpu_time, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.accepting != false {
+            my_size += 2;
+        }
+        if !self.msg.is_empty() {
+            my_size += ::protobuf::rt::string_size(10, &self.msg);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.port != 0 {
+            os.write_int64(1, self.port)?;
+        }
+        if !self.unit.is_empty() {
+            os.write_string(2, &self.unit)?;
+        }
+        if self.state != UnitState::Unknown {
+            os.write_enum(3, self.state.value())?;
+        }
+        if self.pid != 0 {
+            os.write_int64(4, self.pid)?;
+        }
+        if self.uptime != 0 {
+            os.write_int64(5, self.uptime)?;
+        }
+        if self.restarts != 0 {
+            os.write_int64(6, self.restarts)?;
+        }
+        if self.rss != 0 {
+            os.write_int64(7, self.rss)?;
+        }
+        if self.cpu_time != 0 {
+            os.write_int64(8, self.cpu_time)?;
+        }
+        if self.accepting != false {
+            os.write_bool(9, self.accepting)?;
+        }
+        if !self.msg.is_empty() {
+            os.write_string(10, &self.msg)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> InstanceStatus {
+        InstanceStatus::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "port",
+                    |m: &InstanceStatus| { &m.port },
+                    |m: &mut InstanceStatus| { &mut m.port },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "unit",
+                    |m: &InstanceStatus| { &m.unit },
+                    |m: &mut InstanceStatus| { &mut m.unit },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<UnitState>>(
+                    "state",
+                    |m: &InstanceStatus| { &m.state },
+                    |m: &mut InstanceStatus| { &mut m.state },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "pid",
+                    |m: &InstanceStatus| { &m.pid },
+                    |m: &mut InstanceStatus| { &mut m.pid },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "uptime",
+                    |m: &InstanceStatus| { &m.uptime },
+                    |m: &mut InstanceStatus| { &mut m.uptime },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "restarts",
+                    |m: &InstanceStatus| { &m.restarts },
+                    |m: &mut InstanceStatus| { &mut m.restarts },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "rss",
+                    |m: &InstanceStatus| { &m.rss },
+                    |m: &mut InstanceStatus| { &mut m.rss },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "cpu_time",
+                    |m: &InstanceStatus| { &m.cpu_time },
+                    |m: &mut InstanceStatus| { &mut m.cpu_time },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "accepting",
+                    |m: &InstanceStatus| { &m.accepting },
+                    |m: &mut InstanceStatus| { &mut m.accepting },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "msg",
+                    |m: &InstanceStatus| { &m.msg },
+                    |m: &mut InstanceStatus| { &mut m.msg },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<InstanceStatus>(
+                    "InstanceStatus",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static InstanceStatus {
+        static mut instance: ::protobuf::lazy::Lazy<InstanceStatus> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const InstanceStatus,
+        };
+        unsafe {
+            instance.get(InstanceStatus::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for InstanceStatus {
+    fn clear(&mut self) {
+        self.clear_port();
+        self.clear_unit();
+        self.clear_state();
+        self.clear_pid();
+        self.clear_uptime();
+        self.clear_restarts();
+        self.clear_rss();
+        self.clear_cpu_time();
+        self.clear_accepting();
+        self.clear_msg();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for InstanceStatus {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for InstanceStatus {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct InstanceStatuses {
+    // message fields
+    pub statuses: ::protobuf::RepeatedField<InstanceStatus>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl InstanceStatuses {
+    pub fn new() -> InstanceStatuses {
+        ::std::default::Default::default()
+    }
+
+    // repeated .agent.InstanceStatus statuses = 1;
+
+    pub fn clear_statuses(&mut self) {
+        self.statuses.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_statuses(&mut self, v: ::protobuf::RepeatedField<InstanceStatus>) {
+        self.statuses = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_statuses(&mut self) -> &mut ::protobuf::RepeatedField<InstanceStatus> {
+        &mut self.statuses
+    }
+
+    // Take field
+    pub fn take_statuses(&mut self) -> ::protobuf::RepeatedField<InstanceStatus> {
+        ::std::mem::replace(&mut self.statuses, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_statuses(&self) -> &[InstanceStatus] {
+        &self.statuses
+    }
+}
+
+impl ::protobuf::Message for InstanceStatuses {
+    fn is_initialized(&self) -> bool {
+        for v in &self.statuses {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.statuses)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.statuses {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.statuses {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> InstanceStatuses {
+        InstanceStatuses::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<InstanceStatus>>(
+                    "statuses",
+                    |m: &InstanceStatuses| { &m.statuses },
+                    |m: &mut InstanceStatuses| { &mut m.statuses },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<InstanceStatuses>(
+                    "InstanceStatuses",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static InstanceStatuses {
+        static mut instance: ::protobuf::lazy::Lazy<InstanceStatuses> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const InstanceStatuses,
+        };
+        unsafe {
+            instance.get(InstanceStatuses::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for InstanceStatuses {
+    fn clear(&mut self) {
+        self.clear_statuses();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for InstanceStatuses {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for InstanceStatuses {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct MetricsQuery {
+    // message fields
+    pub since: i64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl MetricsQuery {
+    pub fn new() -> MetricsQuery {
+        ::std::default::Default::default()
+    }
+
+    // int64 since = 1;
+
+    pub fn clear_since(&mut self) {
+        self.since = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_since(&mut self, v: i64) {
+        self.since = v;
+    }
+
+    pub fn get_since(&self) -> i64 {
+        self.since
+    }
+}
+
+impl ::protobuf::Message for MetricsQuery {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.since = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.since != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.since, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.since != 0 {
+            os.write_int64(1, self.since)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> MetricsQuery {
+        MetricsQuery::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "since",
+                    |m: &MetricsQuery| { &m.since },
+                    |m: &mut MetricsQuery| { &mut m.since },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<MetricsQuery>(
+                    "MetricsQuery",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static MetricsQuery {
+        static mut instance: ::protobuf::lazy::Lazy<MetricsQuery> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const MetricsQuery,
+        };
+        unsafe {
+            instance.get(MetricsQuery::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for MetricsQuery {
+    fn clear(&mut self) {
+        self.clear_since();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for MetricsQuery {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MetricsQuery {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct HostMetrics {
+    // message fields
+    pub cpu_used: i64,
+    pub cpu_total: i64,
+    pub memory_used: i64,
+    pub memory_total: i64,
+    pub disk_used: i64,
+    pub disk_total: i64,
+    pub net_rx: i64,
+    pub net_tx: i64,
+    pub load1: f64,
+    pub load5: f64,
+    pub load15: f64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl HostMetrics {
+    pub fn new() -> HostMetrics {
+        ::std::default::Default::default()
+    }
+
+    // int64 cpu_used = 1;
+
+    pub fn clear_cpu_used(&mut self) {
+        self.cpu_used = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_used(&mut self, v: i64) {
+        self.cpu_used = v;
+    }
+
+    pub fn get_cpu_used(&self) -> i64 {
+        self.cpu_used
+    }
+
+    // int64 cpu_total = 2;
+
+    pub fn clear_cpu_total(&mut self) {
+        self.cpu_total = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_total(&mut self, v: i64) {
+        self.cpu_total = v;
+    }
+
+    pub fn get_cpu_total(&self) -> i64 {
+        self.cpu_total
+    }
+
+    // int64 memory_used = 3;
+
+    pub fn clear_memory_used(&mut self) {
+        self.memory_used = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memory_used(&mut self, v: i64) {
+        self.memory_used = v;
+    }
+
+    pub fn get_memory_used(&self) -> i64 {
+        self.memory_used
+    }
+
+    // int64 memory_total = 4;
+
+    pub fn clear_memory_total(&mut self) {
+        self.memory_total = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memory_total(&mut self, v: i64) {
+        self.memory_total = v;
+    }
+
+    pub fn get_memory_total(&self) -> i64 {
+        self.memory_total
+    }
+
+    // int64 disk_used = 5;
+
+    pub fn clear_disk_used(&mut self) {
+        self.disk_used = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_disk_used(&mut self, v: i64) {
+        self.disk_used = v;
+    }
+
+    pub fn get_disk_used(&self) -> i64 {
+        self.disk_used
+    }
+
+    // int64 disk_total = 6;
+
+    pub fn clear_disk_total(&mut self) {
+        self.disk_total = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_disk_total(&mut self, v: i64) {
+        self.disk_total = v;
+    }
+
+    pub fn get_disk_total(&self) -> i64 {
+        self.disk_total
+    }
+
+    // int64 net_rx = 7;
+
+    pub fn clear_net_rx(&mut self) {
+        self.net_rx = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_net_rx(&mut self, v: i64) {
+        self.net_rx = v;
+    }
+
+    pub fn get_net_rx(&self) -> i64 {
+        self.net_rx
+    }
+
+    // int64 net_tx = 8;
+
+    pub fn clear_net_tx(&mut self) {
+        self.net_tx = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_net_tx(&mut self, v: i64) {
+        self.net_tx = v;
+    }
+
+    pub fn get_net_tx(&self) -> i64 {
+        self.net_tx
+    }
+
+    // double load1 = 9;
+
+    pub fn clear_load1(&mut self) {
+        self.load1 = 0.;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_load1(&mut self, v: f64) {
+        self.load1 = v;
+    }
+
+    pub fn get_load1(&self) -> f64 {
+        self.load1
+    }
+
+    // double load5 = 10;
+
+    pub fn clear_load5(&mut self) {
+        self.load5 = 0.;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_load5(&mut self, v: f64) {
+        self.load5 = v;
+    }
+
+    pub fn get_load5(&self) -> f64 {
+        self.load5
+    }
+
+    // double load15 = 11;
+
+    pub fn clear_load15(&mut self) {
+        self.load15 = 0.;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_load15(&mut self, v: f64) {
+        self.load15 = v;
+    }
+
+    pub fn get_load15(&self) -> f64 {
+        self.load15
+    }
+}
+
+impl ::protobuf::Message for HostMetrics {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.cpu_used = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.cpu_total = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.memory_used = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.memory_total = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.disk_used = tmp;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.disk_total = tmp;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.net_rx = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.net_tx = tmp;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_double()?;
+                    self.load1 = tmp;
+                },
+                10 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_double()?;
+                    self.load5 = tmp;
+                },
+                11 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_double()?;
+                    self.load15 = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.cpu_used != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.cpu_used, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.cpu_total != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.cpu_total, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.memory_used != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.memory_used, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.memory_total != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.memory_total, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.disk_used != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.disk_used, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.disk_total != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.disk_total, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.net_rx != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.net_rx, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.net_tx != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.net_tx, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.load1 != 0. {
+            my_size += 9;
+        }
+        if self.load5 != 0. {
+            my_size += 9;
+        }
+        if self.load15 != 0. {
+            my_size += 9;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.cpu_used != 0 {
+            os.write_int64(1, self.cpu_used)?;
+        }
+        if self.cpu_total != 0 {
+            os.write_int64(2, self.cpu_total)?;
+        }
+        if self.memory_used != 0 {
+            os.write_int64(3, self.memory_used)?;
+        }
+        if self.memory_total != 0 {
+            os.write_int64(4, self.memory_total)?;
+        }
+        if self.disk_used != 0 {
+            os.write_int64(5, self.disk_used)?;
+        }
+        if self.disk_total != 0 {
+            os.write_int64(6, self.disk_total)?;
+        }
+        if self.net_rx != 0 {
+            os.write_int64(7, self.net_rx)?;
+        }
+        if self.net_tx != 0 {
+            os.write_int64(8, self.net_tx)?;
+        }
+        if self.load1 != 0. {
+            os.write_double(9, self.load1)?;
+        }
+        if self.load5 != 0. {
+            os.write_double(10, self.load5)?;
+        }
+        if self.load15 != 0. {
+            os.write_double(11, self.load15)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> HostMetrics {
+        HostMetrics::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "cpu_used",
+                    |m: &HostMetrics| { &m.cpu_used },
+                    |m: &mut HostMetrics| { &mut m.cpu_used },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "cpu_total",
+                    |m: &HostMetrics| { &m.cpu_total },
+                    |m: &mut HostMetrics| { &mut m.cpu_total },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "memory_used",
+                    |m: &HostMetrics| { &m.memory_used },
+                    |m: &mut HostMetrics| { &mut m.memory_used },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "memory_total",
+                    |m: &HostMetrics| { &m.memory_total },
+                    |m: &mut HostMetrics| { &mut m.memory_total },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "disk_used",
+                    |m: &HostMetrics| { &m.disk_used },
+                    |m: &mut HostMetrics| { &mut m.disk_used },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "disk_total",
+                    |m: &HostMetrics| { &m.disk_total },
+                    |m: &mut HostMetrics| { &mut m.disk_total },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "net_rx",
+                    |m: &HostMetrics| { &m.net_rx },
+                    |m: &mut HostMetrics| { &mut m.net_rx },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "net_tx",
+                    |m: &HostMetrics| { &m.net_tx },
+                    |m: &mut HostMetrics| { &mut m.net_tx },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
+                    "load1",
+                    |m: &HostMetrics| { &m.load1 },
+                    |m: &mut HostMetrics| { &mut m.load1 },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
+                    "load5",
+                    |m: &HostMetrics| { &m.load5 },
+                    |m: &mut HostMetrics| { &mut m.load5 },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
+                    "load15",
+                    |m: &HostMetrics| { &m.load15 },
+                    |m: &mut HostMetrics| { &mut m.load15 },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<HostMetrics>(
+                    "HostMetrics",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static HostMetrics {
+        static mut instance: ::protobuf::lazy::Lazy<HostMetrics> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const HostMetrics,
+        };
+        unsafe {
+            instance.get(HostMetrics::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for HostMetrics {
+    fn clear(&mut self) {
+        self.clear_cpu_used();
+        self.clear_cpu_total();
+        self.clear_memory_used();
+        self.clear_memory_total();
+        self.clear_disk_used();
+        self.clear_disk_total();
+        self.clear_net_rx();
+        self.clear_net_tx();
+        self.clear_load1();
+        self.clear_load5();
+        self.clear_load15();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for HostMetrics {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HostMetrics {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ProcessMetrics {
+    // message fields
+    pub port: i64,
+    pub pid: i64,
+    pub cpu: i64,
+    pub rss: i64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl ProcessMetrics {
+    pub fn new() -> ProcessMetrics {
+        ::std::default::Default::default()
+    }
+
+    // int64 port = 1;
+
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: i64) {
+        self.port = v;
+    }
+
+    pub fn get_port(&self) -> i64 {
+        self.port
+    }
+
+    // int64 pid = 2;
+
+    pub fn clear_pid(&mut self) {
+        self.pid = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pid(&mut self, v: i64) {
+        self.pid = v;
+    }
+
+    pub fn get_pid(&self) -> i64 {
+        self.pid
+    }
+
+    // int64 cpu = 3;
+
+    pub fn clear_cpu(&mut self) {
+        self.cpu = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu(&mut self, v: i64) {
+        self.cpu = v;
+    }
+
+    pub fn get_cpu(&self) -> i64 {
+        self.cpu
+    }
+
+    // int64 rss = 4;
+
+    pub fn clear_rss(&mut self) {
+        self.rss = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rss(&mut self, v: i64) {
+        self.rss = v;
+    }
+
+    pub fn get_rss(&self) -> i64 {
+        self.rss
+    }
+}
+
+impl ::protobuf::Message for ProcessMetrics {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.port = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.pid = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.cpu = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.rss = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.pid != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.pid, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.cpu != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.cpu, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.rss != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.rss, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.port != 0 {
+            os.write_int64(1, self.port)?;
+        }
+        if self.pid != 0 {
+            os.write_int64(2, self.pid)?;
+        }
+        if self.cpu != 0 {
+            os.write_int64(3, self.cpu)?;
+        }
+        if self.rss != 0 {
+            os.write_int64(4, self.rss)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ProcessMetrics {
+        ProcessMetrics::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "port",
+                    |m: &ProcessMetrics| { &m.port },
+                    |m: &mut ProcessMetrics| { &mut m.port },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "pid",
+                    |m: &ProcessMetrics| { &m.pid },
+                    |m: &mut ProcessMetrics| { &mut m.pid },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "cpu",
+                    |m: &ProcessMetrics| { &m.cpu },
+                    |m: &mut ProcessMetrics| { &mut m.cpu },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "rss",
+                    |m: &ProcessMetrics| { &m.rss },
+                    |m: &mut ProcessMetrics| { &mut m.rss },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ProcessMetrics>(
+                    "ProcessMetrics",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static ProcessMetrics {
+        static mut instance: ::protobuf::lazy::Lazy<ProcessMetrics> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ProcessMetrics,
+        };
+        unsafe {
+            instance.get(ProcessMetrics::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for ProcessMetrics {
+    fn clear(&mut self) {
+        self.clear_port();
+        self.clear_pid();
+        self.clear_cpu();
+        self.clear_rss();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ProcessMetrics {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ProcessMetrics {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct MetricsSample {
+    // message fields
+    pub timestamp: i64,
+    pub host: ::protobuf::SingularPtrField<HostMetrics>,
+    pub insts: ::protobuf::RepeatedField<ProcessMetrics>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl MetricsSample {
+    pub fn new() -> MetricsSample {
+        ::std::default::Default::default()
+    }
+
+    // int64 timestamp = 1;
+
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: i64) {
+        self.timestamp = v;
+    }
+
+    pub fn get_timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    // .agent.HostMetrics host = 2;
+
+    pub fn clear_host(&mut self) {
+        self.host.clear();
+    }
+
+    pub fn has_host(&self) -> bool {
+        self.host.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_host(&mut self, v: HostMetrics) {
+        self.host = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_host(&mut self) -> &mut HostMetrics {
+        if self.host.is_none() {
+            self.host.set_default();
+        }
+        self.host.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_host(&mut self) -> HostMetrics {
+        self.host.take().unwrap_or_else(|| HostMetrics::new())
+    }
+
+    pub fn get_host(&self) -> &HostMetrics {
+        self.host.as_ref().unwrap_or_else(|| HostMetrics::default_instance())
+    }
+
+    // repeated .agent.ProcessMetrics insts = 3;
+
+    pub fn clear_insts(&mut self) {
+        self.insts.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_insts(&mut self, v: ::protobuf::RepeatedField<ProcessMetrics>) {
+        self.insts = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_insts(&mut self) -> &mut ::protobuf::RepeatedField<ProcessMetrics> {
+        &mut self.insts
+    }
+
+    // Take field
+    pub fn take_insts(&mut self) -> ::protobuf::RepeatedField<ProcessMetrics> {
+        ::std::mem::replace(&mut self.insts, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_insts(&self) -> &[ProcessMetrics] {
+        &self.insts
+    }
+}
+
+impl ::protobuf::Message for MetricsSample {
+    fn is_initialized(&self) -> bool {
+        for v in &self.host {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.insts {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.timestamp = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.host)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.insts)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.timestamp != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.timestamp, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.host.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        for value in &self.insts {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.timestamp != 0 {
+            os.write_int64(1, self.timestamp)?;
+        }
+        if let Some(ref v) = self.host.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        for v in &self.insts {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> MetricsSample {
+        MetricsSample::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "timestamp",
+                    |m: &MetricsSample| { &m.timestamp },
+                    |m: &mut MetricsSample| { &mut m.timestamp },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<HostMetrics>>(
+                    "host",
+                    |m: &MetricsSample| { &m.host },
+                    |m: &mut MetricsSample| { &mut m.host },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<ProcessMetrics>>(
+                    "insts",
+                    |m: &MetricsSample| { &m.insts },
+                    |m: &mut MetricsSample| { &mut m.insts },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<MetricsSample>(
+                    "MetricsSample",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static MetricsSample {
+        static mut instance: ::protobuf::lazy::Lazy<MetricsSample> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const MetricsSample,
+        };
+        unsafe {
+            instance.get(MetricsSample::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for MetricsSample {
+    fn clear(&mut self) {
+        self.clear_timestamp();
+        self.clear_host();
+        self.clear_insts();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for MetricsSample {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MetricsSample {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct MetricsSamples {
+    // message fields
+    pub samples: ::protobuf::RepeatedField<MetricsSample>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl MetricsSamples {
+    pub fn new() -> MetricsSamples {
+        ::std::default::Default::default()
+    }
+
+    // repeated .agent.MetricsSample samples = 1;
+
+    pub fn clear_samples(&mut self) {
+        self.samples.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_samples(&mut self, v: ::protobuf::RepeatedField<MetricsSample>) {
+        self.samples = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_samples(&mut self) -> &mut ::protobuf::RepeatedField<MetricsSample> {
+        &mut self.samples
+    }
+
+    // Take field
+    pub fn take_samples(&mut self) -> ::protobuf::RepeatedField<MetricsSample> {
+        ::std::mem::replace(&mut self.samples, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_samples(&self) -> &[MetricsSample] {
+        &self.samples
+    }
+}
+
+impl ::protobuf::Message for MetricsSamples {
+    fn is_initialized(&self) -> bool {
+        for v in &self.samples {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.samples)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.samples {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.samples {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> MetricsSamples {
+        MetricsSamples::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<MetricsSample>>(
+                    "samples",
+                    |m: &MetricsSamples| { &m.samples },
+                    |m: &mut MetricsSamples| { &mut m.samples },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<MetricsSamples>(
+                    "MetricsSamples",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static MetricsSamples {
+        static mut instance: ::protobuf::lazy::Lazy<MetricsSamples> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const MetricsSamples,
+        };
+        unsafe {
+            instance.get(MetricsSamples::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for MetricsSamples {
+    fn clear(&mut self) {
+        self.clear_samples();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for MetricsSamples {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MetricsSamples {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 pub struct Action {
     // message fields
     pub action: SystemdAction,
     pub insts: ::protobuf::RepeatedField<Instance>,
+    pub cluster: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -369,29 +3505,55 @@ impl Action {
         self.action
     }
 
-    // repeated .agent.Instance insts = 2;
+    // repeated .agent.Instance insts = 2;
+
+    pub fn clear_insts(&mut self) {
+        self.insts.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_insts(&mut self, v: ::protobuf::RepeatedField<Instance>) {
+        self.insts = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_insts(&mut self) -> &mut ::protobuf::RepeatedField<Instance> {
+        &mut self.insts
+    }
+
+    // Take field
+    pub fn take_insts(&mut self) -> ::protobuf::RepeatedField<Instance> {
+        ::std::mem::replace(&mut self.insts, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_insts(&self) -> &[Instance] {
+        &self.insts
+    }
+
+    // string cluster = 3;
 
-    pub fn clear_insts(&mut self) {
-        self.insts.clear();
+    pub fn clear_cluster(&mut self) {
+        self.cluster.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_insts(&mut self, v: ::protobuf::RepeatedField<Instance>) {
-        self.insts = v;
+    pub fn set_cluster(&mut self, v: ::std::string::String) {
+        self.cluster = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_insts(&mut self) -> &mut ::protobuf::RepeatedField<Instance> {
-        &mut self.insts
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_cluster(&mut self) -> &mut ::std::string::String {
+        &mut self.cluster
     }
 
     // Take field
-    pub fn take_insts(&mut self) -> ::protobuf::RepeatedField<Instance> {
-        ::std::mem::replace(&mut self.insts, ::protobuf::RepeatedField::new())
+    pub fn take_cluster(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.cluster, ::std::string::String::new())
     }
 
-    pub fn get_insts(&self) -> &[Instance] {
-        &self.insts
+    pub fn get_cluster(&self) -> &str {
+        &self.cluster
     }
 }
 
@@ -415,6 +3577,9 @@ impl ::protobuf::Message for Action {
                 2 => {
                     ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.insts)?;
                 },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.cluster)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -434,6 +3599,9 @@ impl ::protobuf::Message for Action {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if !self.cluster.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.cluster);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -448,6 +3616,9 @@ impl ::protobuf::Message for Action {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         };
+        if !self.cluster.is_empty() {
+            os.write_string(3, &self.cluster)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -500,6 +3671,11 @@ impl ::protobuf::Message for Action {
                     |m: &Action| { &m.insts },
                     |m: &mut Action| { &mut m.insts },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "cluster",
+                    |m: &Action| { &m.cluster },
+                    |m: &mut Action| { &mut m.cluster },
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Action>(
                     "Action",
                     fields,
@@ -524,6 +3700,7 @@ impl ::protobuf::Clear for Action {
     fn clear(&mut self) {
         self.clear_action();
         self.clear_insts();
+        self.clear_cluster();
         self.unknown_fields.clear();
     }
 }
@@ -548,6 +3725,7 @@ pub struct CacheInfo {
     pub cluster: ::std::string::String,
     pub version: ::std::string::String,
     pub file_server: ::std::string::String,
+    pub converge: bool,
     pub insts: ::protobuf::RepeatedField<Instance>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
@@ -678,6 +3856,21 @@ impl CacheInfo {
         &self.file_server
     }
 
+    // bool converge = 6;
+
+    pub fn clear_converge(&mut self) {
+        self.converge = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_converge(&mut self, v: bool) {
+        self.converge = v;
+    }
+
+    pub fn get_converge(&self) -> bool {
+        self.converge
+    }
+
     // repeated .agent.Instance insts = 10;
 
     pub fn clear_insts(&mut self) {
@@ -733,6 +3926,13 @@ impl ::protobuf::Message for CacheInfo {
                 5 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.file_server)?;
                 },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.converge = tmp;
+                },
                 10 => {
                     ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.insts)?;
                 },
@@ -763,6 +3963,9 @@ impl ::protobuf::Message for CacheInfo {
         if !self.file_server.is_empty() {
             my_size += ::protobuf::rt::string_size(5, &self.file_server);
         }
+        if self.converge != false {
+            my_size += 2;
+        }
         for value in &self.insts {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
@@ -788,6 +3991,9 @@ impl ::protobuf::Message for CacheInfo {
         if !self.file_server.is_empty() {
             os.write_string(5, &self.file_server)?;
         }
+        if self.converge != false {
+            os.write_bool(6, self.converge)?;
+        }
         for v in &self.insts {
             os.write_tag(10, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
@@ -860,6 +4066,11 @@ impl ::protobuf::Message for CacheInfo {
                     |m: &CacheInfo| { &m.file_server },
                     |m: &mut CacheInfo| { &mut m.file_server },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "converge",
+                    |m: &CacheInfo| { &m.converge },
+                    |m: &mut CacheInfo| { &mut m.converge },
+                ));
                 fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
                     "insts",
                     |m: &CacheInfo| { &m.insts },
@@ -892,6 +4103,7 @@ impl ::protobuf::Clear for CacheInfo {
         self.clear_cluster();
         self.clear_version();
         self.clear_file_server();
+        self.clear_converge();
         self.clear_insts();
         self.unknown_fields.clear();
     }
@@ -1069,13 +4281,218 @@ impl ::protobuf::Message for Instance {
                     |m: &Instance| { &m.port },
                     |m: &mut Instance| { &mut m.port },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<File>>(
-                    "files",
-                    |m: &Instance| { &m.files },
-                    |m: &mut Instance| { &mut m.files },
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<File>>(
+                    "files",
+                    |m: &Instance| { &m.files },
+                    |m: &mut Instance| { &mut m.files },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Instance>(
+                    "Instance",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Instance {
+        static mut instance: ::protobuf::lazy::Lazy<Instance> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Instance,
+        };
+        unsafe {
+            instance.get(Instance::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Instance {
+    fn clear(&mut self) {
+        self.clear_port();
+        self.clear_files();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Instance {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Instance {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct File {
+    // message fields
+    pub fpath: ::std::string::String,
+    pub content: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl File {
+    pub fn new() -> File {
+        ::std::default::Default::default()
+    }
+
+    // string fpath = 1;
+
+    pub fn clear_fpath(&mut self) {
+        self.fpath.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fpath(&mut self, v: ::std::string::String) {
+        self.fpath = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_fpath(&mut self) -> &mut ::std::string::String {
+        &mut self.fpath
+    }
+
+    // Take field
+    pub fn take_fpath(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.fpath, ::std::string::String::new())
+    }
+
+    pub fn get_fpath(&self) -> &str {
+        &self.fpath
+    }
+
+    // string content = 2;
+
+    pub fn clear_content(&mut self) {
+        self.content.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_content(&mut self, v: ::std::string::String) {
+        self.content = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_content(&mut self) -> &mut ::std::string::String {
+        &mut self.content
+    }
+
+    // Take field
+    pub fn take_content(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.content, ::std::string::String::new())
+    }
+
+    pub fn get_content(&self) -> &str {
+        &self.content
+    }
+}
+
+impl ::protobuf::Message for File {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fpath)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.content)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.fpath.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.fpath);
+        }
+        if !self.content.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.content);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.fpath.is_empty() {
+            os.write_string(1, &self.fpath)?;
+        }
+        if !self.content.is_empty() {
+            os.write_string(2, &self.content)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> File {
+        File::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "fpath",
+                    |m: &File| { &m.fpath },
+                    |m: &mut File| { &mut m.fpath },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "content",
+                    |m: &File| { &m.content },
+                    |m: &mut File| { &mut m.content },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<Instance>(
-                    "Instance",
+                ::protobuf::reflect::MessageDescriptor::new::<File>(
+                    "File",
                     fields,
                     file_descriptor_proto()
                 )
@@ -1083,106 +4500,126 @@ impl ::protobuf::Message for Instance {
         }
     }
 
-    fn default_instance() -> &'static Instance {
-        static mut instance: ::protobuf::lazy::Lazy<Instance> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static File {
+        static mut instance: ::protobuf::lazy::Lazy<File> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const Instance,
+            ptr: 0 as *const File,
         };
         unsafe {
-            instance.get(Instance::new)
+            instance.get(File::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Instance {
+impl ::protobuf::Clear for File {
     fn clear(&mut self) {
-        self.clear_port();
-        self.clear_files();
+        self.clear_fpath();
+        self.clear_content();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Instance {
+impl ::std::fmt::Debug for File {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Instance {
+impl ::protobuf::reflect::ProtobufValue for File {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct File {
+pub struct InstanceChange {
     // message fields
-    pub fpath: ::std::string::String,
-    pub content: ::std::string::String,
+    pub port: i64,
+    pub kind: ChangeKind,
+    pub files: ::protobuf::RepeatedField<::std::string::String>,
+    pub restarted: bool,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl File {
-    pub fn new() -> File {
+impl InstanceChange {
+    pub fn new() -> InstanceChange {
         ::std::default::Default::default()
     }
 
-    // string fpath = 1;
+    // int64 port = 1;
 
-    pub fn clear_fpath(&mut self) {
-        self.fpath.clear();
+    pub fn clear_port(&mut self) {
+        self.port = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_fpath(&mut self, v: ::std::string::String) {
-        self.fpath = v;
+    pub fn set_port(&mut self, v: i64) {
+        self.port = v;
     }
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_fpath(&mut self) -> &mut ::std::string::String {
-        &mut self.fpath
+    pub fn get_port(&self) -> i64 {
+        self.port
     }
 
-    // Take field
-    pub fn take_fpath(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.fpath, ::std::string::String::new())
+    // .agent.ChangeKind kind = 2;
+
+    pub fn clear_kind(&mut self) {
+        self.kind = ChangeKind::Unchanged;
     }
 
-    pub fn get_fpath(&self) -> &str {
-        &self.fpath
+    // Param is passed by value, moved
+    pub fn set_kind(&mut self, v: ChangeKind) {
+        self.kind = v;
     }
 
-    // string content = 2;
+    pub fn get_kind(&self) -> ChangeKind {
+        self.kind
+    }
 
-    pub fn clear_content(&mut self) {
-        self.content.clear();
+    // repeated string files = 3;
+
+    pub fn clear_files(&mut self) {
+        self.files.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_content(&mut self, v: ::std::string::String) {
-        self.content = v;
+    pub fn set_files(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.files = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_content(&mut self) -> &mut ::std::string::String {
-        &mut self.content
+    pub fn mut_files(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.files
     }
 
     // Take field
-    pub fn take_content(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.content, ::std::string::String::new())
+    pub fn take_files(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.files, ::protobuf::RepeatedField::new())
     }
 
-    pub fn get_content(&self) -> &str {
-        &self.content
+    pub fn get_files(&self) -> &[::std::string::String] {
+        &self.files
+    }
+
+    // bool restarted = 4;
+
+    pub fn clear_restarted(&mut self) {
+        self.restarted = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_restarted(&mut self, v: bool) {
+        self.restarted = v;
+    }
+
+    pub fn get_restarted(&self) -> bool {
+        self.restarted
     }
 }
 
-impl ::protobuf::Message for File {
+impl ::protobuf::Message for InstanceChange {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -1192,10 +4629,24 @@ impl ::protobuf::Message for File {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.fpath)?;
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.port = tmp;
                 },
                 2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.content)?;
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.kind, 2, &mut self.unknown_fields)?
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.files)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.restarted = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -1209,11 +4660,17 @@ impl ::protobuf::Message for File {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.fpath.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.fpath);
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.port, ::protobuf::wire_format::WireTypeVarint);
         }
-        if !self.content.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.content);
+        if self.kind != ChangeKind::Unchanged {
+            my_size += ::protobuf::rt::enum_size(2, self.kind);
+        }
+        for value in &self.files {
+            my_size += ::protobuf::rt::string_size(3, &value);
+        };
+        if self.restarted != false {
+            my_size += 2;
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -1221,11 +4678,17 @@ impl ::protobuf::Message for File {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        if !self.fpath.is_empty() {
-            os.write_string(1, &self.fpath)?;
+        if self.port != 0 {
+            os.write_int64(1, self.port)?;
         }
-        if !self.content.is_empty() {
-            os.write_string(2, &self.content)?;
+        if self.kind != ChangeKind::Unchanged {
+            os.write_enum(2, self.kind.value())?;
+        }
+        for v in &self.files {
+            os.write_string(3, &v)?;
+        };
+        if self.restarted != false {
+            os.write_bool(4, self.restarted)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -1257,8 +4720,8 @@ impl ::protobuf::Message for File {
         Self::descriptor_static()
     }
 
-    fn new() -> File {
-        File::new()
+    fn new() -> InstanceChange {
+        InstanceChange::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -1269,18 +4732,28 @@ impl ::protobuf::Message for File {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                    "fpath",
-                    |m: &File| { &m.fpath },
-                    |m: &mut File| { &mut m.fpath },
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "port",
+                    |m: &InstanceChange| { &m.port },
+                    |m: &mut InstanceChange| { &mut m.port },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                    "content",
-                    |m: &File| { &m.content },
-                    |m: &mut File| { &mut m.content },
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<ChangeKind>>(
+                    "kind",
+                    |m: &InstanceChange| { &m.kind },
+                    |m: &mut InstanceChange| { &mut m.kind },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<File>(
-                    "File",
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "files",
+                    |m: &InstanceChange| { &m.files },
+                    |m: &mut InstanceChange| { &mut m.files },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "restarted",
+                    |m: &InstanceChange| { &m.restarted },
+                    |m: &mut InstanceChange| { &mut m.restarted },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<InstanceChange>(
+                    "InstanceChange",
                     fields,
                     file_descriptor_proto()
                 )
@@ -1288,32 +4761,34 @@ impl ::protobuf::Message for File {
         }
     }
 
-    fn default_instance() -> &'static File {
-        static mut instance: ::protobuf::lazy::Lazy<File> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static InstanceChange {
+        static mut instance: ::protobuf::lazy::Lazy<InstanceChange> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const File,
+            ptr: 0 as *const InstanceChange,
         };
         unsafe {
-            instance.get(File::new)
+            instance.get(InstanceChange::new)
         }
     }
 }
 
-impl ::protobuf::Clear for File {
+impl ::protobuf::Clear for InstanceChange {
     fn clear(&mut self) {
-        self.clear_fpath();
-        self.clear_content();
+        self.clear_port();
+        self.clear_kind();
+        self.clear_files();
+        self.clear_restarted();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for File {
+impl ::std::fmt::Debug for InstanceChange {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for File {
+impl ::protobuf::reflect::ProtobufValue for InstanceChange {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
@@ -1324,6 +4799,7 @@ pub struct CacheState {
     // message fields
     pub state: State,
     pub msg: ::std::string::String,
+    pub changes: ::protobuf::RepeatedField<InstanceChange>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -1374,10 +4850,40 @@ impl CacheState {
     pub fn get_msg(&self) -> &str {
         &self.msg
     }
+
+    // repeated .agent.InstanceChange changes = 3;
+
+    pub fn clear_changes(&mut self) {
+        self.changes.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_changes(&mut self, v: ::protobuf::RepeatedField<InstanceChange>) {
+        self.changes = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_changes(&mut self) -> &mut ::protobuf::RepeatedField<InstanceChange> {
+        &mut self.changes
+    }
+
+    // Take field
+    pub fn take_changes(&mut self) -> ::protobuf::RepeatedField<InstanceChange> {
+        ::std::mem::replace(&mut self.changes, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_changes(&self) -> &[InstanceChange] {
+        &self.changes
+    }
 }
 
 impl ::protobuf::Message for CacheState {
     fn is_initialized(&self) -> bool {
+        for v in &self.changes {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -1391,6 +4897,9 @@ impl ::protobuf::Message for CacheState {
                 2 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.msg)?;
                 },
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.changes)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1409,6 +4918,10 @@ impl ::protobuf::Message for CacheState {
         if !self.msg.is_empty() {
             my_size += ::protobuf::rt::string_size(2, &self.msg);
         }
+        for value in &self.changes {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1421,6 +4934,11 @@ impl ::protobuf::Message for CacheState {
         if !self.msg.is_empty() {
             os.write_string(2, &self.msg)?;
         }
+        for v in &self.changes {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1473,6 +4991,11 @@ impl ::protobuf::Message for CacheState {
                     |m: &CacheState| { &m.msg },
                     |m: &mut CacheState| { &mut m.msg },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<InstanceChange>>(
+                    "changes",
+                    |m: &CacheState| { &m.changes },
+                    |m: &mut CacheState| { &mut m.changes },
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<CacheState>(
                     "CacheState",
                     fields,
@@ -1497,6 +5020,7 @@ impl ::protobuf::Clear for CacheState {
     fn clear(&mut self) {
         self.clear_state();
         self.clear_msg();
+        self.clear_changes();
         self.unknown_fields.clear();
     }
 }
@@ -1513,6 +5037,70 @@ impl ::protobuf::reflect::ProtobufValue for CacheState {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum UnitState {
+    Unknown = 0,
+    Active = 1,
+    Activating = 2,
+    Inactive = 3,
+    Failed = 4,
+}
+
+impl ::protobuf::ProtobufEnum for UnitState {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<UnitState> {
+        match value {
+            0 => ::std::option::Option::Some(UnitState::Unknown),
+            1 => ::std::option::Option::Some(UnitState::Active),
+            2 => ::std::option::Option::Some(UnitState::Activating),
+            3 => ::std::option::Option::Some(UnitState::Inactive),
+            4 => ::std::option::Option::Some(UnitState::Failed),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [UnitState] = &[
+            UnitState::Unknown,
+            UnitState::Active,
+            UnitState::Activating,
+            UnitState::Inactive,
+            UnitState::Failed,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("UnitState", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for UnitState {
+}
+
+impl ::std::default::Default for UnitState {
+    fn default() -> Self {
+        UnitState::Unknown
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for UnitState {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
 pub enum SystemdAction {
     Restart = 0,
@@ -1693,127 +5281,479 @@ impl ::protobuf::reflect::ProtobufValue for State {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum ChangeKind {
+    Unchanged = 0,
+    Created = 1,
+    Updated = 2,
+}
+
+impl ::protobuf::ProtobufEnum for ChangeKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<ChangeKind> {
+        match value {
+            0 => ::std::option::Option::Some(ChangeKind::Unchanged),
+            1 => ::std::option::Option::Some(ChangeKind::Created),
+            2 => ::std::option::Option::Some(ChangeKind::Updated),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [ChangeKind] = &[
+            ChangeKind::Unchanged,
+            ChangeKind::Created,
+            ChangeKind::Updated,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("ChangeKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for ChangeKind {
+}
+
+impl ::std::default::Default for ChangeKind {
+    fn default() -> Self {
+        ChangeKind::Unchanged
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ChangeKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x0bagent.proto\x12\x05agent\"#\n\x0bPortAcquire\x12\x14\n\x05count\
     \x18\x01\x20\x01(\x03R\x05count\"\x1d\n\x05Ports\x12\x14\n\x05ports\x18\
-    \x01\x20\x03(\x03R\x05ports\"]\n\x06Action\x12,\n\x06action\x18\x01\x20\
-    \x01(\x0e2\x14.agent.SystemdActionR\x06action\x12%\n\x05insts\x18\x02\
-    \x20\x03(\x0b2\x0f.agent.InstanceR\x05insts\"\xcf\x01\n\tCacheInfo\x12\
-    \x15\n\x06job_id\x18\x01\x20\x01(\tR\x05jobId\x12/\n\ncache_type\x18\x02\
-    \x20\x01(\x0e2\x10.agent.CacheTypeR\tcacheType\x12\x18\n\x07cluster\x18\
-    \x03\x20\x01(\tR\x07cluster\x12\x18\n\x07version\x18\x04\x20\x01(\tR\x07\
-    version\x12\x1f\n\x0bfile_server\x18\x05\x20\x01(\tR\nfileServer\x12%\n\
-    \x05insts\x18\n\x20\x03(\x0b2\x0f.agent.InstanceR\x05insts\"A\n\x08Insta\
-    nce\x12\x12\n\x04port\x18\x01\x20\x01(\x03R\x04port\x12!\n\x05files\x18\
-    \x02\x20\x03(\x0b2\x0b.agent.FileR\x05files\"6\n\x04File\x12\x14\n\x05fp\
-    ath\x18\x01\x20\x01(\tR\x05fpath\x12\x18\n\x07content\x18\x02\x20\x01(\t\
-    R\x07content\"B\n\nCacheState\x12\"\n\x05state\x18\x01\x20\x01(\x0e2\x0c\
-    .agent.StateR\x05state\x12\x10\n\x03msg\x18\x02\x20\x01(\tR\x03msg*H\n\r\
-    SystemdAction\x12\x0b\n\x07Restart\x10\0\x12\t\n\x05Start\x10\x01\x12\
-    \x08\n\x04Stop\x10\x03\x12\n\n\x06Remove\x10\x04\x12\t\n\x05Setup\x10\
-    \x05*6\n\tCacheType\x12\t\n\x05Redis\x10\0\x12\x10\n\x0cRedisCluster\x10\
-    \x01\x12\x0c\n\x08Memcache\x10\x02*&\n\x05State\x12\x08\n\x04Done\x10\0\
-    \x12\x08\n\x04Lost\x10\x01\x12\t\n\x05Error\x10\x022\x98\x01\n\x05Agent\
-    \x12/\n\x06Deploy\x12\x10.agent.CacheInfo\x1a\x11.agent.CacheState\"\0\
-    \x12.\n\x08DoAction\x12\r.agent.Action\x1a\x11.agent.CacheState\"\0\x12.\
-    \n\x08GetPorts\x12\x12.agent.PortAcquire\x1a\x0c.agent.Ports\"\0J\xe8\
-    \x0f\n\x06\x12\x04\0\0B\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\
-    \x02\x12\x03\x02\x08\r\n\n\n\x02\x06\0\x12\x04\x04\0\x08\x01\n\n\n\x03\
-    \x06\0\x01\x12\x03\x04\x08\r\n\x0b\n\x04\x06\0\x02\0\x12\x03\x05\x02/\n\
-    \x0c\n\x05\x06\0\x02\0\x01\x12\x03\x05\x06\x0c\n\x0c\n\x05\x06\0\x02\0\
-    \x02\x12\x03\x05\r\x16\n\x0c\n\x05\x06\0\x02\0\x03\x12\x03\x05!+\n\x0b\n\
-    \x04\x06\0\x02\x01\x12\x03\x06\x02.\n\x0c\n\x05\x06\0\x02\x01\x01\x12\
-    \x03\x06\x06\x0e\n\x0c\n\x05\x06\0\x02\x01\x02\x12\x03\x06\x0f\x15\n\x0c\
-    \n\x05\x06\0\x02\x01\x03\x12\x03\x06\x20*\n\x0b\n\x04\x06\0\x02\x02\x12\
-    \x03\x07\x02.\n\x0c\n\x05\x06\0\x02\x02\x01\x12\x03\x07\x06\x0e\n\x0c\n\
-    \x05\x06\0\x02\x02\x02\x12\x03\x07\x0f\x1a\n\x0c\n\x05\x06\0\x02\x02\x03\
-    \x12\x03\x07%*\n\n\n\x02\x04\0\x12\x04\n\0\x0c\x01\n\n\n\x03\x04\0\x01\
-    \x12\x03\n\x08\x13\n\x0b\n\x04\x04\0\x02\0\x12\x03\x0b\x02\x12\n\r\n\x05\
-    \x04\0\x02\0\x04\x12\x04\x0b\x02\n\x15\n\x0c\n\x05\x04\0\x02\0\x05\x12\
-    \x03\x0b\x02\x07\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x0b\x08\r\n\x0c\n\
-    \x05\x04\0\x02\0\x03\x12\x03\x0b\x10\x11\n\n\n\x02\x04\x01\x12\x04\x0e\0\
-    \x10\x01\n\n\n\x03\x04\x01\x01\x12\x03\x0e\x08\r\n\x0b\n\x04\x04\x01\x02\
-    \0\x12\x03\x0f\x02\x1b\n\x0c\n\x05\x04\x01\x02\0\x04\x12\x03\x0f\x02\n\n\
-    \x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x0f\x0b\x10\n\x0c\n\x05\x04\x01\x02\
-    \0\x01\x12\x03\x0f\x11\x16\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x0f\x19\
-    \x1a\n\n\n\x02\x05\0\x12\x04\x12\0\x18\x01\n\n\n\x03\x05\0\x01\x12\x03\
-    \x12\x05\x12\n\x0b\n\x04\x05\0\x02\0\x12\x03\x13\x02\x0e\n\x0c\n\x05\x05\
-    \0\x02\0\x01\x12\x03\x13\x02\t\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x13\
-    \x0c\r\n\x0b\n\x04\x05\0\x02\x01\x12\x03\x14\x02\x0c\n\x0c\n\x05\x05\0\
-    \x02\x01\x01\x12\x03\x14\x02\x07\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\
-    \x14\n\x0b\n\x0b\n\x04\x05\0\x02\x02\x12\x03\x15\x02\x0b\n\x0c\n\x05\x05\
-    \0\x02\x02\x01\x12\x03\x15\x02\x06\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\
-    \x15\t\n\n\x0b\n\x04\x05\0\x02\x03\x12\x03\x16\x02\r\n\x0c\n\x05\x05\0\
-    \x02\x03\x01\x12\x03\x16\x02\x08\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\
-    \x16\x0b\x0c\n\x0b\n\x04\x05\0\x02\x04\x12\x03\x17\x02\x0c\n\x0c\n\x05\
-    \x05\0\x02\x04\x01\x12\x03\x17\x02\x07\n\x0c\n\x05\x05\0\x02\x04\x02\x12\
-    \x03\x17\n\x0b\n\n\n\x02\x04\x02\x12\x04\x1a\0\x1d\x01\n\n\n\x03\x04\x02\
-    \x01\x12\x03\x1a\x08\x0e\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x1b\x03\x1c\n\
-    \r\n\x05\x04\x02\x02\0\x04\x12\x04\x1b\x03\x1a\x10\n\x0c\n\x05\x04\x02\
-    \x02\0\x06\x12\x03\x1b\x03\x10\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x1b\
-    \x11\x17\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x1b\x1a\x1b\n\x0b\n\x04\
-    \x04\x02\x02\x01\x12\x03\x1c\x03\x1f\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\
-    \x03\x1c\x03\x0b\n\x0c\n\x05\x04\x02\x02\x01\x06\x12\x03\x1c\x0c\x14\n\
-    \x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x1c\x15\x1a\n\x0c\n\x05\x04\x02\
-    \x02\x01\x03\x12\x03\x1c\x1d\x1e\n\n\n\x02\x05\x01\x12\x04\x1f\0#\x01\n\
-    \n\n\x03\x05\x01\x01\x12\x03\x1f\x05\x0e\n\x0b\n\x04\x05\x01\x02\0\x12\
-    \x03\x20\x02\x0c\n\x0c\n\x05\x05\x01\x02\0\x01\x12\x03\x20\x02\x07\n\x0c\
-    \n\x05\x05\x01\x02\0\x02\x12\x03\x20\n\x0b\n\x0b\n\x04\x05\x01\x02\x01\
-    \x12\x03!\x02\x13\n\x0c\n\x05\x05\x01\x02\x01\x01\x12\x03!\x02\x0e\n\x0c\
-    \n\x05\x05\x01\x02\x01\x02\x12\x03!\x11\x12\n\x0b\n\x04\x05\x01\x02\x02\
-    \x12\x03\"\x02\x0f\n\x0c\n\x05\x05\x01\x02\x02\x01\x12\x03\"\x02\n\n\x0c\
-    \n\x05\x05\x01\x02\x02\x02\x12\x03\"\r\x0e\n\n\n\x02\x04\x03\x12\x04%\0-\
-    \x01\n\n\n\x03\x04\x03\x01\x12\x03%\x08\x11\n\x0b\n\x04\x04\x03\x02\0\
-    \x12\x03&\x02\x14\n\r\n\x05\x04\x03\x02\0\x04\x12\x04&\x02%\x13\n\x0c\n\
-    \x05\x04\x03\x02\0\x05\x12\x03&\x02\x08\n\x0c\n\x05\x04\x03\x02\0\x01\
-    \x12\x03&\t\x0f\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03&\x12\x13\n\x0b\n\
-    \x04\x04\x03\x02\x01\x12\x03'\x02\x1b\n\r\n\x05\x04\x03\x02\x01\x04\x12\
-    \x04'\x02&\x14\n\x0c\n\x05\x04\x03\x02\x01\x06\x12\x03'\x02\x0b\n\x0c\n\
-    \x05\x04\x03\x02\x01\x01\x12\x03'\x0c\x16\n\x0c\n\x05\x04\x03\x02\x01\
-    \x03\x12\x03'\x19\x1a\n\x0b\n\x04\x04\x03\x02\x02\x12\x03(\x02\x15\n\r\n\
-    \x05\x04\x03\x02\x02\x04\x12\x04(\x02'\x1b\n\x0c\n\x05\x04\x03\x02\x02\
-    \x05\x12\x03(\x02\x08\n\x0c\n\x05\x04\x03\x02\x02\x01\x12\x03(\t\x10\n\
-    \x0c\n\x05\x04\x03\x02\x02\x03\x12\x03(\x13\x14\n\x0b\n\x04\x04\x03\x02\
-    \x03\x12\x03)\x02\x15\n\r\n\x05\x04\x03\x02\x03\x04\x12\x04)\x02(\x15\n\
-    \x0c\n\x05\x04\x03\x02\x03\x05\x12\x03)\x02\x08\n\x0c\n\x05\x04\x03\x02\
-    \x03\x01\x12\x03)\t\x10\n\x0c\n\x05\x04\x03\x02\x03\x03\x12\x03)\x13\x14\
-    \n\x0b\n\x04\x04\x03\x02\x04\x12\x03*\x02\x19\n\r\n\x05\x04\x03\x02\x04\
-    \x04\x12\x04*\x02)\x15\n\x0c\n\x05\x04\x03\x02\x04\x05\x12\x03*\x02\x08\
-    \n\x0c\n\x05\x04\x03\x02\x04\x01\x12\x03*\t\x14\n\x0c\n\x05\x04\x03\x02\
-    \x04\x03\x12\x03*\x17\x18\n\x0b\n\x04\x04\x03\x02\x05\x12\x03,\x02\x1f\n\
-    \x0c\n\x05\x04\x03\x02\x05\x04\x12\x03,\x02\n\n\x0c\n\x05\x04\x03\x02\
-    \x05\x06\x12\x03,\x0b\x13\n\x0c\n\x05\x04\x03\x02\x05\x01\x12\x03,\x14\
-    \x19\n\x0c\n\x05\x04\x03\x02\x05\x03\x12\x03,\x1c\x1e\n\n\n\x02\x04\x04\
-    \x12\x04/\02\x01\n\n\n\x03\x04\x04\x01\x12\x03/\x08\x10\n\x0b\n\x04\x04\
-    \x04\x02\0\x12\x030\x02\x11\n\r\n\x05\x04\x04\x02\0\x04\x12\x040\x02/\
-    \x12\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x030\x02\x07\n\x0c\n\x05\x04\x04\
-    \x02\0\x01\x12\x030\x08\x0c\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x030\x0f\
-    \x10\n\x0b\n\x04\x04\x04\x02\x01\x12\x031\x02\x1a\n\x0c\n\x05\x04\x04\
-    \x02\x01\x04\x12\x031\x02\n\n\x0c\n\x05\x04\x04\x02\x01\x06\x12\x031\x0b\
-    \x0f\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x031\x10\x15\n\x0c\n\x05\x04\
-    \x04\x02\x01\x03\x12\x031\x18\x19\n\n\n\x02\x04\x05\x12\x044\07\x01\n\n\
-    \n\x03\x04\x05\x01\x12\x034\x08\x0c\n\x0b\n\x04\x04\x05\x02\0\x12\x035\
-    \x02\x13\n\r\n\x05\x04\x05\x02\0\x04\x12\x045\x024\x0e\n\x0c\n\x05\x04\
-    \x05\x02\0\x05\x12\x035\x02\x08\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x035\t\
-    \x0e\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x035\x11\x12\n\x0b\n\x04\x04\x05\
-    \x02\x01\x12\x036\x02\x15\n\r\n\x05\x04\x05\x02\x01\x04\x12\x046\x025\
-    \x13\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x036\x02\x08\n\x0c\n\x05\x04\
-    \x05\x02\x01\x01\x12\x036\t\x10\n\x0c\n\x05\x04\x05\x02\x01\x03\x12\x036\
-    \x13\x14\n\n\n\x02\x05\x02\x12\x049\0=\x01\n\n\n\x03\x05\x02\x01\x12\x03\
-    9\x05\n\n\x0b\n\x04\x05\x02\x02\0\x12\x03:\x02\x0b\n\x0c\n\x05\x05\x02\
-    \x02\0\x01\x12\x03:\x02\x06\n\x0c\n\x05\x05\x02\x02\0\x02\x12\x03:\t\n\n\
-    \x0b\n\x04\x05\x02\x02\x01\x12\x03;\x02\x0b\n\x0c\n\x05\x05\x02\x02\x01\
-    \x01\x12\x03;\x02\x06\n\x0c\n\x05\x05\x02\x02\x01\x02\x12\x03;\t\n\n\x0b\
-    \n\x04\x05\x02\x02\x02\x12\x03<\x02\x0c\n\x0c\n\x05\x05\x02\x02\x02\x01\
-    \x12\x03<\x02\x07\n\x0c\n\x05\x05\x02\x02\x02\x02\x12\x03<\n\x0b\n\n\n\
-    \x02\x04\x06\x12\x04?\0B\x01\n\n\n\x03\x04\x06\x01\x12\x03?\x08\x12\n\
-    \x0b\n\x04\x04\x06\x02\0\x12\x03@\x02\x12\n\r\n\x05\x04\x06\x02\0\x04\
-    \x12\x04@\x02?\x14\n\x0c\n\x05\x04\x06\x02\0\x06\x12\x03@\x02\x07\n\x0c\
-    \n\x05\x04\x06\x02\0\x01\x12\x03@\x08\r\n\x0c\n\x05\x04\x06\x02\0\x03\
-    \x12\x03@\x10\x11\n\x0b\n\x04\x04\x06\x02\x01\x12\x03A\x02\x11\n\r\n\x05\
-    \x04\x06\x02\x01\x04\x12\x04A\x02@\x12\n\x0c\n\x05\x04\x06\x02\x01\x05\
-    \x12\x03A\x02\x08\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x03A\t\x0c\n\x0c\n\
-    \x05\x04\x06\x02\x01\x03\x12\x03A\x0f\x10b\x06proto3\
+    \x01\x20\x03(\x03R\x05ports\"\r\n\x0bBinaryQuery\"<\n\x0bBinaryPurge\x12\
+    \x14\n\x05force\x18\x01\x20\x01(\x08R\x05force\x12\x17\n\x07dry_run\x18\
+    \x02\x20\x01(\x08R\x06dryRun\"z\n\x0cCachedBinary\x12\x1d\n\ncache_type\
+    \x18\x01\x20\x01(\tR\tcacheType\x12\x18\n\x07version\x18\x02\x20\x01(\tR\
+    \x07version\x12\x14\n\x05ports\x18\x03\x20\x03(\x03R\x05ports\x12\x1b\n\
+    \tlast_used\x18\x04\x20\x01(\x03R\x08lastUsed\";\n\x08Binaries\x12/\n\
+    \x08binaries\x18\x01\x20\x03(\x0b2\x13.agent.CachedBinaryR\x08binaries\"\
+    &\n\x0eRestoreRequest\x12\x14\n\x05ports\x18\x01\x20\x03(\x03R\x05ports\
+    \"=\n\x0bStatusQuery\x12\x14\n\x05ports\x18\x01\x20\x03(\x03R\x05ports\
+    \x12\x18\n\x07cluster\x18\x02\x20\x01(\tR\x07cluster\"\x83\x02\n\x0eInst\
+    anceStatus\x12\x12\n\x04port\x18\x01\x20\x01(\x03R\x04port\x12\x12\n\x04\
+    unit\x18\x02\x20\x01(\tR\x04unit\x12&\n\x05state\x18\x03\x20\x01(\x0e2\
+    \x10.agent.UnitStateR\x05state\x12\x10\n\x03pid\x18\x04\x20\x01(\x03R\
+    \x03pid\x12\x16\n\x06uptime\x18\x05\x20\x01(\x03R\x06uptime\x12\x1a\n\
+    \x08restarts\x18\x06\x20\x01(\x03R\x08restarts\x12\x10\n\x03rss\x18\x07\
+    \x20\x01(\x03R\x03rss\x12\x19\n\x08cpu_time\x18\x08\x20\x01(\x03R\x07cpu\
+    Time\x12\x1c\n\taccepting\x18\t\x20\x01(\x08R\taccepting\x12\x10\n\x03ms\
+    g\x18\n\x20\x01(\tR\x03msg\"E\n\x10InstanceStatuses\x121\n\x08statuses\
+    \x18\x01\x20\x03(\x0b2\x15.agent.InstanceStatusR\x08statuses\"$\n\x0cMet\
+    ricsQuery\x12\x14\n\x05since\x18\x01\x20\x01(\x03R\x05since\"\xb7\x02\n\
+    \x0bHostMetrics\x12\x19\n\x08cpu_used\x18\x01\x20\x01(\x03R\x07cpuUsed\
+    \x12\x1b\n\tcpu_total\x18\x02\x20\x01(\x03R\x08cpuTotal\x12\x1f\n\x0bmem\
+    ory_used\x18\x03\x20\x01(\x03R\nmemoryUsed\x12!\n\x0cmemory_total\x18\
+    \x04\x20\x01(\x03R\x0bmemoryTotal\x12\x1b\n\tdisk_used\x18\x05\x20\x01(\
+    \x03R\x08diskUsed\x12\x1d\n\ndisk_total\x18\x06\x20\x01(\x03R\tdiskTotal\
+    \x12\x15\n\x06net_rx\x18\x07\x20\x01(\x03R\x05netRx\x12\x15\n\x06net_tx\
+    \x18\x08\x20\x01(\x03R\x05netTx\x12\x14\n\x05load1\x18\t\x20\x01(\x01R\
+    \x05load1\x12\x14\n\x05load5\x18\n\x20\x01(\x01R\x05load5\x12\x16\n\x06l\
+    oad15\x18\x0b\x20\x01(\x01R\x06load15\"Z\n\x0eProcessMetrics\x12\x12\n\
+    \x04port\x18\x01\x20\x01(\x03R\x04port\x12\x10\n\x03pid\x18\x02\x20\x01(\
+    \x03R\x03pid\x12\x10\n\x03cpu\x18\x03\x20\x01(\x03R\x03cpu\x12\x10\n\x03\
+    rss\x18\x04\x20\x01(\x03R\x03rss\"\x82\x01\n\rMetricsSample\x12\x1c\n\tt\
+    imestamp\x18\x01\x20\x01(\x03R\ttimestamp\x12&\n\x04host\x18\x02\x20\x01\
+    (\x0b2\x12.agent.HostMetricsR\x04host\x12+\n\x05insts\x18\x03\x20\x03(\
+    \x0b2\x15.agent.ProcessMetricsR\x05insts\"@\n\x0eMetricsSamples\x12.\n\
+    \x07samples\x18\x01\x20\x03(\x0b2\x14.agent.MetricsSampleR\x07samples\"w\
+    \n\x06Action\x12,\n\x06action\x18\x01\x20\x01(\x0e2\x14.agent.SystemdAct\
+    ionR\x06action\x12%\n\x05insts\x18\x02\x20\x03(\x0b2\x0f.agent.InstanceR\
+    \x05insts\x12\x18\n\x07cluster\x18\x03\x20\x01(\tR\x07cluster\"\xeb\x01\
+    \n\tCacheInfo\x12\x15\n\x06job_id\x18\x01\x20\x01(\tR\x05jobId\x12/\n\nc\
+    ache_type\x18\x02\x20\x01(\x0e2\x10.agent.CacheTypeR\tcacheType\x12\x18\
+    \n\x07cluster\x18\x03\x20\x01(\tR\x07cluster\x12\x18\n\x07version\x18\
+    \x04\x20\x01(\tR\x07version\x12\x1f\n\x0bfile_server\x18\x05\x20\x01(\tR\
+    \nfileServer\x12\x1a\n\x08converge\x18\x06\x20\x01(\x08R\x08converge\x12\
+    %\n\x05insts\x18\n\x20\x03(\x0b2\x0f.agent.InstanceR\x05insts\"A\n\x08In\
+    stance\x12\x12\n\x04port\x18\x01\x20\x01(\x03R\x04port\x12!\n\x05files\
+    \x18\x02\x20\x03(\x0b2\x0b.agent.FileR\x05files\"6\n\x04File\x12\x14\n\
+    \x05fpath\x18\x01\x20\x01(\tR\x05fpath\x12\x18\n\x07content\x18\x02\x20\
+    \x01(\tR\x07content\"\x7f\n\x0eInstanceChange\x12\x12\n\x04port\x18\x01\
+    \x20\x01(\x03R\x04port\x12%\n\x04kind\x18\x02\x20\x01(\x0e2\x11.agent.Ch\
+    angeKindR\x04kind\x12\x14\n\x05files\x18\x03\x20\x03(\tR\x05files\x12\
+    \x1c\n\trestarted\x18\x04\x20\x01(\x08R\trestarted\"s\n\nCacheState\x12\
+    \"\n\x05state\x18\x01\x20\x01(\x0e2\x0c.agent.StateR\x05state\x12\x10\n\
+    \x03msg\x18\x02\x20\x01(\tR\x03msg\x12/\n\x07changes\x18\x03\x20\x03(\
+    \x0b2\x15.agent.InstanceChangeR\x07changes*N\n\tUnitState\x12\x0b\n\x07U\
+    nknown\x10\0\x12\n\n\x06Active\x10\x01\x12\x0e\n\nActivating\x10\x02\x12\
+    \x0c\n\x08Inactive\x10\x03\x12\n\n\x06Failed\x10\x04*H\n\rSystemdAction\
+    \x12\x0b\n\x07Restart\x10\0\x12\t\n\x05Start\x10\x01\x12\x08\n\x04Stop\
+    \x10\x03\x12\n\n\x06Remove\x10\x04\x12\t\n\x05Setup\x10\x05*6\n\tCacheTy\
+    pe\x12\t\n\x05Redis\x10\0\x12\x10\n\x0cRedisCluster\x10\x01\x12\x0c\n\
+    \x08Memcache\x10\x02*&\n\x05State\x12\x08\n\x04Done\x10\0\x12\x08\n\x04L\
+    ost\x10\x01\x12\t\n\x05Error\x10\x02*5\n\nChangeKind\x12\r\n\tUnchanged\
+    \x10\0\x12\x0b\n\x07Created\x10\x01\x12\x0b\n\x07Updated\x10\x022\xb0\
+    \x03\n\x05Agent\x12/\n\x06Deploy\x12\x10.agent.CacheInfo\x1a\x11.agent.C\
+    acheState\"\0\x12.\n\x08DoAction\x12\r.agent.Action\x1a\x11.agent.CacheS\
+    tate\"\0\x12.\n\x08GetPorts\x12\x12.agent.PortAcquire\x1a\x0c.agent.Port\
+    s\"\0\x125\n\x0cListBinaries\x12\x12.agent.BinaryQuery\x1a\x0f.agent.Bin\
+    aries\"\0\x126\n\rPurgeBinaries\x12\x12.agent.BinaryPurge\x1a\x0f.agent.\
+    Binaries\"\0\x125\n\x07Restore\x12\x15.agent.RestoreRequest\x1a\x11.agen\
+    t.CacheState\"\0\x127\n\x06Status\x12\x12.agent.StatusQuery\x1a\x17.agen\
+    t.InstanceStatuses\"\0\x127\n\x07Metrics\x12\x13.agent.MetricsQuery\x1a\
+    \x15.agent.MetricsSamples\"\0J\x96;\n\x07\x12\x05\0\0\xc5\x01\x01\n\x08\
+    \n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x02\x08\r\n\n\n\x02\
+    \x06\0\x12\x04\x04\0\r\x01\n\n\n\x03\x06\0\x01\x12\x03\x04\x08\r\n\x0b\n\
+    \x04\x06\0\x02\0\x12\x03\x05\x02/\n\x0c\n\x05\x06\0\x02\0\x01\x12\x03\
+    \x05\x06\x0c\n\x0c\n\x05\x06\0\x02\0\x02\x12\x03\x05\r\x16\n\x0c\n\x05\
+    \x06\0\x02\0\x03\x12\x03\x05!+\n\x0b\n\x04\x06\0\x02\x01\x12\x03\x06\x02\
+    .\n\x0c\n\x05\x06\0\x02\x01\x01\x12\x03\x06\x06\x0e\n\x0c\n\x05\x06\0\
+    \x02\x01\x02\x12\x03\x06\x0f\x15\n\x0c\n\x05\x06\0\x02\x01\x03\x12\x03\
+    \x06\x20*\n\x0b\n\x04\x06\0\x02\x02\x12\x03\x07\x02.\n\x0c\n\x05\x06\0\
+    \x02\x02\x01\x12\x03\x07\x06\x0e\n\x0c\n\x05\x06\0\x02\x02\x02\x12\x03\
+    \x07\x0f\x1a\n\x0c\n\x05\x06\0\x02\x02\x03\x12\x03\x07%*\n\x0b\n\x04\x06\
+    \0\x02\x03\x12\x03\x08\x025\n\x0c\n\x05\x06\0\x02\x03\x01\x12\x03\x08\
+    \x06\x12\n\x0c\n\x05\x06\0\x02\x03\x02\x12\x03\x08\x13\x1e\n\x0c\n\x05\
+    \x06\0\x02\x03\x03\x12\x03\x08)1\n\x0b\n\x04\x06\0\x02\x04\x12\x03\t\x02\
+    6\n\x0c\n\x05\x06\0\x02\x04\x01\x12\x03\t\x06\x13\n\x0c\n\x05\x06\0\x02\
+    \x04\x02\x12\x03\t\x14\x1f\n\x0c\n\x05\x06\0\x02\x04\x03\x12\x03\t*2\n\
+    \x0b\n\x04\x06\0\x02\x05\x12\x03\n\x025\n\x0c\n\x05\x06\0\x02\x05\x01\
+    \x12\x03\n\x06\r\n\x0c\n\x05\x06\0\x02\x05\x02\x12\x03\n\x0e\x1c\n\x0c\n\
+    \x05\x06\0\x02\x05\x03\x12\x03\n'1\n\x0b\n\x04\x06\0\x02\x06\x12\x03\x0b\
+    \x027\n\x0c\n\x05\x06\0\x02\x06\x01\x12\x03\x0b\x06\x0c\n\x0c\n\x05\x06\
+    \0\x02\x06\x02\x12\x03\x0b\r\x18\n\x0c\n\x05\x06\0\x02\x06\x03\x12\x03\
+    \x0b#3\n\x0b\n\x04\x06\0\x02\x07\x12\x03\x0c\x027\n\x0c\n\x05\x06\0\x02\
+    \x07\x01\x12\x03\x0c\x06\r\n\x0c\n\x05\x06\0\x02\x07\x02\x12\x03\x0c\x0e\
+    \x1a\n\x0c\n\x05\x06\0\x02\x07\x03\x12\x03\x0c%3\n\n\n\x02\x04\0\x12\x04\
+    \x0f\0\x11\x01\n\n\n\x03\x04\0\x01\x12\x03\x0f\x08\x13\n\x0b\n\x04\x04\0\
+    \x02\0\x12\x03\x10\x02\x12\n\r\n\x05\x04\0\x02\0\x04\x12\x04\x10\x02\x0f\
+    \x15\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x10\x02\x07\n\x0c\n\x05\x04\0\
+    \x02\0\x01\x12\x03\x10\x08\r\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x10\x10\
+    \x11\n\n\n\x02\x04\x01\x12\x04\x13\0\x15\x01\n\n\n\x03\x04\x01\x01\x12\
+    \x03\x13\x08\r\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x14\x02\x1b\n\x0c\n\x05\
+    \x04\x01\x02\0\x04\x12\x03\x14\x02\n\n\x0c\n\x05\x04\x01\x02\0\x05\x12\
+    \x03\x14\x0b\x10\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x14\x11\x16\n\x0c\
+    \n\x05\x04\x01\x02\0\x03\x12\x03\x14\x19\x1a\n\n\n\x02\x04\x02\x12\x04\
+    \x17\0\x18\x01\n\n\n\x03\x04\x02\x01\x12\x03\x17\x08\x13\n\n\n\x02\x04\
+    \x03\x12\x04\x1a\0\x1e\x01\n\n\n\x03\x04\x03\x01\x12\x03\x1a\x08\x13\nG\
+    \n\x04\x04\x03\x02\0\x12\x03\x1c\x02\x11\x1a:\x20purge\x20all\x20the\x20\
+    unreferenced\x20binaries\x20without\x20grace\x20period\n\n\r\n\x05\x04\
+    \x03\x02\0\x04\x12\x04\x1c\x02\x1a\x15\n\x0c\n\x05\x04\x03\x02\0\x05\x12\
+    \x03\x1c\x02\x06\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x1c\x07\x0c\n\x0c\
+    \n\x05\x04\x03\x02\0\x03\x12\x03\x1c\x0f\x10\n\x0b\n\x04\x04\x03\x02\x01\
+    \x12\x03\x1d\x02\x13\n\r\n\x05\x04\x03\x02\x01\x04\x12\x04\x1d\x02\x1c\
+    \x11\n\x0c\n\x05\x04\x03\x02\x01\x05\x12\x03\x1d\x02\x06\n\x0c\n\x05\x04\
+    \x03\x02\x01\x01\x12\x03\x1d\x07\x0e\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\
+    \x03\x1d\x11\x12\n\n\n\x02\x04\x04\x12\x04\x20\0'\x01\n\n\n\x03\x04\x04\
+    \x01\x12\x03\x20\x08\x14\n\x0b\n\x04\x04\x04\x02\0\x12\x03!\x02\x18\n\r\
+    \n\x05\x04\x04\x02\0\x04\x12\x04!\x02\x20\x16\n\x0c\n\x05\x04\x04\x02\0\
+    \x05\x12\x03!\x02\x08\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03!\t\x13\n\x0c\
+    \n\x05\x04\x04\x02\0\x03\x12\x03!\x16\x17\n\x0b\n\x04\x04\x04\x02\x01\
+    \x12\x03\"\x02\x15\n\r\n\x05\x04\x04\x02\x01\x04\x12\x04\"\x02!\x18\n\
+    \x0c\n\x05\x04\x04\x02\x01\x05\x12\x03\"\x02\x08\n\x0c\n\x05\x04\x04\x02\
+    \x01\x01\x12\x03\"\t\x10\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03\"\x13\
+    \x14\n8\n\x04\x04\x04\x02\x02\x12\x03$\x02\x1b\x1a+\x20ports\x20of\x20th\
+    e\x20instances\x20which\x20reference\x20it\n\n\x0c\n\x05\x04\x04\x02\x02\
+    \x04\x12\x03$\x02\n\n\x0c\n\x05\x04\x04\x02\x02\x05\x12\x03$\x0b\x10\n\
+    \x0c\n\x05\x04\x04\x02\x02\x01\x12\x03$\x11\x16\n\x0c\n\x05\x04\x04\x02\
+    \x02\x03\x12\x03$\x19\x1a\n@\n\x04\x04\x04\x02\x03\x12\x03&\x02\x16\x1a3\
+    \x20unix\x20timestamp\x20of\x20the\x20last\x20time\x20it\x20was\x20refer\
+    enced\n\n\r\n\x05\x04\x04\x02\x03\x04\x12\x04&\x02$\x1b\n\x0c\n\x05\x04\
+    \x04\x02\x03\x05\x12\x03&\x02\x07\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\
+    \x03&\x08\x11\n\x0c\n\x05\x04\x04\x02\x03\x03\x12\x03&\x14\x15\n\n\n\x02\
+    \x04\x05\x12\x04)\0+\x01\n\n\n\x03\x04\x05\x01\x12\x03)\x08\x10\n\x0b\n\
+    \x04\x04\x05\x02\0\x12\x03*\x02%\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03*\
+    \x02\n\n\x0c\n\x05\x04\x05\x02\0\x06\x12\x03*\x0b\x17\n\x0c\n\x05\x04\
+    \x05\x02\0\x01\x12\x03*\x18\x20\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03*#$\
+    \nC\n\x02\x04\x06\x12\x04.\00\x01\x1a7\x20restore\x20the\x20last\x20know\
+    n\x20good\x20config\x20files\x20of\x20instances\n\n\n\n\x03\x04\x06\x01\
+    \x12\x03.\x08\x16\n\x0b\n\x04\x04\x06\x02\0\x12\x03/\x02\x1b\n\x0c\n\x05\
+    \x04\x06\x02\0\x04\x12\x03/\x02\n\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x03/\
+    \x0b\x10\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03/\x11\x16\n\x0c\n\x05\x04\
+    \x06\x02\0\x03\x12\x03/\x19\x1a\n\x98\x01\n\x02\x04\x07\x12\x044\07\x01\
+    \x1a\x8b\x01\x20status\x20of\x20the\x20instances\x20on\x20ports,\x20or\
+    \x20all\x20the\x20instances\x20of\x20cluster\x20if\x20ports\x20is\x20emp\
+    ty,\n\x20or\x20all\x20the\x20instances\x20on\x20the\x20host\x20if\x20bot\
+    h\x20are\x20empty.\n\n\n\n\x03\x04\x07\x01\x12\x034\x08\x13\n\x0b\n\x04\
+    \x04\x07\x02\0\x12\x035\x02\x1b\n\x0c\n\x05\x04\x07\x02\0\x04\x12\x035\
+    \x02\n\n\x0c\n\x05\x04\x07\x02\0\x05\x12\x035\x0b\x10\n\x0c\n\x05\x04\
+    \x07\x02\0\x01\x12\x035\x11\x16\n\x0c\n\x05\x04\x07\x02\0\x03\x12\x035\
+    \x19\x1a\n\x0b\n\x04\x04\x07\x02\x01\x12\x036\x02\x15\n\r\n\x05\x04\x07\
+    \x02\x01\x04\x12\x046\x025\x1b\n\x0c\n\x05\x04\x07\x02\x01\x05\x12\x036\
+    \x02\x08\n\x0c\n\x05\x04\x07\x02\x01\x01\x12\x036\t\x10\n\x0c\n\x05\x04\
+    \x07\x02\x01\x03\x12\x036\x13\x14\n\n\n\x02\x05\0\x12\x049\0?\x01\n\n\n\
+    \x03\x05\0\x01\x12\x039\x05\x0e\n\x0b\n\x04\x05\0\x02\0\x12\x03:\x02\x0e\
+    \n\x0c\n\x05\x05\0\x02\0\x01\x12\x03:\x02\t\n\x0c\n\x05\x05\0\x02\0\x02\
+    \x12\x03:\x0c\r\n\x0b\n\x04\x05\0\x02\x01\x12\x03;\x02\r\n\x0c\n\x05\x05\
+    \0\x02\x01\x01\x12\x03;\x02\x08\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03;\
+    \x0b\x0c\n\x0b\n\x04\x05\0\x02\x02\x12\x03<\x02\x11\n\x0c\n\x05\x05\0\
+    \x02\x02\x01\x12\x03<\x02\x0c\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03<\x0f\
+    \x10\n\x0b\n\x04\x05\0\x02\x03\x12\x03=\x02\x0f\n\x0c\n\x05\x05\0\x02\
+    \x03\x01\x12\x03=\x02\n\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03=\r\x0e\n\
+    \x0b\n\x04\x05\0\x02\x04\x12\x03>\x02\r\n\x0c\n\x05\x05\0\x02\x04\x01\
+    \x12\x03>\x02\x08\n\x0c\n\x05\x05\0\x02\x04\x02\x12\x03>\x0b\x0c\n\n\n\
+    \x02\x04\x08\x12\x04A\0R\x01\n\n\n\x03\x04\x08\x01\x12\x03A\x08\x16\n\
+    \x0b\n\x04\x04\x08\x02\0\x12\x03B\x02\x11\n\r\n\x05\x04\x08\x02\0\x04\
+    \x12\x04B\x02A\x18\n\x0c\n\x05\x04\x08\x02\0\x05\x12\x03B\x02\x07\n\x0c\
+    \n\x05\x04\x08\x02\0\x01\x12\x03B\x08\x0c\n\x0c\n\x05\x04\x08\x02\0\x03\
+    \x12\x03B\x0f\x10\n\x0b\n\x04\x04\x08\x02\x01\x12\x03C\x02\x12\n\r\n\x05\
+    \x04\x08\x02\x01\x04\x12\x04C\x02B\x11\n\x0c\n\x05\x04\x08\x02\x01\x05\
+    \x12\x03C\x02\x08\n\x0c\n\x05\x04\x08\x02\x01\x01\x12\x03C\t\r\n\x0c\n\
+    \x05\x04\x08\x02\x01\x03\x12\x03C\x10\x11\n\x0b\n\x04\x04\x08\x02\x02\
+    \x12\x03D\x02\x16\n\r\n\x05\x04\x08\x02\x02\x04\x12\x04D\x02C\x12\n\x0c\
+    \n\x05\x04\x08\x02\x02\x06\x12\x03D\x02\x0b\n\x0c\n\x05\x04\x08\x02\x02\
+    \x01\x12\x03D\x0c\x11\n\x0c\n\x05\x04\x08\x02\x02\x03\x12\x03D\x14\x15\n\
+    /\n\x04\x04\x08\x02\x03\x12\x03F\x02\x10\x1a\"\x200\x20if\x20the\x20inst\
+    ance\x20is\x20not\x20running\n\n\r\n\x05\x04\x08\x02\x03\x04\x12\x04F\
+    \x02D\x16\n\x0c\n\x05\x04\x08\x02\x03\x05\x12\x03F\x02\x07\n\x0c\n\x05\
+    \x04\x08\x02\x03\x01\x12\x03F\x08\x0b\n\x0c\n\x05\x04\x08\x02\x03\x03\
+    \x12\x03F\x0e\x0f\n0\n\x04\x04\x08\x02\x04\x12\x03H\x02\x13\x1a#\x20seco\
+    nds\x20since\x20the\x20process\x20started\n\n\r\n\x05\x04\x08\x02\x04\
+    \x04\x12\x04H\x02F\x10\n\x0c\n\x05\x04\x08\x02\x04\x05\x12\x03H\x02\x07\
+    \n\x0c\n\x05\x04\x08\x02\x04\x01\x12\x03H\x08\x0e\n\x0c\n\x05\x04\x08\
+    \x02\x04\x03\x12\x03H\x11\x12\n\x0b\n\x04\x04\x08\x02\x05\x12\x03I\x02\
+    \x15\n\r\n\x05\x04\x08\x02\x05\x04\x12\x04I\x02H\x13\n\x0c\n\x05\x04\x08\
+    \x02\x05\x05\x12\x03I\x02\x07\n\x0c\n\x05\x04\x08\x02\x05\x01\x12\x03I\
+    \x08\x10\n\x0c\n\x05\x04\x08\x02\x05\x03\x12\x03I\x13\x14\n'\n\x04\x04\
+    \x08\x02\x06\x12\x03K\x02\x10\x1a\x1a\x20resident\x20memory\x20in\x20byt\
+    es\n\n\r\n\x05\x04\x08\x02\x06\x04\x12\x04K\x02I\x15\n\x0c\n\x05\x04\x08\
+    \x02\x06\x05\x12\x03K\x02\x07\n\x0c\n\x05\x04\x08\x02\x06\x01\x12\x03K\
+    \x08\x0b\n\x0c\n\x05\x04\x08\x02\x06\x03\x12\x03K\x0e\x0f\n7\n\x04\x04\
+    \x08\x02\x07\x12\x03M\x02\x15\x1a*\x20user\x20and\x20system\x20cpu\x20ti\
+    me\x20in\x20milliseconds\n\n\r\n\x05\x04\x08\x02\x07\x04\x12\x04M\x02K\
+    \x10\n\x0c\n\x05\x04\x08\x02\x07\x05\x12\x03M\x02\x07\n\x0c\n\x05\x04\
+    \x08\x02\x07\x01\x12\x03M\x08\x10\n\x0c\n\x05\x04\x08\x02\x07\x03\x12\
+    \x03M\x13\x14\n4\n\x04\x04\x08\x02\x08\x12\x03O\x02\x15\x1a'\x20the\x20p\
+    ort\x20is\x20accepting\x20tcp\x20connections\n\n\r\n\x05\x04\x08\x02\x08\
+    \x04\x12\x04O\x02M\x15\n\x0c\n\x05\x04\x08\x02\x08\x05\x12\x03O\x02\x06\
+    \n\x0c\n\x05\x04\x08\x02\x08\x01\x12\x03O\x07\x10\n\x0c\n\x05\x04\x08\
+    \x02\x08\x03\x12\x03O\x13\x14\n+\n\x04\x04\x08\x02\t\x12\x03Q\x02\x12\
+    \x1a\x1e\x20why\x20the\x20status\x20is\x20incomplete\n\n\r\n\x05\x04\x08\
+    \x02\t\x04\x12\x04Q\x02O\x15\n\x0c\n\x05\x04\x08\x02\t\x05\x12\x03Q\x02\
+    \x08\n\x0c\n\x05\x04\x08\x02\t\x01\x12\x03Q\t\x0c\n\x0c\n\x05\x04\x08\
+    \x02\t\x03\x12\x03Q\x0f\x11\n\n\n\x02\x04\t\x12\x04T\0V\x01\n\n\n\x03\
+    \x04\t\x01\x12\x03T\x08\x18\n\x0b\n\x04\x04\t\x02\0\x12\x03U\x02'\n\x0c\
+    \n\x05\x04\t\x02\0\x04\x12\x03U\x02\n\n\x0c\n\x05\x04\t\x02\0\x06\x12\
+    \x03U\x0b\x19\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03U\x1a\"\n\x0c\n\x05\x04\
+    \t\x02\0\x03\x12\x03U%&\n\n\n\x02\x04\n\x12\x04X\0[\x01\n\n\n\x03\x04\n\
+    \x01\x12\x03X\x08\x14\nI\n\x04\x04\n\x02\0\x12\x03Z\x02\x12\x1a<\x20only\
+    \x20the\x20samples\x20taken\x20after\x20the\x20unix\x20timestamp,\x200\
+    \x20for\x20all\n\n\r\n\x05\x04\n\x02\0\x04\x12\x04Z\x02X\x16\n\x0c\n\x05\
+    \x04\n\x02\0\x05\x12\x03Z\x02\x07\n\x0c\n\x05\x04\n\x02\0\x01\x12\x03Z\
+    \x08\r\n\x0c\n\x05\x04\n\x02\0\x03\x12\x03Z\x10\x11\n\n\n\x02\x04\x0b\
+    \x12\x04]\0m\x01\n\n\n\x03\x04\x0b\x01\x12\x03]\x08\x13\nF\n\x04\x04\x0b\
+    \x02\0\x12\x03_\x02\x15\x1a9\x20percentage\x20of\x20all\x20the\x20cpus,\
+    \x20100\x20for\x20each\x20cpu\x20like\x20offer\n\n\r\n\x05\x04\x0b\x02\0\
+    \x04\x12\x04_\x02]\x15\n\x0c\n\x05\x04\x0b\x02\0\x05\x12\x03_\x02\x07\n\
+    \x0c\n\x05\x04\x0b\x02\0\x01\x12\x03_\x08\x10\n\x0c\n\x05\x04\x0b\x02\0\
+    \x03\x12\x03_\x13\x14\n\x0b\n\x04\x04\x0b\x02\x01\x12\x03`\x02\x16\n\r\n\
+    \x05\x04\x0b\x02\x01\x04\x12\x04`\x02_\x15\n\x0c\n\x05\x04\x0b\x02\x01\
+    \x05\x12\x03`\x02\x07\n\x0c\n\x05\x04\x0b\x02\x01\x01\x12\x03`\x08\x11\n\
+    \x0c\n\x05\x04\x0b\x02\x01\x03\x12\x03`\x14\x15\n\x17\n\x04\x04\x0b\x02\
+    \x02\x12\x03b\x02\x18\x1a\n\x20in\x20bytes\n\n\r\n\x05\x04\x0b\x02\x02\
+    \x04\x12\x04b\x02`\x16\n\x0c\n\x05\x04\x0b\x02\x02\x05\x12\x03b\x02\x07\
+    \n\x0c\n\x05\x04\x0b\x02\x02\x01\x12\x03b\x08\x13\n\x0c\n\x05\x04\x0b\
+    \x02\x02\x03\x12\x03b\x16\x17\n\x0b\n\x04\x04\x0b\x02\x03\x12\x03c\x02\
+    \x19\n\r\n\x05\x04\x0b\x02\x03\x04\x12\x04c\x02b\x18\n\x0c\n\x05\x04\x0b\
+    \x02\x03\x05\x12\x03c\x02\x07\n\x0c\n\x05\x04\x0b\x02\x03\x01\x12\x03c\
+    \x08\x14\n\x0c\n\x05\x04\x0b\x02\x03\x03\x12\x03c\x17\x18\n)\n\x04\x04\
+    \x0b\x02\x04\x12\x03e\x02\x16\x1a\x1c\x20of\x20the\x20data\x20root,\x20i\
+    n\x20bytes\n\n\r\n\x05\x04\x0b\x02\x04\x04\x12\x04e\x02c\x19\n\x0c\n\x05\
+    \x04\x0b\x02\x04\x05\x12\x03e\x02\x07\n\x0c\n\x05\x04\x0b\x02\x04\x01\
+    \x12\x03e\x08\x11\n\x0c\n\x05\x04\x0b\x02\x04\x03\x12\x03e\x14\x15\n\x0b\
+    \n\x04\x04\x0b\x02\x05\x12\x03f\x02\x17\n\r\n\x05\x04\x0b\x02\x05\x04\
+    \x12\x04f\x02e\x16\n\x0c\n\x05\x04\x0b\x02\x05\x05\x12\x03f\x02\x07\n\
+    \x0c\n\x05\x04\x0b\x02\x05\x01\x12\x03f\x08\x12\n\x0c\n\x05\x04\x0b\x02\
+    \x05\x03\x12\x03f\x15\x16\n?\n\x04\x04\x0b\x02\x06\x12\x03h\x02\x13\x1a2\
+    \x20bytes\x20per\x20second\x20of\x20all\x20the\x20interfaces\x20except\
+    \x20lo\n\n\r\n\x05\x04\x0b\x02\x06\x04\x12\x04h\x02f\x17\n\x0c\n\x05\x04\
+    \x0b\x02\x06\x05\x12\x03h\x02\x07\n\x0c\n\x05\x04\x0b\x02\x06\x01\x12\
+    \x03h\x08\x0e\n\x0c\n\x05\x04\x0b\x02\x06\x03\x12\x03h\x11\x12\n\x0b\n\
+    \x04\x04\x0b\x02\x07\x12\x03i\x02\x13\n\r\n\x05\x04\x0b\x02\x07\x04\x12\
+    \x04i\x02h\x13\n\x0c\n\x05\x04\x0b\x02\x07\x05\x12\x03i\x02\x07\n\x0c\n\
+    \x05\x04\x0b\x02\x07\x01\x12\x03i\x08\x0e\n\x0c\n\x05\x04\x0b\x02\x07\
+    \x03\x12\x03i\x11\x12\n\x0b\n\x04\x04\x0b\x02\x08\x12\x03j\x02\x13\n\r\n\
+    \x05\x04\x0b\x02\x08\x04\x12\x04j\x02i\x13\n\x0c\n\x05\x04\x0b\x02\x08\
+    \x05\x12\x03j\x02\x08\n\x0c\n\x05\x04\x0b\x02\x08\x01\x12\x03j\t\x0e\n\
+    \x0c\n\x05\x04\x0b\x02\x08\x03\x12\x03j\x11\x12\n\x0b\n\x04\x04\x0b\x02\
+    \t\x12\x03k\x02\x14\n\r\n\x05\x04\x0b\x02\t\x04\x12\x04k\x02j\x13\n\x0c\
+    \n\x05\x04\x0b\x02\t\x05\x12\x03k\x02\x08\n\x0c\n\x05\x04\x0b\x02\t\x01\
+    \x12\x03k\t\x0e\n\x0c\n\x05\x04\x0b\x02\t\x03\x12\x03k\x11\x13\n\x0b\n\
+    \x04\x04\x0b\x02\n\x12\x03l\x02\x15\n\r\n\x05\x04\x0b\x02\n\x04\x12\x04l\
+    \x02k\x14\n\x0c\n\x05\x04\x0b\x02\n\x05\x12\x03l\x02\x08\n\x0c\n\x05\x04\
+    \x0b\x02\n\x01\x12\x03l\t\x0f\n\x0c\n\x05\x04\x0b\x02\n\x03\x12\x03l\x12\
+    \x14\n\n\n\x02\x04\x0c\x12\x04o\0u\x01\n\n\n\x03\x04\x0c\x01\x12\x03o\
+    \x08\x16\n\x0b\n\x04\x04\x0c\x02\0\x12\x03p\x02\x11\n\r\n\x05\x04\x0c\
+    \x02\0\x04\x12\x04p\x02o\x18\n\x0c\n\x05\x04\x0c\x02\0\x05\x12\x03p\x02\
+    \x07\n\x0c\n\x05\x04\x0c\x02\0\x01\x12\x03p\x08\x0c\n\x0c\n\x05\x04\x0c\
+    \x02\0\x03\x12\x03p\x0f\x10\n\x0b\n\x04\x04\x0c\x02\x01\x12\x03q\x02\x10\
+    \n\r\n\x05\x04\x0c\x02\x01\x04\x12\x04q\x02p\x11\n\x0c\n\x05\x04\x0c\x02\
+    \x01\x05\x12\x03q\x02\x07\n\x0c\n\x05\x04\x0c\x02\x01\x01\x12\x03q\x08\
+    \x0b\n\x0c\n\x05\x04\x0c\x02\x01\x03\x12\x03q\x0e\x0f\n-\n\x04\x04\x0c\
+    \x02\x02\x12\x03s\x02\x10\x1a\x20\x20percentage,\x20100\x20for\x20a\x20f\
+    ull\x20cpu\n\n\r\n\x05\x04\x0c\x02\x02\x04\x12\x04s\x02q\x10\n\x0c\n\x05\
+    \x04\x0c\x02\x02\x05\x12\x03s\x02\x07\n\x0c\n\x05\x04\x0c\x02\x02\x01\
+    \x12\x03s\x08\x0b\n\x0c\n\x05\x04\x0c\x02\x02\x03\x12\x03s\x0e\x0f\n\x0b\
+    \n\x04\x04\x0c\x02\x03\x12\x03t\x02\x10\n\r\n\x05\x04\x0c\x02\x03\x04\
+    \x12\x04t\x02s\x10\n\x0c\n\x05\x04\x0c\x02\x03\x05\x12\x03t\x02\x07\n\
+    \x0c\n\x05\x04\x0c\x02\x03\x01\x12\x03t\x08\x0b\n\x0c\n\x05\x04\x0c\x02\
+    \x03\x03\x12\x03t\x0e\x0f\n\n\n\x02\x04\r\x12\x04w\0|\x01\n\n\n\x03\x04\
+    \r\x01\x12\x03w\x08\x15\n\x1d\n\x04\x04\r\x02\0\x12\x03y\x02\x16\x1a\x10\
+    \x20unix\x20timestamp\n\n\r\n\x05\x04\r\x02\0\x04\x12\x04y\x02w\x17\n\
+    \x0c\n\x05\x04\r\x02\0\x05\x12\x03y\x02\x07\n\x0c\n\x05\x04\r\x02\0\x01\
+    \x12\x03y\x08\x11\n\x0c\n\x05\x04\r\x02\0\x03\x12\x03y\x14\x15\n\x0b\n\
+    \x04\x04\r\x02\x01\x12\x03z\x02\x17\n\r\n\x05\x04\r\x02\x01\x04\x12\x04z\
+    \x02y\x16\n\x0c\n\x05\x04\r\x02\x01\x06\x12\x03z\x02\r\n\x0c\n\x05\x04\r\
+    \x02\x01\x01\x12\x03z\x0e\x12\n\x0c\n\x05\x04\r\x02\x01\x03\x12\x03z\x15\
+    \x16\n\x0b\n\x04\x04\r\x02\x02\x12\x03{\x02$\n\x0c\n\x05\x04\r\x02\x02\
+    \x04\x12\x03{\x02\n\n\x0c\n\x05\x04\r\x02\x02\x06\x12\x03{\x0b\x19\n\x0c\
+    \n\x05\x04\r\x02\x02\x01\x12\x03{\x1a\x1f\n\x0c\n\x05\x04\r\x02\x02\x03\
+    \x12\x03{\"#\n\x0b\n\x02\x04\x0e\x12\x05~\0\x80\x01\x01\n\n\n\x03\x04\
+    \x0e\x01\x12\x03~\x08\x16\n\x0b\n\x04\x04\x0e\x02\0\x12\x03\x7f\x02%\n\
+    \x0c\n\x05\x04\x0e\x02\0\x04\x12\x03\x7f\x02\n\n\x0c\n\x05\x04\x0e\x02\0\
+    \x06\x12\x03\x7f\x0b\x18\n\x0c\n\x05\x04\x0e\x02\0\x01\x12\x03\x7f\x19\
+    \x20\n\x0c\n\x05\x04\x0e\x02\0\x03\x12\x03\x7f#$\n\x0c\n\x02\x05\x01\x12\
+    \x06\x82\x01\0\x88\x01\x01\n\x0b\n\x03\x05\x01\x01\x12\x04\x82\x01\x05\
+    \x12\n\x0c\n\x04\x05\x01\x02\0\x12\x04\x83\x01\x02\x0e\n\r\n\x05\x05\x01\
+    \x02\0\x01\x12\x04\x83\x01\x02\t\n\r\n\x05\x05\x01\x02\0\x02\x12\x04\x83\
+    \x01\x0c\r\n\x0c\n\x04\x05\x01\x02\x01\x12\x04\x84\x01\x02\x0c\n\r\n\x05\
+    \x05\x01\x02\x01\x01\x12\x04\x84\x01\x02\x07\n\r\n\x05\x05\x01\x02\x01\
+    \x02\x12\x04\x84\x01\n\x0b\n\x0c\n\x04\x05\x01\x02\x02\x12\x04\x85\x01\
+    \x02\x0b\n\r\n\x05\x05\x01\x02\x02\x01\x12\x04\x85\x01\x02\x06\n\r\n\x05\
+    \x05\x01\x02\x02\x02\x12\x04\x85\x01\t\n\n\x0c\n\x04\x05\x01\x02\x03\x12\
+    \x04\x86\x01\x02\r\n\r\n\x05\x05\x01\x02\x03\x01\x12\x04\x86\x01\x02\x08\
+    \n\r\n\x05\x05\x01\x02\x03\x02\x12\x04\x86\x01\x0b\x0c\n\x0c\n\x04\x05\
+    \x01\x02\x04\x12\x04\x87\x01\x02\x0c\n\r\n\x05\x05\x01\x02\x04\x01\x12\
+    \x04\x87\x01\x02\x07\n\r\n\x05\x05\x01\x02\x04\x02\x12\x04\x87\x01\n\x0b\
+    \n\x0c\n\x02\x04\x0f\x12\x06\x8a\x01\0\x8f\x01\x01\n\x0b\n\x03\x04\x0f\
+    \x01\x12\x04\x8a\x01\x08\x0e\n\x0c\n\x04\x04\x0f\x02\0\x12\x04\x8b\x01\
+    \x03\x1c\n\x0f\n\x05\x04\x0f\x02\0\x04\x12\x06\x8b\x01\x03\x8a\x01\x10\n\
+    \r\n\x05\x04\x0f\x02\0\x06\x12\x04\x8b\x01\x03\x10\n\r\n\x05\x04\x0f\x02\
+    \0\x01\x12\x04\x8b\x01\x11\x17\n\r\n\x05\x04\x0f\x02\0\x03\x12\x04\x8b\
+    \x01\x1a\x1b\n\x0c\n\x04\x04\x0f\x02\x01\x12\x04\x8c\x01\x03\x1f\n\r\n\
+    \x05\x04\x0f\x02\x01\x04\x12\x04\x8c\x01\x03\x0b\n\r\n\x05\x04\x0f\x02\
+    \x01\x06\x12\x04\x8c\x01\x0c\x14\n\r\n\x05\x04\x0f\x02\x01\x01\x12\x04\
+    \x8c\x01\x15\x1a\n\r\n\x05\x04\x0f\x02\x01\x03\x12\x04\x8c\x01\x1d\x1e\n\
+    J\n\x04\x04\x0f\x02\x02\x12\x04\x8e\x01\x03\x16\x1a<\x20all\x20the\x20in\
+    stances\x20of\x20cluster\x20on\x20the\x20host\x20if\x20insts\x20is\x20em\
+    pty\n\n\x0f\n\x05\x04\x0f\x02\x02\x04\x12\x06\x8e\x01\x03\x8c\x01\x1f\n\
+    \r\n\x05\x04\x0f\x02\x02\x05\x12\x04\x8e\x01\x03\t\n\r\n\x05\x04\x0f\x02\
+    \x02\x01\x12\x04\x8e\x01\n\x11\n\r\n\x05\x04\x0f\x02\x02\x03\x12\x04\x8e\
+    \x01\x14\x15\n\x0c\n\x02\x05\x02\x12\x06\x91\x01\0\x95\x01\x01\n\x0b\n\
+    \x03\x05\x02\x01\x12\x04\x91\x01\x05\x0e\n\x0c\n\x04\x05\x02\x02\0\x12\
+    \x04\x92\x01\x02\x0c\n\r\n\x05\x05\x02\x02\0\x01\x12\x04\x92\x01\x02\x07\
+    \n\r\n\x05\x05\x02\x02\0\x02\x12\x04\x92\x01\n\x0b\n\x0c\n\x04\x05\x02\
+    \x02\x01\x12\x04\x93\x01\x02\x13\n\r\n\x05\x05\x02\x02\x01\x01\x12\x04\
+    \x93\x01\x02\x0e\n\r\n\x05\x05\x02\x02\x01\x02\x12\x04\x93\x01\x11\x12\n\
+    \x0c\n\x04\x05\x02\x02\x02\x12\x04\x94\x01\x02\x0f\n\r\n\x05\x05\x02\x02\
+    \x02\x01\x12\x04\x94\x01\x02\n\n\r\n\x05\x05\x02\x02\x02\x02\x12\x04\x94\
+    \x01\r\x0e\n\x0c\n\x02\x04\x10\x12\x06\x97\x01\0\xa1\x01\x01\n\x0b\n\x03\
+    \x04\x10\x01\x12\x04\x97\x01\x08\x11\n\x0c\n\x04\x04\x10\x02\0\x12\x04\
+    \x98\x01\x02\x14\n\x0f\n\x05\x04\x10\x02\0\x04\x12\x06\x98\x01\x02\x97\
+    \x01\x13\n\r\n\x05\x04\x10\x02\0\x05\x12\x04\x98\x01\x02\x08\n\r\n\x05\
+    \x04\x10\x02\0\x01\x12\x04\x98\x01\t\x0f\n\r\n\x05\x04\x10\x02\0\x03\x12\
+    \x04\x98\x01\x12\x13\n\x0c\n\x04\x04\x10\x02\x01\x12\x04\x99\x01\x02\x1b\
+    \n\x0f\n\x05\x04\x10\x02\x01\x04\x12\x06\x99\x01\x02\x98\x01\x14\n\r\n\
+    \x05\x04\x10\x02\x01\x06\x12\x04\x99\x01\x02\x0b\n\r\n\x05\x04\x10\x02\
+    \x01\x01\x12\x04\x99\x01\x0c\x16\n\r\n\x05\x04\x10\x02\x01\x03\x12\x04\
+    \x99\x01\x19\x1a\n\x0c\n\x04\x04\x10\x02\x02\x12\x04\x9a\x01\x02\x15\n\
+    \x0f\n\x05\x04\x10\x02\x02\x04\x12\x06\x9a\x01\x02\x99\x01\x1b\n\r\n\x05\
+    \x04\x10\x02\x02\x05\x12\x04\x9a\x01\x02\x08\n\r\n\x05\x04\x10\x02\x02\
+    \x01\x12\x04\x9a\x01\t\x10\n\r\n\x05\x04\x10\x02\x02\x03\x12\x04\x9a\x01\
+    \x13\x14\n\x0c\n\x04\x04\x10\x02\x03\x12\x04\x9b\x01\x02\x15\n\x0f\n\x05\
+    \x04\x10\x02\x03\x04\x12\x06\x9b\x01\x02\x9a\x01\x15\n\r\n\x05\x04\x10\
+    \x02\x03\x05\x12\x04\x9b\x01\x02\x08\n\r\n\x05\x04\x10\x02\x03\x01\x12\
+    \x04\x9b\x01\t\x10\n\r\n\x05\x04\x10\x02\x03\x03\x12\x04\x9b\x01\x13\x14\
+    \n\x0c\n\x04\x04\x10\x02\x04\x12\x04\x9c\x01\x02\x19\n\x0f\n\x05\x04\x10\
+    \x02\x04\x04\x12\x06\x9c\x01\x02\x9b\x01\x15\n\r\n\x05\x04\x10\x02\x04\
+    \x05\x12\x04\x9c\x01\x02\x08\n\r\n\x05\x04\x10\x02\x04\x01\x12\x04\x9c\
+    \x01\t\x14\n\r\n\x05\x04\x10\x02\x04\x03\x12\x04\x9c\x01\x17\x18\n^\n\
+    \x04\x04\x10\x02\x05\x12\x04\x9e\x01\x02\x14\x1aP\x20converge\x20the\x20\
+    instances\x20to\x20the\x20desired\x20files\x20instead\x20of\x20clean\x20\
+    and\x20redeploy\x20them\n\n\x0f\n\x05\x04\x10\x02\x05\x04\x12\x06\x9e\
+    \x01\x02\x9c\x01\x19\n\r\n\x05\x04\x10\x02\x05\x05\x12\x04\x9e\x01\x02\
+    \x06\n\r\n\x05\x04\x10\x02\x05\x01\x12\x04\x9e\x01\x07\x0f\n\r\n\x05\x04\
+    \x10\x02\x05\x03\x12\x04\x9e\x01\x12\x13\n\x0c\n\x04\x04\x10\x02\x06\x12\
+    \x04\xa0\x01\x02\x1f\n\r\n\x05\x04\x10\x02\x06\x04\x12\x04\xa0\x01\x02\n\
+    \n\r\n\x05\x04\x10\x02\x06\x06\x12\x04\xa0\x01\x0b\x13\n\r\n\x05\x04\x10\
+    \x02\x06\x01\x12\x04\xa0\x01\x14\x19\n\r\n\x05\x04\x10\x02\x06\x03\x12\
+    \x04\xa0\x01\x1c\x1e\n\x0c\n\x02\x04\x11\x12\x06\xa3\x01\0\xa6\x01\x01\n\
+    \x0b\n\x03\x04\x11\x01\x12\x04\xa3\x01\x08\x10\n\x0c\n\x04\x04\x11\x02\0\
+    \x12\x04\xa4\x01\x02\x11\n\x0f\n\x05\x04\x11\x02\0\x04\x12\x06\xa4\x01\
+    \x02\xa3\x01\x12\n\r\n\x05\x04\x11\x02\0\x05\x12\x04\xa4\x01\x02\x07\n\r\
+    \n\x05\x04\x11\x02\0\x01\x12\x04\xa4\x01\x08\x0c\n\r\n\x05\x04\x11\x02\0\
+    \x03\x12\x04\xa4\x01\x0f\x10\n\x0c\n\x04\x04\x11\x02\x01\x12\x04\xa5\x01\
+    \x02\x1a\n\r\n\x05\x04\x11\x02\x01\x04\x12\x04\xa5\x01\x02\n\n\r\n\x05\
+    \x04\x11\x02\x01\x06\x12\x04\xa5\x01\x0b\x0f\n\r\n\x05\x04\x11\x02\x01\
+    \x01\x12\x04\xa5\x01\x10\x15\n\r\n\x05\x04\x11\x02\x01\x03\x12\x04\xa5\
+    \x01\x18\x19\n\x0c\n\x02\x04\x12\x12\x06\xa8\x01\0\xab\x01\x01\n\x0b\n\
+    \x03\x04\x12\x01\x12\x04\xa8\x01\x08\x0c\n\x0c\n\x04\x04\x12\x02\0\x12\
+    \x04\xa9\x01\x02\x13\n\x0f\n\x05\x04\x12\x02\0\x04\x12\x06\xa9\x01\x02\
+    \xa8\x01\x0e\n\r\n\x05\x04\x12\x02\0\x05\x12\x04\xa9\x01\x02\x08\n\r\n\
+    \x05\x04\x12\x02\0\x01\x12\x04\xa9\x01\t\x0e\n\r\n\x05\x04\x12\x02\0\x03\
+    \x12\x04\xa9\x01\x11\x12\n\x0c\n\x04\x04\x12\x02\x01\x12\x04\xaa\x01\x02\
+    \x15\n\x0f\n\x05\x04\x12\x02\x01\x04\x12\x06\xaa\x01\x02\xa9\x01\x13\n\r\
+    \n\x05\x04\x12\x02\x01\x05\x12\x04\xaa\x01\x02\x08\n\r\n\x05\x04\x12\x02\
+    \x01\x01\x12\x04\xaa\x01\t\x10\n\r\n\x05\x04\x12\x02\x01\x03\x12\x04\xaa\
+    \x01\x13\x14\n\x0c\n\x02\x05\x03\x12\x06\xad\x01\0\xb1\x01\x01\n\x0b\n\
+    \x03\x05\x03\x01\x12\x04\xad\x01\x05\n\n\x0c\n\x04\x05\x03\x02\0\x12\x04\
+    \xae\x01\x02\x0b\n\r\n\x05\x05\x03\x02\0\x01\x12\x04\xae\x01\x02\x06\n\r\
+    \n\x05\x05\x03\x02\0\x02\x12\x04\xae\x01\t\n\n\x0c\n\x04\x05\x03\x02\x01\
+    \x12\x04\xaf\x01\x02\x0b\n\r\n\x05\x05\x03\x02\x01\x01\x12\x04\xaf\x01\
+    \x02\x06\n\r\n\x05\x05\x03\x02\x01\x02\x12\x04\xaf\x01\t\n\n\x0c\n\x04\
+    \x05\x03\x02\x02\x12\x04\xb0\x01\x02\x0c\n\r\n\x05\x05\x03\x02\x02\x01\
+    \x12\x04\xb0\x01\x02\x07\n\r\n\x05\x05\x03\x02\x02\x02\x12\x04\xb0\x01\n\
+    \x0b\n\x0c\n\x02\x05\x04\x12\x06\xb3\x01\0\xb7\x01\x01\n\x0b\n\x03\x05\
+    \x04\x01\x12\x04\xb3\x01\x05\x0f\n\x0c\n\x04\x05\x04\x02\0\x12\x04\xb4\
+    \x01\x02\x10\n\r\n\x05\x05\x04\x02\0\x01\x12\x04\xb4\x01\x02\x0b\n\r\n\
+    \x05\x05\x04\x02\0\x02\x12\x04\xb4\x01\x0e\x0f\n\x0c\n\x04\x05\x04\x02\
+    \x01\x12\x04\xb5\x01\x02\x0e\n\r\n\x05\x05\x04\x02\x01\x01\x12\x04\xb5\
+    \x01\x02\t\n\r\n\x05\x05\x04\x02\x01\x02\x12\x04\xb5\x01\x0c\r\n\x0c\n\
+    \x04\x05\x04\x02\x02\x12\x04\xb6\x01\x02\x0e\n\r\n\x05\x05\x04\x02\x02\
+    \x01\x12\x04\xb6\x01\x02\t\n\r\n\x05\x05\x04\x02\x02\x02\x12\x04\xb6\x01\
+    \x0c\r\n\x0c\n\x02\x04\x13\x12\x06\xb9\x01\0\xbf\x01\x01\n\x0b\n\x03\x04\
+    \x13\x01\x12\x04\xb9\x01\x08\x16\n\x0c\n\x04\x04\x13\x02\0\x12\x04\xba\
+    \x01\x02\x11\n\x0f\n\x05\x04\x13\x02\0\x04\x12\x06\xba\x01\x02\xb9\x01\
+    \x18\n\r\n\x05\x04\x13\x02\0\x05\x12\x04\xba\x01\x02\x07\n\r\n\x05\x04\
+    \x13\x02\0\x01\x12\x04\xba\x01\x08\x0c\n\r\n\x05\x04\x13\x02\0\x03\x12\
+    \x04\xba\x01\x0f\x10\n\x0c\n\x04\x04\x13\x02\x01\x12\x04\xbb\x01\x02\x16\
+    \n\x0f\n\x05\x04\x13\x02\x01\x04\x12\x06\xbb\x01\x02\xba\x01\x11\n\r\n\
+    \x05\x04\x13\x02\x01\x06\x12\x04\xbb\x01\x02\x0c\n\r\n\x05\x04\x13\x02\
+    \x01\x01\x12\x04\xbb\x01\r\x11\n\r\n\x05\x04\x13\x02\x01\x03\x12\x04\xbb\
+    \x01\x14\x15\n*\n\x04\x04\x13\x02\x02\x12\x04\xbd\x01\x02\x1c\x1a\x1c\
+    \x20files\x20which\x20were\x20rewritten\n\n\r\n\x05\x04\x13\x02\x02\x04\
+    \x12\x04\xbd\x01\x02\n\n\r\n\x05\x04\x13\x02\x02\x05\x12\x04\xbd\x01\x0b\
+    \x11\n\r\n\x05\x04\x13\x02\x02\x01\x12\x04\xbd\x01\x12\x17\n\r\n\x05\x04\
+    \x13\x02\x02\x03\x12\x04\xbd\x01\x1a\x1b\n\x0c\n\x04\x04\x13\x02\x03\x12\
+    \x04\xbe\x01\x02\x15\n\x0f\n\x05\x04\x13\x02\x03\x04\x12\x06\xbe\x01\x02\
+    \xbd\x01\x1c\n\r\n\x05\x04\x13\x02\x03\x05\x12\x04\xbe\x01\x02\x06\n\r\n\
+    \x05\x04\x13\x02\x03\x01\x12\x04\xbe\x01\x07\x10\n\r\n\x05\x04\x13\x02\
+    \x03\x03\x12\x04\xbe\x01\x13\x14\n\x0c\n\x02\x04\x14\x12\x06\xc1\x01\0\
+    \xc5\x01\x01\n\x0b\n\x03\x04\x14\x01\x12\x04\xc1\x01\x08\x12\n\x0c\n\x04\
+    \x04\x14\x02\0\x12\x04\xc2\x01\x02\x12\n\x0f\n\x05\x04\x14\x02\0\x04\x12\
+    \x06\xc2\x01\x02\xc1\x01\x14\n\r\n\x05\x04\x14\x02\0\x06\x12\x04\xc2\x01\
+    \x02\x07\n\r\n\x05\x04\x14\x02\0\x01\x12\x04\xc2\x01\x08\r\n\r\n\x05\x04\
+    \x14\x02\0\x03\x12\x04\xc2\x01\x10\x11\n\x0c\n\x04\x04\x14\x02\x01\x12\
+    \x04\xc3\x01\x02\x11\n\x0f\n\x05\x04\x14\x02\x01\x04\x12\x06\xc3\x01\x02\
+    \xc2\x01\x12\n\r\n\x05\x04\x14\x02\x01\x05\x12\x04\xc3\x01\x02\x08\n\r\n\
+    \x05\x04\x14\x02\x01\x01\x12\x04\xc3\x01\t\x0c\n\r\n\x05\x04\x14\x02\x01\
+    \x03\x12\x04\xc3\x01\x0f\x10\n\x0c\n\x04\x04\x14\x02\x02\x12\x04\xc4\x01\
+    \x02&\n\r\n\x05\x04\x14\x02\x02\x04\x12\x04\xc4\x01\x02\n\n\r\n\x05\x04\
+    \x14\x02\x02\x06\x12\x04\xc4\x01\x0b\x19\n\r\n\x05\x04\x14\x02\x02\x01\
+    \x12\x04\xc4\x01\x1a!\n\r\n\x05\x04\x14\x02\x02\x03\x12\x04\xc4\x01$%b\
+    \x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -39,6 +39,41 @@ const METHOD_AGENT_GET_PORTS: ::grpcio::Method<super::agent::PortAcquire, super:
     resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
 };
 
+const METHOD_AGENT_LIST_BINARIES: ::grpcio::Method<super::agent::BinaryQuery, super::agent::Binaries> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/agent.Agent/ListBinaries",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_AGENT_PURGE_BINARIES: ::grpcio::Method<super::agent::BinaryPurge, super::agent::Binaries> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/agent.Agent/PurgeBinaries",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_AGENT_RESTORE: ::grpcio::Method<super::agent::RestoreRequest, super::agent::CacheState> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/agent.Agent/Restore",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_AGENT_STATUS: ::grpcio::Method<super::agent::StatusQuery, super::agent::InstanceStatuses> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/agent.Agent/Status",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_AGENT_METRICS: ::grpcio::Method<super::agent::MetricsQuery, super::agent::MetricsSamples> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/agent.Agent/Metrics",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
 #[derive(Clone)]
 pub struct AgentClient {
     client: ::grpcio::Client,
@@ -98,6 +133,86 @@ impl AgentClient {
     pub fn get_ports_async(&self, req: &super::agent::PortAcquire) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::Ports>> {
         self.get_ports_async_opt(req, ::grpcio::CallOption::default())
     }
+
+    pub fn list_binaries_opt(&self, req: &super::agent::BinaryQuery, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::agent::Binaries> {
+        self.client.unary_call(&METHOD_AGENT_LIST_BINARIES, req, opt)
+    }
+
+    pub fn list_binaries(&self, req: &super::agent::BinaryQuery) -> ::grpcio::Result<super::agent::Binaries> {
+        self.list_binaries_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn list_binaries_async_opt(&self, req: &super::agent::BinaryQuery, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::Binaries>> {
+        self.client.unary_call_async(&METHOD_AGENT_LIST_BINARIES, req, opt)
+    }
+
+    pub fn list_binaries_async(&self, req: &super::agent::BinaryQuery) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::Binaries>> {
+        self.list_binaries_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn purge_binaries_opt(&self, req: &super::agent::BinaryPurge, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::agent::Binaries> {
+        self.client.unary_call(&METHOD_AGENT_PURGE_BINARIES, req, opt)
+    }
+
+    pub fn purge_binaries(&self, req: &super::agent::BinaryPurge) -> ::grpcio::Result<super::agent::Binaries> {
+        self.purge_binaries_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn purge_binaries_async_opt(&self, req: &super::agent::BinaryPurge, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::Binaries>> {
+        self.client.unary_call_async(&METHOD_AGENT_PURGE_BINARIES, req, opt)
+    }
+
+    pub fn purge_binaries_async(&self, req: &super::agent::BinaryPurge) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::Binaries>> {
+        self.purge_binaries_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn restore_opt(&self, req: &super::agent::RestoreRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::agent::CacheState> {
+        self.client.unary_call(&METHOD_AGENT_RESTORE, req, opt)
+    }
+
+    pub fn restore(&self, req: &super::agent::RestoreRequest) -> ::grpcio::Result<super::agent::CacheState> {
+        self.restore_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn restore_async_opt(&self, req: &super::agent::RestoreRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::CacheState>> {
+        self.client.unary_call_async(&METHOD_AGENT_RESTORE, req, opt)
+    }
+
+    pub fn restore_async(&self, req: &super::agent::RestoreRequest) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::CacheState>> {
+        self.restore_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn status_opt(&self, req: &super::agent::StatusQuery, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::agent::InstanceStatuses> {
+        self.client.unary_call(&METHOD_AGENT_STATUS, req, opt)
+    }
+
+    pub fn status(&self, req: &super::agent::StatusQuery) -> ::grpcio::Result<super::agent::InstanceStatuses> {
+        self.status_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn status_async_opt(&self, req: &super::agent::StatusQuery, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::InstanceStatuses>> {
+        self.client.unary_call_async(&METHOD_AGENT_STATUS, req, opt)
+    }
+
+    pub fn status_async(&self, req: &super::agent::StatusQuery) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::InstanceStatuses>> {
+        self.status_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn metrics_opt(&self, req: &super::agent::MetricsQuery, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::agent::MetricsSamples> {
+        self.client.unary_call(&METHOD_AGENT_METRICS, req, opt)
+    }
+
+    pub fn metrics(&self, req: &super::agent::MetricsQuery) -> ::grpcio::Result<super::agent::MetricsSamples> {
+        self.metrics_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn metrics_async_opt(&self, req: &super::agent::MetricsQuery, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::MetricsSamples>> {
+        self.client.unary_call_async(&METHOD_AGENT_METRICS, req, opt)
+    }
+
+    pub fn metrics_async(&self, req: &super::agent::MetricsQuery) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::agent::MetricsSamples>> {
+        self.metrics_async_opt(req, ::grpcio::CallOption::default())
+    }
     pub fn spawn<F>(&self, f: F) where F: ::futures::Future<Item = (), Error = ()> + Send + 'static {
         self.client.spawn(f)
     }
@@ -107,6 +222,11 @@ pub trait Agent {
     fn deploy(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::CacheInfo, sink: ::grpcio::UnarySink<super::agent::CacheState>);
     fn do_action(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::Action, sink: ::grpcio::UnarySink<super::agent::CacheState>);
     fn get_ports(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::PortAcquire, sink: ::grpcio::UnarySink<super::agent::Ports>);
+    fn list_binaries(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::BinaryQuery, sink: ::grpcio::UnarySink<super::agent::Binaries>);
+    fn purge_binaries(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::BinaryPurge, sink: ::grpcio::UnarySink<super::agent::Binaries>);
+    fn restore(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::RestoreRequest, sink: ::grpcio::UnarySink<super::agent::CacheState>);
+    fn status(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::StatusQuery, sink: ::grpcio::UnarySink<super::agent::InstanceStatuses>);
+    fn metrics(&mut self, ctx: ::grpcio::RpcContext, req: super::agent::MetricsQuery, sink: ::grpcio::UnarySink<super::agent::MetricsSamples>);
 }
 
 pub fn create_agent<S: Agent + Send + Clone + 'static>(s: S) -> ::grpcio::Service {
@@ -123,5 +243,25 @@ pub fn create_agent<S: Agent + Send + Clone + 'static>(s: S) -> ::grpcio::Servic
     builder = builder.add_unary_handler(&METHOD_AGENT_GET_PORTS, move |ctx, req, resp| {
         instance.get_ports(ctx, req, resp)
     });
+    let mut instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_AGENT_LIST_BINARIES, move |ctx, req, resp| {
+        instance.list_binaries(ctx, req, resp)
+    });
+    let mut instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_AGENT_PURGE_BINARIES, move |ctx, req, resp| {
+        instance.purge_binaries(ctx, req, resp)
+    });
+    let mut instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_AGENT_RESTORE, move |ctx, req, resp| {
+        instance.restore(ctx, req, resp)
+    });
+    let mut instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_AGENT_STATUS, move |ctx, req, resp| {
+        instance.status(ctx, req, resp)
+    });
+    let mut instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_AGENT_METRICS, move |ctx, req, resp| {
+        instance.metrics(ctx, req, resp)
+    });
     builder.build()
 }
@@ -0,0 +1,518 @@
+//! the reconciler of leader, which keeps the clusters as their specs every interval:
+//!   * the cluster is deployed if it's missing
+//!   * the configs and appids are written again if they are out of sync
+//!   * the inactive or failed instances are restored by their agents
+//!   * the redis clusters are upgraded by rolling if the version is changed
+//!   * the configs of redis clusters are changed by rolling if the template is changed
+//!   * the other drifts like the number of instances are only flagged in the status of spec
+//!
+//! the missing instances of an existing cluster are not created, e.g. the ones removed by api.
+//! the leader can't add instances into a cluster since it has no resharding, so they must be
+//! deployed again by removing the cluster.
+//!
+//! the fixes run as jobs, and a spec is skipped until it's last job is finished or while
+//! the cluster is being deployed, upgraded or configured by someone else. a failed job is not
+//! retried until the spec is saved as a new revision, and the instances which were stopped by
+//! operator are never restored.
+
+use crate::deploy::plan::{cache_type_name, EtcdWrite};
+use crate::deploy::server::{config_writes, connect_agent, STATE_STOPPED};
+use crate::exporter::SPEC_DRIFTS;
+use crate::job::{
+    cluster_lock_holder, gen_job_id, spawn_deploy, spawn_job, ClusterBusy, DeployConflict,
+    DeployStart, Job, JobState,
+};
+use crate::myetcd::MyEtcd;
+use crate::proto::{CacheType, RestoreRequest, State, StatusQuery, UnitState};
+use crate::reconfig::spawn_reconfig;
+use crate::spec::{list_specs, load_status, save_status, ClusterSpec, SpecStatus};
+use crate::stats::cluster_instances;
+use crate::upgrade::spawn_upgrade;
+
+use failure::{format_err, Error};
+use log::{info, warn};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::{self, Duration, SystemTime};
+
+// what is out of sync and can be fixed by a job
+#[derive(Default)]
+struct Fixes {
+    // host -> ports
+    down: BTreeMap<String, Vec<i64>>,
+    // the version to upgrade to
+    upgrade: Option<String>,
+    // the template to change the configs by
+    reconfig: Option<String>,
+    writes: Vec<EtcdWrite>,
+    removed_appids: Vec<String>,
+}
+
+/// reconcile all the specs every interval seconds in background.
+pub fn spawn_reconcile(etcd: &str, interval: u64) {
+    let etcd = etcd.to_string();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(interval.max(1)));
+        let rslt =
+            catch_panic(|| MyEtcd::open(&etcd).and_then(|myetcd| reconcile_all(&myetcd, &etcd)));
+        if let Err(err) = rslt {
+            warn!("fail to reconcile specs due {}", err);
+        }
+    });
+}
+
+fn reconcile_all(myetcd: &MyEtcd, etcd: &str) -> Result<(), Error> {
+    let specs = list_specs(myetcd)?;
+    SPEC_DRIFTS.reset();
+    for spec in &specs {
+        // a spec which panics must not stop the others from being reconciled
+        match catch_panic(|| reconcile(myetcd, etcd, spec)) {
+            Ok(drifts) => SPEC_DRIFTS
+                .with_label_values(&[&spec.name])
+                .set(drifts as i64),
+            Err(err) => warn!("fail to reconcile spec {} due {}", spec.name, err),
+        }
+    }
+    info!("reconcile {} specs done", specs.len());
+    Ok(())
+}
+
+// reconcile the cluster of spec and return the number of drifts.
+fn reconcile(myetcd: &MyEtcd, etcd: &str, spec: &ClusterSpec) -> Result<usize, Error> {
+    let mut status = load_status(myetcd, &spec.name)?.unwrap_or_default();
+    let mut failed = None;
+    if let Some(id) = &status.last_job {
+        if let Some(job) = Job::load(myetcd, id)? {
+            if job.state == JobState::Running {
+                info!(
+                    "skip spec {} due job {} is still running",
+                    spec.name, job.id
+                );
+                return Ok(status.drifts.len());
+            }
+            // the revision of status is the one which started the job
+            if job.state == JobState::Error && status.revision == spec.revision {
+                failed = Some(job);
+            }
+        }
+    }
+
+    if let Some(holder) = cluster_lock_holder(myetcd, &spec.name)? {
+        info!(
+            "skip spec {} due it's cluster is locked by {}",
+            spec.name, holder
+        );
+        return Ok(status.drifts.len());
+    }
+
+    status.revision = spec.revision;
+    status.drifts.clear();
+    let cache_type = myetcd.get_value(&format!("/haste/clusters/{}/cache_type", spec.name))?;
+    match cache_type {
+        None => {
+            status.drifts.push("cluster is missing".to_string());
+            match failed {
+                Some(job) => status.drifts.push(not_retried(&job)),
+                None => deploy_missing(etcd, spec, &mut status),
+            }
+        }
+        Some(ref cache_type) if cache_type != cache_type_name(spec.cache_type) => {
+            // a cluster can't be changed into another type
+            status.drifts.push(format!(
+                "cache_type is {} but spec is {}",
+                cache_type,
+                cache_type_name(spec.cache_type)
+            ));
+        }
+        Some(_) => {
+            let fixes = find_drifts(myetcd, spec, &mut status.drifts)?;
+            match failed {
+                Some(job) if !status.drifts.is_empty() => status.drifts.push(not_retried(&job)),
+                Some(_) => {}
+                None => {
+                    if let Some(job) = spawn_fixes(myetcd, etcd, spec, fixes)? {
+                        status.last_job = Some(job.id);
+                    }
+                }
+            }
+        }
+    }
+
+    if !status.drifts.is_empty() {
+        info!("spec {} has drifts {:?}", spec.name, status.drifts);
+    }
+    status.checked_at = now();
+    save_status(myetcd, &spec.name, &status)?;
+    Ok(status.drifts.len())
+}
+
+fn deploy_missing(etcd: &str, spec: &ClusterSpec, status: &mut SpecStatus) {
+    match spawn_deploy(etcd, &spec.to_param(), false) {
+        Ok(DeployStart::Started(job)) => status.last_job = Some(job.id),
+        Ok(DeployStart::Planned(_)) => {}
+        // someone is deploying it by api
+        Err(ref err)
+            if err.downcast_ref::<DeployConflict>().is_some()
+                || err.downcast_ref::<ClusterBusy>().is_some() => {}
+        Err(err) => status.drifts.push(format!("fail to deploy due {}", err)),
+    }
+}
+
+fn not_retried(job: &Job) -> String {
+    format!(
+        "{} job {} failed due {}, it's not retried until the spec is changed",
+        job.kind, job.id, job.msg
+    )
+}
+
+// the cluster in etcd which is compared with spec.
+#[derive(Default)]
+struct ClusterState {
+    version: String,
+    template: String,
+    // the current values of the config keys of spec, missing if they are not set
+    configs: HashMap<String, String>,
+    appids: Vec<String>,
+    addrs: Vec<String>,
+}
+
+impl ClusterState {
+    fn load(myetcd: &MyEtcd, spec: &ClusterSpec) -> Result<ClusterState, Error> {
+        let base = format!("/haste/clusters/{}", spec.name);
+        let get = |key: &str| -> Result<String, Error> {
+            Ok(myetcd
+                .get_value(&format!("{}/{}", base, key))?
+                .unwrap_or_default())
+        };
+        let mut configs = HashMap::new();
+        for write in config_writes(&spec.to_param()) {
+            if let Some(value) = myetcd.get_value(&write.key)? {
+                configs.insert(write.key, value);
+            }
+        }
+        Ok(ClusterState {
+            version: get("version")?,
+            template: get("template")?,
+            configs,
+            appids: myetcd
+                .list_keys(&format!("{}/appids", base))?
+                .iter()
+                .map(|key| key.rsplit('/').next().unwrap_or("").to_string())
+                .collect(),
+            addrs: cluster_instances(myetcd, &spec.name)?,
+        })
+    }
+}
+
+// compare the cluster with spec, the drifts are pushed into drifts.
+fn compare(spec: &ClusterSpec, cluster: &ClusterState, drifts: &mut Vec<String>) -> Fixes {
+    let mut fixes = Fixes::default();
+    if cluster.version != spec.version {
+        drifts.push(format!(
+            "version is {} but spec is {}",
+            cluster.version, spec.version
+        ));
+        if spec.cache_type == CacheType::RedisCluster {
+            fixes.upgrade = Some(spec.version.clone());
+        }
+    }
+    if cluster.template != spec.tpl_name {
+        drifts.push(format!(
+            "template is {} but spec is {}",
+            cluster.template, spec.tpl_name
+        ));
+        if spec.cache_type != CacheType::Memcache {
+            fixes.reconfig = Some(spec.tpl_name.clone());
+        }
+    }
+
+    for write in config_writes(&spec.to_param()) {
+        if cluster.configs.get(&write.key) != Some(&write.value) {
+            fixes.writes.push(write);
+        }
+    }
+    if !fixes.writes.is_empty() {
+        drifts.push(format!("{} configs are out of sync", fixes.writes.len()));
+    }
+    let appids: HashSet<_> = spec.appids.iter().map(|x| x.trim()).collect();
+    for appid in &cluster.appids {
+        if !appids.contains(appid.as_str()) {
+            drifts.push(format!("appid {} is not in spec", appid));
+            fixes.removed_appids.push(appid.clone());
+        }
+    }
+
+    // only flagged, see the module doc
+    if cluster.addrs.len() != spec.instances() {
+        drifts.push(format!(
+            "{} instances but spec is {}",
+            cluster.addrs.len(),
+            spec.instances()
+        ));
+    }
+    fixes
+}
+
+// compare the cluster and the units of it's instances with spec, the drifts are pushed into
+// drifts.
+fn find_drifts(
+    myetcd: &MyEtcd,
+    spec: &ClusterSpec,
+    drifts: &mut Vec<String>,
+) -> Result<Fixes, Error> {
+    let base = format!("/haste/clusters/{}", spec.name);
+    let cluster = ClusterState::load(myetcd, spec)?;
+    let mut fixes = compare(spec, &cluster, drifts);
+
+    let addrs = cluster.addrs;
+    let mut hosts = HashSet::new();
+    for addr in &addrs {
+        if let Some(pos) = addr.rfind(':') {
+            hosts.insert(addr[..pos].to_string());
+        }
+    }
+    for host in hosts {
+        let mut query = StatusQuery::new();
+        query.set_cluster(spec.name.clone());
+        let statuses = match connect_agent(myetcd, &host).and_then(|x| Ok(x.status(&query)?)) {
+            Ok(statuses) => statuses,
+            Err(err) => {
+                drifts.push(format!("agent of {} is unreachable due {}", host, err));
+                continue;
+            }
+        };
+        for status in statuses.get_statuses() {
+            match status.get_state() {
+                UnitState::Inactive
+                    if stopped_by_operator(myetcd, &base, &host, status.get_port())? =>
+                {
+                    info!(
+                        "skip {}:{} of cluster {} due it was stopped by operator",
+                        host,
+                        status.get_port(),
+                        spec.name
+                    );
+                }
+                UnitState::Inactive | UnitState::Failed => {
+                    drifts.push(format!(
+                        "{}:{} is {:?}",
+                        host,
+                        status.get_port(),
+                        status.get_state()
+                    ));
+                    fixes
+                        .down
+                        .entry(host.clone())
+                        .or_default()
+                        .push(status.get_port());
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(fixes)
+}
+
+// the instances stopped by `hastectl instances stop` are marked in their state until they
+// are started again.
+fn stopped_by_operator(myetcd: &MyEtcd, base: &str, host: &str, port: i64) -> Result<bool, Error> {
+    let state = myetcd.get_value(&format!("{}/instances/{}:{}/state", base, host, port))?;
+    Ok(state.as_ref().map(|x| x.as_str()) == Some(STATE_STOPPED))
+}
+
+// start a job to fix the drifts, restoring the instances goes first since the upgrade and
+// configs are useless without instances. it's `None` if there is nothing to fix.
+fn spawn_fixes(
+    myetcd: &MyEtcd,
+    etcd: &str,
+    spec: &ClusterSpec,
+    fixes: Fixes,
+) -> Result<Option<Job>, Error> {
+    let etcd_addr = etcd.to_string();
+    let name = spec.name.clone();
+    if !fixes.down.is_empty() {
+        let job = Job::new(&gen_job_id(&name), "restore", &name);
+        let job = spawn_job(myetcd, etcd, job, move || {
+            let myetcd = MyEtcd::open(&etcd_addr)?;
+            for (host, ports) in &fixes.down {
+                let mut req = RestoreRequest::new();
+                req.set_ports(ports.clone());
+                let state = connect_agent(&myetcd, host)?.restore(&req)?;
+                if state.get_state() == State::Error {
+                    return Err(format_err!(
+                        "fail to restore {:?} on {} due {}",
+                        ports,
+                        host,
+                        state.get_msg()
+                    ));
+                }
+            }
+            Ok(())
+        })?;
+        return Ok(Some(job));
+    }
+
+    if let Some(version) = &fixes.upgrade {
+        match spawn_upgrade(etcd, &name, version) {
+            Ok(job) => return Ok(Some(job)),
+            Err(err) => warn!("fail to upgrade cluster {} due {}", name, err),
+        }
+    }
+    if let Some(tpl_name) = &fixes.reconfig {
+        match spawn_reconfig(etcd, &name, Some(tpl_name)) {
+            Ok(job) => return Ok(Some(job)),
+            Err(err) => warn!("fail to change configs of cluster {} due {}", name, err),
+        }
+    }
+
+    if fixes.writes.is_empty() && fixes.removed_appids.is_empty() {
+        return Ok(None);
+    }
+    let job = Job::new(&gen_job_id(&name), "config", &name);
+    let job = spawn_job(myetcd, etcd, job, move || {
+        let myetcd = MyEtcd::open(&etcd_addr)?;
+        for write in &fixes.writes {
+            myetcd.set(&write.key, &write.value)?;
+        }
+        for appid in &fixes.removed_appids {
+            myetcd.delete_dir(&format!("/haste/clusters/{}/appids/{}", name, appid))?;
+            myetcd.delete_dir(&format!("/haste/appids/{}/{}", appid, name))?;
+        }
+        Ok(())
+    })?;
+    Ok(Some(job))
+}
+
+// turn the panic of f into an error, the message is already printed by the panic hook.
+fn catch_panic<T, F>(f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error>,
+{
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err(format_err!("reconcile panicked")))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(cache_type: CacheType) -> ClusterSpec {
+        ClusterSpec {
+            name: "session".to_string(),
+            cache_type,
+            version: "4.0.11".to_string(),
+            tpl_name: "default".to_string(),
+            cpu_percent: 100,
+            max_memory: 4096,
+            replicas: 2,
+            appids: vec!["account.session".to_string()],
+            group: String::new(),
+            dial_timeout: Some(1000),
+            read_timeout: None,
+            write_timeout: None,
+            revision: 1,
+            updated_at: 0,
+        }
+    }
+
+    // the cluster which is synced with spec
+    fn synced(spec: &ClusterSpec) -> ClusterState {
+        ClusterState {
+            version: spec.version.clone(),
+            template: spec.tpl_name.clone(),
+            configs: config_writes(&spec.to_param())
+                .into_iter()
+                .map(|x| (x.key, x.value))
+                .collect(),
+            appids: spec.appids.clone(),
+            addrs: vec!["10.0.0.1:7000".to_string(), "10.0.0.2:7000".to_string()],
+        }
+    }
+
+    fn is_empty(fixes: &Fixes) -> bool {
+        fixes.down.is_empty()
+            && fixes.upgrade.is_none()
+            && fixes.reconfig.is_none()
+            && fixes.writes.is_empty()
+            && fixes.removed_appids.is_empty()
+    }
+
+    #[test]
+    fn nothing_to_fix() {
+        let spec = spec(CacheType::RedisCluster);
+        let mut drifts = Vec::new();
+        assert!(is_empty(&compare(&spec, &synced(&spec), &mut drifts)));
+        assert!(drifts.is_empty());
+    }
+
+    #[test]
+    fn upgrade_and_reconfig_redis_cluster() {
+        let spec = spec(CacheType::RedisCluster);
+        let mut cluster = synced(&spec);
+        cluster.version = "4.0.10".to_string();
+        cluster.template = "small".to_string();
+        let mut drifts = Vec::new();
+        let fixes = compare(&spec, &cluster, &mut drifts);
+        assert_eq!(fixes.upgrade.as_deref(), Some("4.0.11"));
+        assert_eq!(fixes.reconfig.as_deref(), Some("default"));
+        assert_eq!(
+            drifts,
+            vec![
+                "version is 4.0.10 but spec is 4.0.11",
+                "template is small but spec is default"
+            ]
+        );
+    }
+
+    #[test]
+    fn only_flag_unsupported_fixes() {
+        // only redis clusters are upgraded and memcache has no redis.conf
+        let spec = spec(CacheType::Memcache);
+        let mut cluster = synced(&spec);
+        cluster.version = "1.5.21".to_string();
+        cluster.template = "small".to_string();
+        let mut drifts = Vec::new();
+        assert!(is_empty(&compare(&spec, &cluster, &mut drifts)));
+        assert_eq!(drifts.len(), 2);
+    }
+
+    #[test]
+    fn write_configs_and_remove_appids() {
+        let spec = spec(CacheType::Redis);
+        let mut cluster = synced(&spec);
+        let key = cluster.configs.keys().next().cloned().unwrap();
+        cluster.configs.remove(&key);
+        cluster.appids.push("account.old".to_string());
+        let mut drifts = Vec::new();
+        let fixes = compare(&spec, &cluster, &mut drifts);
+        assert_eq!(fixes.writes.len(), 1);
+        assert_eq!(fixes.writes[0].key, key);
+        assert_eq!(fixes.removed_appids, vec!["account.old"]);
+        assert_eq!(
+            drifts,
+            vec![
+                "1 configs are out of sync",
+                "appid account.old is not in spec"
+            ]
+        );
+    }
+
+    #[test]
+    fn only_flag_missing_instances() {
+        let spec = spec(CacheType::RedisCluster);
+        let mut cluster = synced(&spec);
+        cluster.addrs.pop();
+        let mut drifts = Vec::new();
+        assert!(is_empty(&compare(&spec, &cluster, &mut drifts)));
+        assert_eq!(drifts, vec!["1 instances but spec is 2"]);
+    }
+}
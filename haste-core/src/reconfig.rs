@@ -0,0 +1,416 @@
+//! rolling config change of a redis cluster by the template:
+//!   1. render the redis.conf of each instance and compare it's directives with `CONFIG GET`
+//!   2. apply the hot directives by `CONFIG SET` and `CONFIG REWRITE` them into the file, an
+//!      instance which refuses any of them is restarted as the next step
+//!   3. converge the instances which have any restart directive with the rendered files, so
+//!      the agents restart them, one host in each round
+//!   4. save the template of cluster
+//!
+//! the directives unknown to `CONFIG GET` like `rename-command` can't be compared and are
+//! skipped. the cluster must be healthy after each round, or the change stops with the
+//! problems.
+
+use crate::deploy::plan::parse_cache_type;
+use crate::deploy::server::{connect_agent, DeployParm, Template, FILE_SERVER_KEY};
+use crate::job::{gen_job_id, spawn_job, ClusterLock, Job};
+use crate::layout::InstanceLayout;
+use crate::myetcd::MyEtcd;
+use crate::myredis::MyRedis;
+use crate::offer::get_capacity;
+use crate::proto::{CacheInfo, CacheType, File, Instance, State};
+use crate::stats::{cluster_instances, wait_healthy};
+use crate::upgrade::{split_addr, HEALTH_TIMEOUT};
+
+use failure::{format_err, Error, Fail};
+use log::{info, warn};
+use serde_derive::Serialize;
+
+use std::collections::BTreeMap;
+use std::slice;
+
+// directives which can't be changed by `CONFIG SET`, or are not safe to change at runtime
+const RESTART_DIRECTIVES: &[&str] = &[
+    "bind",
+    "port",
+    "daemonize",
+    "supervised",
+    "pidfile",
+    "logfile",
+    "syslog-enabled",
+    "syslog-ident",
+    "unixsocket",
+    "unixsocketperm",
+    "databases",
+    "dir",
+    "tcp-backlog",
+    "cluster-enabled",
+    "cluster-config-file",
+];
+
+#[derive(Debug, Fail)]
+pub enum ReconfigError {
+    #[fail(display = "cluster {} is not found", _0)]
+    NotFound(String),
+    #[fail(display = "cluster {} is memcache, which has no redis.conf", _0)]
+    Unsupported(String),
+    #[fail(display = "bad template due {}", _0)]
+    BadTemplate(String),
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DirectiveChange {
+    pub name: String,
+    // empty if the directive is not set
+    pub current: String,
+    pub desired: String,
+    // can be applied by `CONFIG SET`
+    pub hot: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InstanceReconfig {
+    pub addr: String,
+    pub changes: Vec<DirectiveChange>,
+}
+
+impl InstanceReconfig {
+    pub fn needs_restart(&self) -> bool {
+        self.changes.iter().any(|x| !x.hot)
+    }
+}
+
+/// the changes of all the instances by the template, which is the current one of cluster if
+/// tpl_name is `None`. the instances without changes are omitted.
+pub fn plan_reconfig(
+    etcd: &str,
+    name: &str,
+    tpl_name: Option<&str>,
+) -> Result<Vec<InstanceReconfig>, Error> {
+    Reconfig::new(MyEtcd::open(etcd)?, name, tpl_name, "")?.plan()
+}
+
+/// change the configs of cluster by the template as a job. the cluster is locked until the job
+/// is finished, so it's a `ClusterBusy` if another job is running on it.
+pub fn spawn_reconfig(etcd: &str, name: &str, tpl_name: Option<&str>) -> Result<Job, Error> {
+    let myetcd = MyEtcd::open(etcd)?;
+    let job = Job::new(&gen_job_id(name), "reconfig", name);
+    // load the template before the job, so the bad ones are replied at once
+    Reconfig::new(MyEtcd::open(etcd)?, name, tpl_name, &job.id)?;
+
+    let lock = ClusterLock::acquire(&myetcd, etcd, name, "reconfig")?;
+
+    let etcd_addr = etcd.to_string();
+    let (name, tpl_name, job_id) = (
+        name.to_string(),
+        tpl_name.map(|x| x.to_string()),
+        job.id.clone(),
+    );
+    spawn_job(&myetcd, etcd, job, move || {
+        let _lock = lock;
+        MyEtcd::open(&etcd_addr)
+            .and_then(|myetcd| Reconfig::new(myetcd, &name, tpl_name.as_deref(), &job_id))
+            .and_then(|reconfig| reconfig.run())
+    })
+}
+
+struct Reconfig {
+    myetcd: MyEtcd,
+    job_id: String,
+    file_server: String,
+    template: Template,
+    // the current param of cluster with the new template, only used to render the files
+    param: DeployParm,
+}
+
+impl Reconfig {
+    fn new(
+        myetcd: MyEtcd,
+        name: &str,
+        tpl_name: Option<&str>,
+        job_id: &str,
+    ) -> Result<Reconfig, Error> {
+        let base = format!("/haste/clusters/{}", name);
+        let cache_type = myetcd
+            .get_value(&format!("{}/cache_type", base))?
+            .ok_or_else(|| ReconfigError::NotFound(name.to_string()))?;
+        let cache_type = parse_cache_type(&cache_type)?;
+        if cache_type == CacheType::Memcache {
+            return Err(ReconfigError::Unsupported(name.to_string()).into());
+        }
+        let tpl_name = match tpl_name {
+            Some(tpl_name) => tpl_name.to_string(),
+            None => myetcd
+                .get_value(&format!("{}/template", base))?
+                .ok_or_else(|| format_err!("template of cluster {} is not found", name))?,
+        };
+        let template = Template::load(&myetcd, cache_type, &tpl_name)
+            .map_err(|err| ReconfigError::BadTemplate(format!("{}", err)))?;
+        let version = myetcd
+            .get_value(&format!("{}/version", base))?
+            .unwrap_or_default();
+        let file_server = myetcd.get_value(FILE_SERVER_KEY)?.unwrap_or_default();
+
+        let param = DeployParm {
+            name: name.to_string(),
+            cpu_percent: 0,
+            max_memory: 0,
+            total_memory: 0,
+            version,
+            tpl_name,
+            cache_type,
+            appids: String::new(),
+            group: String::new(),
+            dial_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+        };
+        Ok(Reconfig {
+            myetcd,
+            job_id: job_id.to_string(),
+            file_server,
+            template,
+            param,
+        })
+    }
+
+    fn run(&self) -> Result<(), Error> {
+        let name = &self.param.name;
+        let addrs = cluster_instances(&self.myetcd, name)?;
+        wait_healthy(name, &addrs, 0)?;
+
+        let plan = self.plan()?;
+        let mut restarts: Vec<_> = plan
+            .iter()
+            .filter(|x| x.needs_restart())
+            .map(|x| x.addr.as_str())
+            .collect();
+        info!(
+            "change configs of {} instances of cluster {}, {} of them need restart",
+            plan.len(),
+            name,
+            restarts.len()
+        );
+
+        // the restarted instances get all the changes from the files
+        for inst in plan.iter().filter(|x| !x.needs_restart()) {
+            if !self.set_hot(inst)? {
+                restarts.push(&inst.addr);
+            }
+        }
+        wait_healthy(name, &addrs, HEALTH_TIMEOUT)?;
+
+        let mut hosts: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for addr in &restarts {
+            let (host, port) = split_addr(addr)?;
+            hosts.entry(host).or_default().push(port);
+        }
+        for (host, ports) in &hosts {
+            self.converge(host, ports)?;
+            wait_healthy(name, &addrs, HEALTH_TIMEOUT)?;
+        }
+
+        self.myetcd.set(
+            &format!("/haste/clusters/{}/template", name),
+            &self.param.tpl_name,
+        )?;
+        info!(
+            "configs of cluster {} are changed by template {}",
+            name, self.param.tpl_name
+        );
+        Ok(())
+    }
+
+    // apply the changes by `CONFIG SET` and rewrite them into the file, it's false if any of
+    // them is refused, then the instance has to be restarted with the files.
+    fn set_hot(&self, inst: &InstanceReconfig) -> Result<bool, Error> {
+        let mut myredis = MyRedis::open(slice::from_ref(&inst.addr))?;
+        for change in &inst.changes {
+            info!(
+                "set {} of {} from {:?} to {:?}",
+                change.name, inst.addr, change.current, change.desired
+            );
+            if let Err(err) = myredis.config_set(&inst.addr, &change.name, &change.desired) {
+                warn!(
+                    "fail to set {} of {} due {}, restart it instead",
+                    change.name, inst.addr, err
+                );
+                return Ok(false);
+            }
+        }
+        let _: () = myredis.execute(&inst.addr, "CONFIG REWRITE")?;
+        Ok(true)
+    }
+
+    fn plan(&self) -> Result<Vec<InstanceReconfig>, Error> {
+        let mut plan = Vec::new();
+        for addr in cluster_instances(&self.myetcd, &self.param.name)? {
+            let (host, port) = split_addr(&addr)?;
+            let conf = self
+                .render(host, port)?
+                .into_iter()
+                .find(|x| x.get_fpath().ends_with("redis.conf"))
+                .ok_or_else(|| format_err!("no redis.conf is rendered for {}", addr))?;
+
+            let mut myredis = MyRedis::open(slice::from_ref(&addr))?;
+            let mut changes = Vec::new();
+            for (name, desired) in parse_conf(conf.get_content()) {
+                let current = match myredis.config_get(&addr, &name)? {
+                    Some(current) => current,
+                    None => {
+                        warn!("skip directive {} which is unknown to {}", name, addr);
+                        continue;
+                    }
+                };
+                if normalize(&current) != normalize(&desired) {
+                    changes.push(DirectiveChange {
+                        hot: !RESTART_DIRECTIVES.contains(&name.as_str()),
+                        name,
+                        current,
+                        desired,
+                    });
+                }
+            }
+            if !changes.is_empty() {
+                plan.push(InstanceReconfig { addr, changes });
+            }
+        }
+        Ok(plan)
+    }
+
+    fn render(&self, host: &str, port: usize) -> Result<Vec<File>, Error> {
+        let layout = get_capacity(&self.myetcd, host)?
+            .map(|offer| InstanceLayout::new(&offer.data_root))
+            .unwrap_or_default();
+        self.template.render_redis(&layout, port, &self.param)
+    }
+
+    // converge the instances on host with the rendered files, the agent restarts the ones
+    // whose files are changed.
+    fn converge(&self, host: &str, ports: &[usize]) -> Result<(), Error> {
+        let mut insts = Vec::new();
+        for port in ports {
+            let mut inst = Instance::new();
+            inst.set_port(*port as i64);
+            inst.set_files(self.render(host, *port)?.into());
+            insts.push(inst);
+        }
+        let mut ci = CacheInfo::new();
+        ci.set_job_id(self.job_id.clone());
+        ci.set_cache_type(self.param.cache_type);
+        ci.set_cluster(self.param.name.clone());
+        ci.set_version(self.param.version.clone());
+        ci.set_file_server(self.file_server.clone());
+        ci.set_converge(true);
+        ci.set_insts(insts.into());
+
+        info!("restart {:?} on {} with the new configs", ports, host);
+        let state = connect_agent(&self.myetcd, host)?.deploy(&ci)?;
+        if state.get_state() == State::Error {
+            return Err(format_err!(
+                "fail to restart {:?} on {} due {}",
+                ports,
+                host,
+                state.get_msg()
+            ));
+        }
+        Ok(())
+    }
+}
+
+// the directives of redis.conf in order, the values of repeated ones like `save` are joined
+// by ' ' as `CONFIG GET` does.
+fn parse_conf(content: &str) -> Vec<(String, String)> {
+    let mut directives: Vec<(String, String)> = Vec::new();
+    for line in content.lines().map(|x| x.trim()) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut items = line.split_whitespace();
+        let name = match items.next() {
+            Some(name) => name.to_lowercase(),
+            None => continue,
+        };
+        let value: Vec<_> = items.map(|x| x.trim_matches('"')).collect();
+        let value = value.join(" ");
+        match directives.iter_mut().find(|(x, _)| *x == name) {
+            Some((_, current)) => {
+                current.push(' ');
+                current.push_str(&value);
+            }
+            None => directives.push((name, value)),
+        }
+    }
+    directives
+}
+
+// lowercase the value and turn memory units like 4gb into bytes.
+fn normalize(value: &str) -> String {
+    let items: Vec<_> = value
+        .split_whitespace()
+        .map(|item| {
+            let item = item.to_lowercase();
+            let pos = item
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(item.len());
+            let unit = match &item[pos..] {
+                "k" => 1000,
+                "kb" => 1024,
+                "m" => 1000 * 1000,
+                "mb" => 1024 * 1024,
+                "g" => 1000 * 1000 * 1000,
+                "gb" => 1024 * 1024 * 1024,
+                _ => return item,
+            };
+            match item[..pos].parse::<u64>() {
+                Ok(num) => format!("{}", num * unit),
+                Err(_) => item,
+            }
+        })
+        .collect();
+    items.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_redis_conf() {
+        let conf = r#"
+# snapshot
+save 900 1
+save 300 10
+SAVE 60 10000
+
+port 7000
+maxmemory 4gb
+maxmemory-policy "allkeys-lru"
+"#;
+        let directives = parse_conf(conf);
+        let get = |name: &str| {
+            directives
+                .iter()
+                .find(|(x, _)| x == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(get("save"), Some("900 1 300 10 60 10000"));
+        assert_eq!(get("port"), Some("7000"));
+        assert_eq!(get("maxmemory"), Some("4gb"));
+        assert_eq!(get("maxmemory-policy"), Some("allkeys-lru"));
+        assert_eq!(directives.len(), 4);
+        assert_eq!(directives[0].0, "save");
+    }
+
+    #[test]
+    fn normalize_values() {
+        assert_eq!(normalize("4gb"), normalize("4294967296"));
+        assert_eq!(normalize("4GB"), "4294967296");
+        assert_eq!(normalize("4g"), "4000000000");
+        assert_eq!(normalize("100mb"), "104857600");
+        assert_eq!(normalize("64kb"), "65536");
+        assert_ne!(normalize("4gb"), normalize("4g"));
+        assert_eq!(normalize("allkeys-LRU"), "allkeys-lru");
+        assert_eq!(normalize("900  1 300 10"), "900 1 300 10");
+        assert_eq!(normalize("10xb"), "10xb");
+    }
+}
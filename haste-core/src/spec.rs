@@ -0,0 +1,269 @@
+//! declarative specs of clusters, saved in etcd as
+//!   /haste/specs/{name}/spec      the json of ClusterSpec
+//!   /haste/specs/{name}/status    the json of SpecStatus, written by reconciler
+//!
+//! the revision of spec is increased by each save, so a spec which was changed by someone
+//! else since it's read can't be saved again.
+
+use crate::deploy::server::DeployParm;
+use crate::myetcd::MyEtcd;
+use crate::proto::CacheType;
+
+use failure::{format_err, Error, Fail};
+use serde_derive::{Deserialize, Serialize};
+
+use std::time::{self, SystemTime};
+
+const SPECS_DIR: &str = "/haste/specs";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClusterSpec {
+    pub name: String,
+    #[serde(with = "crate::deploy::plan::serde_cache_type")]
+    pub cache_type: CacheType,
+    pub version: String,
+    pub tpl_name: String,
+    #[serde(default)]
+    pub cpu_percent: usize,
+    // memory of each instance
+    pub max_memory: usize,
+    // the number of instances, it's rounded up to even since each master has a slave
+    pub replicas: usize,
+    #[serde(default)]
+    pub appids: Vec<String>,
+    #[serde(default)]
+    pub group: String,
+    pub dial_timeout: Option<u64>,
+    pub read_timeout: Option<u64>,
+    pub write_timeout: Option<u64>,
+    // 0 when the spec is never saved
+    #[serde(default)]
+    pub revision: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+impl ClusterSpec {
+    pub fn to_param(&self) -> DeployParm {
+        DeployParm {
+            name: self.name.clone(),
+            cpu_percent: self.cpu_percent,
+            max_memory: self.max_memory,
+            total_memory: self.max_memory * self.replicas,
+            version: self.version.clone(),
+            tpl_name: self.tpl_name.clone(),
+            cache_type: self.cache_type,
+            appids: self.appids.join(","),
+            group: self.group.clone(),
+            dial_timeout: self.dial_timeout,
+            read_timeout: self.read_timeout,
+            write_timeout: self.write_timeout,
+        }
+    }
+
+    /// the number of instances which the deploy of spec creates.
+    pub fn instances(&self) -> usize {
+        self.replicas + self.replicas % 2
+    }
+}
+
+/// the result of the latest reconcile of spec.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpecStatus {
+    // the revision of spec which was reconciled
+    pub revision: u64,
+    // the differences between spec and cluster, empty if the cluster is synced
+    pub drifts: Vec<String>,
+    // the latest job started by reconciler
+    pub last_job: Option<String>,
+    pub checked_at: u64,
+}
+
+#[derive(Debug, Fail)]
+#[fail(
+    display = "spec {} is at revision {} but {} was given",
+    name, current, given
+)]
+pub struct RevisionConflict {
+    pub name: String,
+    pub current: u64,
+    pub given: u64,
+}
+
+/// save the spec as the next revision. the revision of spec must be the current one or 0,
+/// or it's a `RevisionConflict`.
+pub fn save_spec(myetcd: &MyEtcd, spec: &ClusterSpec) -> Result<ClusterSpec, Error> {
+    let key = spec_key(&spec.name);
+    let current = myetcd.get_indexed(&key)?;
+    let saved = match current {
+        Some((ref data, _)) => next_revision(
+            spec,
+            Some(&serde_json::from_str::<ClusterSpec>(data)?),
+            now(),
+        )?,
+        None => next_revision(spec, None, now())?,
+    };
+
+    let data = serde_json::to_string(&saved)?;
+    let swapped = match current {
+        Some((_, index)) => myetcd.compare_and_swap(&key, &data, index)?,
+        None => myetcd.create(&key, &data)?,
+    };
+    if !swapped {
+        return Err(format_err!(
+            "spec {} was changed by someone else, try it again",
+            spec.name
+        ));
+    }
+    Ok(saved)
+}
+
+// the spec to save after the current one, which is `None` if it's never saved.
+fn next_revision(
+    spec: &ClusterSpec,
+    current: Option<&ClusterSpec>,
+    now: u64,
+) -> Result<ClusterSpec, RevisionConflict> {
+    let revision = current.map(|x| x.revision).unwrap_or(0);
+    if spec.revision != 0 && spec.revision != revision {
+        return Err(RevisionConflict {
+            name: spec.name.clone(),
+            current: revision,
+            given: spec.revision,
+        });
+    }
+
+    let mut saved = spec.clone();
+    saved.revision = revision + 1;
+    saved.updated_at = now;
+    Ok(saved)
+}
+
+/// load the spec, `None` if it's not exists.
+pub fn load_spec(myetcd: &MyEtcd, name: &str) -> Result<Option<ClusterSpec>, Error> {
+    match myetcd.get_value(&spec_key(name))? {
+        Some(data) => Ok(Some(serde_json::from_str(&data)?)),
+        None => Ok(None),
+    }
+}
+
+/// all the specs ordered by name.
+pub fn list_specs(myetcd: &MyEtcd) -> Result<Vec<ClusterSpec>, Error> {
+    let mut names: Vec<_> = myetcd
+        .list_keys(SPECS_DIR)?
+        .into_iter()
+        .filter_map(|key| key.rsplit('/').next().map(|x| x.to_string()))
+        .collect();
+    names.sort();
+
+    let mut specs = Vec::new();
+    for name in names {
+        if let Some(spec) = load_spec(myetcd, &name)? {
+            specs.push(spec);
+        }
+    }
+    Ok(specs)
+}
+
+/// delete the spec and it's status, the cluster is kept as it is.
+pub fn delete_spec(myetcd: &MyEtcd, name: &str) -> Result<(), Error> {
+    myetcd.delete_dir(&format!("{}/{}", SPECS_DIR, name))
+}
+
+pub fn load_status(myetcd: &MyEtcd, name: &str) -> Result<Option<SpecStatus>, Error> {
+    match myetcd.get_value(&status_key(name))? {
+        Some(data) => Ok(Some(serde_json::from_str(&data)?)),
+        None => Ok(None),
+    }
+}
+
+pub fn save_status(myetcd: &MyEtcd, name: &str, status: &SpecStatus) -> Result<(), Error> {
+    myetcd.set(&status_key(name), &serde_json::to_string(status)?)?;
+    Ok(())
+}
+
+fn spec_key(name: &str) -> String {
+    format!("{}/{}/spec", SPECS_DIR, name)
+}
+
+fn status_key(name: &str) -> String {
+    format!("{}/{}/status", SPECS_DIR, name)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(revision: u64) -> ClusterSpec {
+        ClusterSpec {
+            name: "session".to_string(),
+            cache_type: CacheType::RedisCluster,
+            version: "4.0.11".to_string(),
+            tpl_name: "default".to_string(),
+            cpu_percent: 0,
+            max_memory: 4096,
+            replicas: 3,
+            appids: vec!["account.session".to_string()],
+            group: String::new(),
+            dial_timeout: Some(1000),
+            read_timeout: None,
+            write_timeout: None,
+            revision,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn first_revision() {
+        let saved = next_revision(&spec(0), None, 100).unwrap();
+        assert_eq!((saved.revision, saved.updated_at), (1, 100));
+    }
+
+    #[test]
+    fn next_of_current_revision() {
+        let current = spec(3);
+        // 0 overwrites whatever the current one is
+        assert_eq!(
+            next_revision(&spec(0), Some(&current), 100)
+                .unwrap()
+                .revision,
+            4
+        );
+        assert_eq!(
+            next_revision(&spec(3), Some(&current), 100)
+                .unwrap()
+                .revision,
+            4
+        );
+    }
+
+    #[test]
+    fn conflict_with_stale_revision() {
+        let current = spec(3);
+        let conflict = next_revision(&spec(2), Some(&current), 100).unwrap_err();
+        assert_eq!((conflict.current, conflict.given), (3, 2));
+        assert_eq!(
+            format!("{}", conflict),
+            "spec session is at revision 3 but 2 was given"
+        );
+        assert!(next_revision(&spec(4), Some(&current), 100).is_err());
+        assert!(next_revision(&spec(1), None, 100).is_err());
+    }
+
+    #[test]
+    fn even_instances() {
+        assert_eq!(spec(0).instances(), 4);
+        let mut even = spec(0);
+        even.replicas = 4;
+        assert_eq!(even.instances(), 4);
+        assert_eq!(even.to_param().total_memory, 4 * 4096);
+        assert_eq!(even.to_param().appids, "account.session");
+    }
+}
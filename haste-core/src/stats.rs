@@ -0,0 +1,485 @@
+//! typed metrics of redis instances, scraped from `INFO ALL` and `CLUSTER INFO`.
+//!
+//! the stats of all the instances of a cluster are aggregated into `ClusterStats`, which is
+//! used to check the preconditions like "the instance is in low ops" before removing it.
+
+use crate::myetcd::MyEtcd;
+use crate::myredis::MyRedis;
+
+use failure::{format_err, Error};
+use serde_derive::Serialize;
+
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// the instances under it are in low ops and can be removed.
+pub const LOW_OPS: u64 = 100;
+// seconds between the health checks in `wait_healthy`
+const CHECK_INTERVAL: u64 = 2;
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SlaveInfo {
+    pub addr: String,
+    // online after the sync is done, or wait_bgsave, send_bulk ...
+    pub state: String,
+    pub offset: u64,
+    // seconds since the last ack of slave
+    pub lag: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RedisInfo {
+    pub version: String,
+    pub role: String,
+    pub ops_per_sec: u64,
+    pub keyspace_hits: u64,
+    pub keyspace_misses: u64,
+    pub used_memory: u64,
+    pub maxmemory: u64,
+    pub evicted_keys: u64,
+    pub connected_clients: u64,
+    pub cluster_enabled: bool,
+    // master_repl_offset of master, or the offset received from master of slave
+    pub repl_offset: u64,
+    // only for slave
+    pub master_link_up: bool,
+    // only for master
+    pub slaves: Vec<SlaveInfo>,
+}
+
+impl RedisInfo {
+    pub fn parse(text: &str) -> RedisInfo {
+        let fields = parse_fields(text, ':');
+        let mut info = RedisInfo {
+            version: fields.get("redis_version").cloned().unwrap_or_default(),
+            role: fields.get("role").cloned().unwrap_or_default(),
+            ops_per_sec: get_u64(&fields, "instantaneous_ops_per_sec"),
+            keyspace_hits: get_u64(&fields, "keyspace_hits"),
+            keyspace_misses: get_u64(&fields, "keyspace_misses"),
+            used_memory: get_u64(&fields, "used_memory"),
+            maxmemory: get_u64(&fields, "maxmemory"),
+            evicted_keys: get_u64(&fields, "evicted_keys"),
+            connected_clients: get_u64(&fields, "connected_clients"),
+            cluster_enabled: get_u64(&fields, "cluster_enabled") == 1,
+            master_link_up: fields.get("master_link_status").map(|x| x.as_str()) == Some("up"),
+            ..RedisInfo::default()
+        };
+
+        if info.role == "slave" {
+            info.repl_offset = get_u64(&fields, "slave_repl_offset");
+        } else {
+            info.repl_offset = get_u64(&fields, "master_repl_offset");
+        }
+
+        // slave0:ip=10.0.0.1,port=7001,state=online,offset=3171,lag=0
+        let mut slaves: Vec<_> = fields
+            .iter()
+            .filter(|(key, _)| key.starts_with("slave") && key[5..].parse::<u64>().is_ok())
+            .map(|(_, value)| {
+                let kv = parse_fields(value.replace(',', "\n").as_str(), '=');
+                SlaveInfo {
+                    addr: format!(
+                        "{}:{}",
+                        kv.get("ip").cloned().unwrap_or_default(),
+                        kv.get("port").cloned().unwrap_or_default()
+                    ),
+                    state: kv.get("state").cloned().unwrap_or_default(),
+                    offset: get_u64(&kv, "offset"),
+                    lag: get_u64(&kv, "lag"),
+                }
+            })
+            .collect();
+        slaves.sort_by(|x, y| x.addr.cmp(&y.addr));
+        info.slaves = slaves;
+        info
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        hit_ratio(self.keyspace_hits, self.keyspace_misses)
+    }
+
+    /// the max bytes of slaves behind it.
+    pub fn repl_lag(&self) -> u64 {
+        self.slaves
+            .iter()
+            .map(|x| self.repl_offset.saturating_sub(x.offset))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ClusterInfo {
+    pub state: String,
+    pub slots_assigned: u64,
+    pub slots_ok: u64,
+    pub slots_pfail: u64,
+    pub slots_fail: u64,
+    pub known_nodes: u64,
+    pub size: u64,
+    pub current_epoch: u64,
+}
+
+impl ClusterInfo {
+    pub fn parse(text: &str) -> ClusterInfo {
+        let fields = parse_fields(text, ':');
+        ClusterInfo {
+            state: fields.get("cluster_state").cloned().unwrap_or_default(),
+            slots_assigned: get_u64(&fields, "cluster_slots_assigned"),
+            slots_ok: get_u64(&fields, "cluster_slots_ok"),
+            slots_pfail: get_u64(&fields, "cluster_slots_pfail"),
+            slots_fail: get_u64(&fields, "cluster_slots_fail"),
+            known_nodes: get_u64(&fields, "cluster_known_nodes"),
+            size: get_u64(&fields, "cluster_size"),
+            current_epoch: get_u64(&fields, "cluster_current_epoch"),
+        }
+    }
+}
+
+/// the stats of an instance, info is None if it's unreachable.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RedisStats {
+    pub addr: String,
+    pub info: Option<RedisInfo>,
+    pub cluster: Option<ClusterInfo>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ClusterStats {
+    pub instances: Vec<RedisStats>,
+    pub ops_per_sec: u64,
+    pub keyspace_hits: u64,
+    pub keyspace_misses: u64,
+    pub used_memory: u64,
+    pub evicted_keys: u64,
+    pub connected_clients: u64,
+    // the max bytes of slaves behind their masters
+    pub repl_lag: u64,
+    pub unreachable: usize,
+}
+
+impl ClusterStats {
+    pub fn aggregate(mut instances: Vec<RedisStats>) -> ClusterStats {
+        instances.sort_by(|x, y| x.addr.cmp(&y.addr));
+        let mut stats = ClusterStats::default();
+        for inst in &instances {
+            let info = match inst.info.as_ref() {
+                Some(info) => info,
+                None => {
+                    stats.unreachable += 1;
+                    continue;
+                }
+            };
+            stats.ops_per_sec += info.ops_per_sec;
+            stats.keyspace_hits += info.keyspace_hits;
+            stats.keyspace_misses += info.keyspace_misses;
+            stats.used_memory += info.used_memory;
+            stats.evicted_keys += info.evicted_keys;
+            stats.connected_clients += info.connected_clients;
+            stats.repl_lag = stats.repl_lag.max(info.repl_lag());
+        }
+        stats.instances = instances;
+        stats
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        hit_ratio(self.keyspace_hits, self.keyspace_misses)
+    }
+
+    pub fn get(&self, addr: &str) -> Option<&RedisStats> {
+        self.instances.iter().find(|x| x.addr == addr)
+    }
+
+    /// check all the instances are reachable, their cluster states are ok and the slaves are
+    /// linked to their masters.
+    pub fn check_healthy(&self) -> Result<(), Error> {
+        let mut problems = Vec::new();
+        for inst in &self.instances {
+            let info = match inst.info.as_ref() {
+                Some(info) => info,
+                None => {
+                    problems.push(format!("{} is unreachable", inst.addr));
+                    continue;
+                }
+            };
+            if info.role == "slave" && !info.master_link_up {
+                problems.push(format!("{} is not linked to it's master", inst.addr));
+            }
+            if let Some(cluster) = inst.cluster.as_ref() {
+                if cluster.state != "ok" {
+                    problems.push(format!(
+                        "{} is in cluster state {}",
+                        inst.addr, cluster.state
+                    ));
+                }
+            }
+        }
+        if !problems.is_empty() {
+            return Err(format_err!("{}", problems.join(", ")));
+        }
+        Ok(())
+    }
+
+    /// check the instances are in low ops before removing them. an instance which is
+    /// unreachable or not in the cluster is an error, since it's ops are unknown.
+    pub fn check_low_ops(&self, addrs: &[String], max_ops: u64) -> Result<(), Error> {
+        let mut busy = Vec::new();
+        let mut unreachable = Vec::new();
+        for addr in addrs {
+            let inst = self
+                .get(addr)
+                .ok_or_else(|| format_err!("instance {} is not in the cluster", addr))?;
+            match inst.info.as_ref() {
+                Some(info) if info.ops_per_sec > max_ops => {
+                    busy.push(format!("{}({} ops)", addr, info.ops_per_sec))
+                }
+                Some(_) => {}
+                None => unreachable.push(format!(
+                    "{}({})",
+                    addr,
+                    inst.error.as_ref().map(|x| x.as_str()).unwrap_or("unknown")
+                )),
+            }
+        }
+        if !unreachable.is_empty() {
+            return Err(format_err!(
+                "ops of unreachable instances are unknown: {}",
+                unreachable.join(", ")
+            ));
+        }
+        if !busy.is_empty() {
+            return Err(format_err!(
+                "instances are not in low ops (max {}): {}",
+                max_ops,
+                busy.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// the addrs of all the instances of cluster, in `/haste/clusters/{name}/instances/{ip}:{port}`.
+pub fn cluster_instances(myetcd: &MyEtcd, name: &str) -> Result<Vec<String>, Error> {
+    let dir = format!("/haste/clusters/{}/instances", name);
+    Ok(myetcd
+        .list_keys(&dir)?
+        .into_iter()
+        .filter_map(|key| key.rsplit('/').next().map(|x| x.to_string()))
+        .collect())
+}
+
+/// scrape all the instances of cluster.
+pub fn scrape_cluster(myetcd: &MyEtcd, name: &str) -> Result<ClusterStats, Error> {
+    let addrs = cluster_instances(myetcd, name)?;
+    if addrs.is_empty() {
+        return Err(format_err!("cluster {} has no instances", name));
+    }
+    Ok(MyRedis::open(&addrs)?.scrape())
+}
+
+/// wait until all the instances of cluster are healthy, or it's an error with the problems
+/// after timeout seconds.
+pub fn wait_healthy(name: &str, addrs: &[String], timeout: u64) -> Result<(), Error> {
+    let start = Instant::now();
+    loop {
+        let problems = match MyRedis::open(addrs)?.scrape().check_healthy() {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        if start.elapsed() >= Duration::from_secs(timeout) {
+            return Err(format_err!(
+                "cluster {} is not healthy due {}",
+                name,
+                problems
+            ));
+        }
+        thread::sleep(Duration::from_secs(CHECK_INTERVAL));
+    }
+}
+
+fn hit_ratio(hits: u64, misses: u64) -> f64 {
+    if hits + misses == 0 {
+        return 0.0;
+    }
+    hits as f64 / (hits + misses) as f64
+}
+
+// parse lines of `{key}{sep}{value}`, the comments and sections are skipped.
+fn parse_fields(text: &str, sep: char) -> HashMap<String, String> {
+    text.lines()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty() && !x.starts_with('#'))
+        .filter_map(|line| {
+            let mut kv = line.splitn(2, sep);
+            match (kv.next(), kv.next()) {
+                (Some(key), Some(value)) => Some((key.to_string(), value.to_string())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn get_u64(fields: &HashMap<String, String>, key: &str) -> u64 {
+    fields.get(key).and_then(|x| x.parse().ok()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // captured from `INFO ALL` of redis 4.0.14, some sections are omitted
+    const MASTER_INFO: &str = "# Server\r
+redis_version:4.0.14\r
+redis_git_sha1:00000000\r
+redis_mode:cluster\r
+tcp_port:7000\r
+uptime_in_seconds:1739\r
+\r
+# Clients\r
+connected_clients:12\r
+blocked_clients:0\r
+\r
+# Memory\r
+used_memory:2651616\r
+used_memory_human:2.53M\r
+maxmemory:4294967296\r
+maxmemory_human:4.00G\r
+maxmemory_policy:allkeys-lru\r
+\r
+# Stats\r
+total_connections_received:57\r
+instantaneous_ops_per_sec:1532\r
+evicted_keys:7\r
+keyspace_hits:9000\r
+keyspace_misses:1000\r
+\r
+# Replication\r
+role:master\r
+connected_slaves:2\r
+slave0:ip=10.0.0.2,port=7001,state=online,offset=3171,lag=0\r
+slave1:ip=10.0.0.3,port=7001,state=wait_bgsave,offset=0,lag=1\r
+master_replid:4a2d8c4d2fd4bc9fbf0c2f3a0d8b57ba8d6e6a35\r
+master_repl_offset:3185\r
+\r
+# Cluster\r
+cluster_enabled:1\r
+\r
+# Keyspace\r
+db0:keys=12,expires=0,avg_ttl=0\r
+";
+
+    const SLAVE_INFO: &str = "# Server\r
+redis_version:4.0.14\r
+\r
+# Replication\r
+role:slave\r
+master_host:10.0.0.1\r
+master_port:7000\r
+master_link_status:down\r
+master_last_io_seconds_ago:-1\r
+master_sync_in_progress:0\r
+slave_repl_offset:3100\r
+slave_priority:100\r
+slave_read_only:1\r
+connected_slaves:0\r
+master_repl_offset:3100\r
+\r
+# Cluster\r
+cluster_enabled:1\r
+";
+
+    // captured from `CLUSTER INFO`
+    const CLUSTER_INFO: &str = "cluster_state:fail\r
+cluster_slots_assigned:16384\r
+cluster_slots_ok:10923\r
+cluster_slots_pfail:0\r
+cluster_slots_fail:5461\r
+cluster_known_nodes:6\r
+cluster_size:3\r
+cluster_current_epoch:8\r
+cluster_my_epoch:2\r
+cluster_stats_messages_ping_sent:2386\r
+cluster_stats_messages_sent:4861\r
+";
+
+    #[test]
+    fn parse_master_info() {
+        let info = RedisInfo::parse(MASTER_INFO);
+        assert_eq!(info.version, "4.0.14");
+        assert_eq!(info.role, "master");
+        assert_eq!(info.ops_per_sec, 1532);
+        assert_eq!(info.connected_clients, 12);
+        assert_eq!(info.used_memory, 2651616);
+        assert_eq!(info.maxmemory, 4294967296);
+        assert_eq!(info.evicted_keys, 7);
+        assert!(info.cluster_enabled);
+        assert!((info.hit_ratio() - 0.9).abs() < 1e-9);
+        assert_eq!(info.repl_offset, 3185);
+        assert_eq!(info.slaves.len(), 2);
+        assert_eq!(info.slaves[0].addr, "10.0.0.2:7001");
+        assert_eq!(info.slaves[0].state, "online");
+        assert_eq!(info.slaves[0].offset, 3171);
+        assert_eq!(info.slaves[1].addr, "10.0.0.3:7001");
+        assert_eq!(info.slaves[1].state, "wait_bgsave");
+        assert_eq!(info.slaves[1].lag, 1);
+        assert_eq!(info.repl_lag(), 3185);
+    }
+
+    #[test]
+    fn parse_slave_info() {
+        let info = RedisInfo::parse(SLAVE_INFO);
+        assert_eq!(info.role, "slave");
+        assert!(!info.master_link_up);
+        assert_eq!(info.repl_offset, 3100);
+        assert!(info.slaves.is_empty());
+        assert!(RedisInfo::parse(&SLAVE_INFO.replace("down", "up")).master_link_up);
+    }
+
+    #[test]
+    fn parse_cluster_info() {
+        let info = ClusterInfo::parse(CLUSTER_INFO);
+        assert_eq!(info.state, "fail");
+        assert_eq!(info.slots_assigned, 16384);
+        assert_eq!(info.slots_ok, 10923);
+        assert_eq!(info.slots_pfail, 0);
+        assert_eq!(info.slots_fail, 5461);
+        assert_eq!(info.known_nodes, 6);
+        assert_eq!(info.size, 3);
+        assert_eq!(info.current_epoch, 8);
+    }
+
+    #[test]
+    fn unreachable_is_not_idle() {
+        let stats = ClusterStats::aggregate(vec![
+            RedisStats {
+                addr: "10.0.0.1:7000".to_string(),
+                info: Some(RedisInfo::parse(MASTER_INFO)),
+                ..RedisStats::default()
+            },
+            RedisStats {
+                addr: "10.0.0.2:7001".to_string(),
+                info: Some(RedisInfo::parse(SLAVE_INFO)),
+                ..RedisStats::default()
+            },
+            RedisStats {
+                addr: "10.0.0.3:7001".to_string(),
+                error: Some("connection refused".to_string()),
+                ..RedisStats::default()
+            },
+        ]);
+        assert_eq!(stats.unreachable, 1);
+        let addrs = |x: &[&str]| x.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        assert!(stats.check_low_ops(&addrs(&["10.0.0.2:7001"]), 100).is_ok());
+        assert!(stats
+            .check_low_ops(&addrs(&["10.0.0.1:7000"]), 100)
+            .is_err());
+        assert!(stats
+            .check_low_ops(&addrs(&["10.0.0.3:7001"]), 100)
+            .is_err());
+        assert!(stats
+            .check_low_ops(&addrs(&["10.0.0.4:7001"]), 100)
+            .is_err());
+    }
+}
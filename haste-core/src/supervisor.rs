@@ -0,0 +1,82 @@
+//! the backends which run the cache instances of agent.
+//!
+//! the units rendered by leader are always the definition of instances, the backends only
+//! differ in how they run them:
+//!   * systemd: by `systemctl`, the default one
+//!   * exec: fork/exec the `ExecStart` of unit directly with it's restart policy and pid file,
+//!     for the hosts without systemd like containers
+//!   * mock: only records the calls in memory, for tests
+//!
+//! the backend is selected by `[supervisor]` of agent config.
+
+pub mod exec;
+pub mod mock;
+
+use crate::proto::UnitState;
+use crate::supervisor::exec::ExecSupervisor;
+use crate::supervisor::mock::MockSupervisor;
+use crate::systemd::SystemdSupervisor;
+
+use failure::Error;
+use serde_derive::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+pub const RUN_DIR: &str = "/data/haste/run";
+
+pub trait Supervisor: Send + Sync {
+    /// reload the units after they were changed.
+    fn reload(&self) -> Result<(), Error>;
+
+    /// start the unit and wait until it's running, a no-op if it was running.
+    fn start(&self, unit: &str) -> Result<(), Error>;
+
+    fn stop(&self, unit: &str) -> Result<(), Error>;
+
+    fn restart(&self, unit: &str) -> Result<(), Error>;
+
+    fn status(&self, unit: &str) -> Result<UnitStatus, Error>;
+}
+
+#[derive(Clone, Debug)]
+pub struct UnitStatus {
+    pub state: UnitState,
+    // 0 if not running
+    pub pid: u32,
+    // how many times the unit was restarted by it's restart policy
+    pub restarts: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    Systemd,
+    Exec,
+    Mock,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorConfig {
+    pub backend: Backend,
+    // pid files and outputs of exec backend
+    pub run_dir: String,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        SupervisorConfig {
+            backend: Backend::Systemd,
+            run_dir: RUN_DIR.to_string(),
+        }
+    }
+}
+
+pub fn new_supervisor(config: &SupervisorConfig) -> Result<Arc<dyn Supervisor>, Error> {
+    let supervisor: Arc<dyn Supervisor> = match config.backend {
+        Backend::Systemd => Arc::new(SystemdSupervisor),
+        Backend::Exec => Arc::new(ExecSupervisor::new(&config.run_dir)?),
+        Backend::Mock => Arc::new(MockSupervisor::default()),
+    };
+    Ok(supervisor)
+}
@@ -0,0 +1,528 @@
+//! supervisor which fork/exec the instances directly, for the hosts without systemd.
+//!
+//! the `[Service]` section of unit is parsed for `ExecStart`, `WorkingDirectory`, `Environment`,
+//! `Restart` and `RestartSec`, so the same rendered unit works in both backends. the pid of each
+//! unit is saved in `{run_dir}/{unit}.pid` and it's output is appended into `{run_dir}/{unit}.log`.
+//!
+//! a monitor thread restarts the exited processes by their restart policy, and the processes left
+//! by the previous agent are adopted by their pid files. starting a unit which is pending restart
+//! is an error, since it's not running.
+
+use crate::layout::UNIT_DIR;
+use crate::proto::UnitState;
+use crate::supervisor::{Supervisor, UnitStatus};
+
+use failure::{format_err, Error};
+use log::{info, warn};
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MONITOR_INTERVAL: Duration = Duration::from_millis(500);
+// the process must be alive after it to be treated as started
+const START_CHECK: Duration = Duration::from_secs(1);
+// SIGKILL the process if it's still alive after SIGTERM
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_RESTART_SEC: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RestartPolicy {
+    No,
+    OnFailure,
+    Always,
+}
+
+#[derive(Clone, Debug)]
+struct ExecSpec {
+    argv: Vec<String>,
+    dir: Option<String>,
+    envs: Vec<(String, String)>,
+    restart: RestartPolicy,
+    restart_sec: Duration,
+}
+
+struct Process {
+    spec: ExecSpec,
+    pid: u32,
+    // None for the processes adopted from pid files, they can't be waited
+    child: Option<Child>,
+    // the process was exited and will be restarted at
+    restart_at: Option<Instant>,
+    restarts: u64,
+}
+
+pub struct ExecSupervisor {
+    run_dir: PathBuf,
+    procs: Arc<Mutex<HashMap<String, Process>>>,
+    // held while the unit is being started or stopped, so a unit is never spawned twice
+    units: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl ExecSupervisor {
+    pub fn new(run_dir: &str) -> Result<ExecSupervisor, Error> {
+        fs::create_dir_all(run_dir)?;
+        let supervisor = ExecSupervisor {
+            run_dir: PathBuf::from(run_dir),
+            procs: Arc::new(Mutex::new(HashMap::new())),
+            units: Mutex::new(HashMap::new()),
+        };
+        supervisor.adopt()?;
+        supervisor.spawn_monitor();
+        Ok(supervisor)
+    }
+
+    // adopt the alive processes in pid files, the stale pid files are removed.
+    fn adopt(&self) -> Result<(), Error> {
+        let mut procs = self.procs.lock().unwrap();
+        for entry in fs::read_dir(&self.run_dir)? {
+            let path = entry?.path();
+            if path.extension().map(|x| x != "pid").unwrap_or(true) {
+                continue;
+            }
+            let unit = match path.file_stem() {
+                Some(stem) => stem.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            let pid = fs::read_to_string(&path)
+                .ok()
+                .and_then(|x| x.trim().parse::<u32>().ok());
+            match (pid, parse_unit(&unit)) {
+                (Some(pid), Ok(spec)) if is_alive(pid, &spec) => {
+                    info!("adopt process {} of unit {}", pid, unit);
+                    procs.insert(
+                        unit,
+                        Process {
+                            spec,
+                            pid,
+                            child: None,
+                            restart_at: None,
+                            restarts: 0,
+                        },
+                    );
+                }
+                _ => {
+                    info!("remove stale pid file {:?}", path);
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn spawn_monitor(&self) {
+        let procs = self.procs.clone();
+        let run_dir = self.run_dir.clone();
+        thread::spawn(move || loop {
+            thread::sleep(MONITOR_INTERVAL);
+            procs
+                .lock()
+                .unwrap()
+                .retain(|unit, process| poll(&run_dir, unit, process));
+        });
+    }
+
+    fn unit_lock(&self, unit: &str) -> Arc<Mutex<()>> {
+        self.units
+            .lock()
+            .unwrap()
+            .entry(unit.to_string())
+            .or_default()
+            .clone()
+    }
+
+    fn terminate(&self, unit: &str, mut process: Process) -> Result<(), Error> {
+        if process.restart_at.is_none() {
+            info!("stop process {} of unit {}", process.pid, unit);
+            signal(process.pid, "TERM");
+            let deadline = Instant::now() + STOP_TIMEOUT;
+            loop {
+                let exited = match process.child.as_mut() {
+                    Some(child) => child.try_wait()?.is_some(),
+                    None => !is_alive(process.pid, &process.spec),
+                };
+                if exited {
+                    break;
+                }
+                if Instant::now() >= deadline {
+                    warn!("kill unit {} which is alive after {:?}", unit, STOP_TIMEOUT);
+                    signal(process.pid, "KILL");
+                    if let Some(child) = process.child.as_mut() {
+                        child.wait()?;
+                    }
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+        remove_pid_file(&self.run_dir, unit);
+        Ok(())
+    }
+}
+
+impl Supervisor for ExecSupervisor {
+    // units are parsed at each start, nothing to reload.
+    fn reload(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn start(&self, unit: &str) -> Result<(), Error> {
+        let lock = self.unit_lock(unit);
+        let _guard = lock.lock().unwrap();
+        match self.procs.lock().unwrap().get(unit) {
+            // it exited and is not running until the monitor restarts it
+            Some(process) if process.restart_at.is_some() => {
+                return Err(format_err!(
+                    "unit {} exited and is pending restart after {} restarts, see {:?}",
+                    unit,
+                    process.restarts,
+                    log_path(&self.run_dir, unit)
+                ));
+            }
+            Some(_) => return Ok(()),
+            None => {}
+        }
+
+        let spec = parse_unit(unit)?;
+        let mut process = spawn(&self.run_dir, unit, spec)?;
+        thread::sleep(START_CHECK);
+        if let Some(child) = process.child.as_mut() {
+            if let Some(status) = child.try_wait()? {
+                remove_pid_file(&self.run_dir, unit);
+                return Err(format_err!(
+                    "unit {} exit with {} after start, see {:?}",
+                    unit,
+                    status,
+                    log_path(&self.run_dir, unit)
+                ));
+            }
+        }
+        self.procs.lock().unwrap().insert(unit.to_string(), process);
+        Ok(())
+    }
+
+    fn stop(&self, unit: &str) -> Result<(), Error> {
+        let lock = self.unit_lock(unit);
+        let _guard = lock.lock().unwrap();
+        let process = self.procs.lock().unwrap().remove(unit);
+        match process {
+            Some(process) => self.terminate(unit, process),
+            None => {
+                info!("unit {} is not running", unit);
+                Ok(())
+            }
+        }
+    }
+
+    fn restart(&self, unit: &str) -> Result<(), Error> {
+        self.stop(unit)?;
+        self.start(unit)
+    }
+
+    fn status(&self, unit: &str) -> Result<UnitStatus, Error> {
+        let procs = self.procs.lock().unwrap();
+        let status = match procs.get(unit) {
+            // waiting for restart
+            Some(process) if process.restart_at.is_some() => UnitStatus {
+                state: UnitState::Activating,
+                pid: 0,
+                restarts: process.restarts,
+            },
+            Some(process) => UnitStatus {
+                state: UnitState::Active,
+                pid: process.pid,
+                restarts: process.restarts,
+            },
+            None => UnitStatus {
+                state: UnitState::Inactive,
+                pid: 0,
+                restarts: 0,
+            },
+        };
+        Ok(status)
+    }
+}
+
+// check the process and restart it by policy, return false if it should be forgotten.
+fn poll(run_dir: &Path, unit: &str, process: &mut Process) -> bool {
+    if let Some(at) = process.restart_at {
+        if Instant::now() < at {
+            return true;
+        }
+        match spawn(run_dir, unit, process.spec.clone()) {
+            Ok(mut restarted) => {
+                info!("restart unit {} with pid {}", unit, restarted.pid);
+                restarted.restarts = process.restarts + 1;
+                *process = restarted;
+            }
+            Err(err) => {
+                warn!("fail to restart unit {} due {}", unit, err);
+                process.restart_at = Some(Instant::now() + process.spec.restart_sec);
+            }
+        }
+        return true;
+    }
+
+    // the exit status of adopted process is unknown, treat it as failure
+    let success = match process.child.as_mut() {
+        Some(child) => match child.try_wait() {
+            Ok(Some(status)) => status.success(),
+            Ok(None) => return true,
+            Err(err) => {
+                warn!("fail to wait unit {} due {}", unit, err);
+                return true;
+            }
+        },
+        None if is_alive(process.pid, &process.spec) => return true,
+        None => false,
+    };
+
+    let restart = match process.spec.restart {
+        RestartPolicy::Always => true,
+        RestartPolicy::OnFailure => !success,
+        RestartPolicy::No => false,
+    };
+    warn!(
+        "process {} of unit {} exited with success {}, restart {}",
+        process.pid, unit, success, restart
+    );
+    if restart {
+        process.restart_at = Some(Instant::now() + process.spec.restart_sec);
+        return true;
+    }
+    remove_pid_file(run_dir, unit);
+    false
+}
+
+fn spawn(run_dir: &Path, unit: &str, spec: ExecSpec) -> Result<Process, Error> {
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path(run_dir, unit))?;
+    let mut cmd = Command::new(&spec.argv[0]);
+    cmd.args(&spec.argv[1..])
+        .envs(spec.envs.iter().cloned())
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(log.try_clone()?))
+        .stderr(Stdio::from(log));
+    if let Some(dir) = spec.dir.as_ref() {
+        cmd.current_dir(dir);
+    }
+
+    let child = cmd
+        .spawn()
+        .map_err(|err| format_err!("fail to exec {:?} due {}", spec.argv, err))?;
+    let pid = child.id();
+    fs::write(run_dir.join(format!("{}.pid", unit)), format!("{}\n", pid))?;
+    info!("spawn unit {} with pid {}", unit, pid);
+    Ok(Process {
+        spec,
+        pid,
+        child: Some(child),
+        restart_at: None,
+        restarts: 0,
+    })
+}
+
+fn parse_unit(unit: &str) -> Result<ExecSpec, Error> {
+    let path = Path::new(UNIT_DIR).join(unit);
+    let content = fs::read_to_string(&path)
+        .map_err(|err| format_err!("fail to read unit {:?} due {}", path, err))?;
+    parse_service(&content).ok_or_else(|| format_err!("no ExecStart in unit {:?}", path))
+}
+
+fn parse_service(content: &str) -> Option<ExecSpec> {
+    let mut spec = ExecSpec {
+        argv: Vec::new(),
+        dir: None,
+        envs: Vec::new(),
+        restart: RestartPolicy::No,
+        restart_sec: DEFAULT_RESTART_SEC,
+    };
+
+    let content = content.replace("\\\n", " ");
+    let mut in_service = false;
+    for line in content.lines().map(|x| x.trim()) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_service = line == "[Service]";
+            continue;
+        }
+        let pos = match line.find('=') {
+            Some(pos) if in_service => pos,
+            _ => continue,
+        };
+        let (key, value) = (line[..pos].trim(), line[pos + 1..].trim());
+        match key {
+            // the prefixes of special executable are ignored
+            "ExecStart" => {
+                spec.argv = split_args(value.trim_start_matches(|c| "-@+!:".contains(c)))
+            }
+            "WorkingDirectory" => spec.dir = Some(value.trim_start_matches('-').to_string()),
+            "Environment" => {
+                for env in split_args(value) {
+                    if let Some(pos) = env.find('=') {
+                        spec.envs
+                            .push((env[..pos].to_string(), env[pos + 1..].to_string()));
+                    }
+                }
+            }
+            "Restart" => {
+                spec.restart = match value {
+                    "no" => RestartPolicy::No,
+                    "always" => RestartPolicy::Always,
+                    _ => RestartPolicy::OnFailure,
+                }
+            }
+            "RestartSec" => {
+                if let Ok(secs) = value.trim_end_matches('s').parse::<f64>() {
+                    spec.restart_sec = Duration::from_millis((secs * 1000.0) as u64);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if spec.argv.is_empty() {
+        return None;
+    }
+    Some(spec)
+}
+
+// split by whitespace, the double quoted ones are kept as one.
+fn split_args(value: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(current.clone());
+                    current.clear();
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+// the pid may be reused, so it's cmdline must contain the name of executable.
+fn is_alive(pid: u32, spec: &ExecSpec) -> bool {
+    let cmdline = match fs::read(format!("/proc/{}/cmdline", pid)) {
+        Ok(cmdline) => String::from_utf8_lossy(&cmdline).to_string(),
+        Err(_) => return false,
+    };
+    Path::new(&spec.argv[0])
+        .file_name()
+        .map(|name| cmdline.contains(&*name.to_string_lossy()))
+        .unwrap_or(false)
+}
+
+fn signal(pid: u32, sig: &str) {
+    let rslt = Command::new("kill")
+        .arg(format!("-{}", sig))
+        .arg(format!("{}", pid))
+        .status();
+    if let Err(err) = rslt {
+        warn!("fail to send SIG{} to {} due {}", sig, pid, err);
+    }
+}
+
+fn log_path(run_dir: &Path, unit: &str) -> PathBuf {
+    run_dir.join(format!("{}.log", unit))
+}
+
+fn remove_pid_file(run_dir: &Path, unit: &str) {
+    let _ = fs::remove_file(run_dir.join(format!("{}.pid", unit)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_exec_start() {
+        let unit = r#"[Unit]
+Description=cache mycluster 7000
+ExecStart=/bin/false
+
+[Service]
+Type=simple
+# comment
+; comment
+ExecStart=-/data/haste/lib/redis/4.0.11/redis-server \
+    /data/cache/7000/redis.conf --loglevel "verbose warning"
+WorkingDirectory=-/data/cache/7000
+Environment="LANG=C" MALLOC_ARENA_MAX=2
+Restart=always
+RestartSec=2.5s
+
+[Install]
+WantedBy=multi-user.target
+"#;
+        let spec = parse_service(unit).unwrap();
+        assert_eq!(
+            spec.argv,
+            vec![
+                "/data/haste/lib/redis/4.0.11/redis-server",
+                "/data/cache/7000/redis.conf",
+                "--loglevel",
+                "verbose warning",
+            ]
+        );
+        assert_eq!(spec.dir.as_deref(), Some("/data/cache/7000"));
+        assert_eq!(
+            spec.envs,
+            vec![
+                ("LANG".to_string(), "C".to_string()),
+                ("MALLOC_ARENA_MAX".to_string(), "2".to_string()),
+            ]
+        );
+        assert_eq!(spec.restart, RestartPolicy::Always);
+        assert_eq!(spec.restart_sec, Duration::from_millis(2500));
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let spec = parse_service("[Service]\nExecStart=/usr/bin/memcached -p 7000\n").unwrap();
+        assert_eq!(spec.argv, vec!["/usr/bin/memcached", "-p", "7000"]);
+        assert_eq!(spec.dir, None);
+        assert_eq!(spec.restart, RestartPolicy::No);
+        assert_eq!(spec.restart_sec, DEFAULT_RESTART_SEC);
+        assert_eq!(
+            parse_service("[Service]\nRestart=on-failure\nExecStart=/bin/true")
+                .unwrap()
+                .restart,
+            RestartPolicy::OnFailure
+        );
+    }
+
+    #[test]
+    fn no_exec_start() {
+        assert!(parse_service("").is_none());
+        assert!(parse_service("[Unit]\nExecStart=/bin/true\n").is_none());
+        assert!(parse_service("[Service]\nExecStart=\n").is_none());
+    }
+
+    #[test]
+    fn split_quoted_args() {
+        assert_eq!(split_args("a  b\tc"), vec!["a", "b", "c"]);
+        assert_eq!(split_args(r#"a "b c" d"#), vec!["a", "b c", "d"]);
+        assert_eq!(split_args(r#"--name="my cache""#), vec!["--name=my cache"]);
+        assert_eq!(split_args(r#""""#), Vec::<String>::new());
+        assert_eq!(split_args("  "), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,88 @@
+//! in-memory supervisor which runs nothing, the calls are recorded for tests.
+
+use crate::proto::UnitState;
+use crate::supervisor::{Supervisor, UnitStatus};
+
+use failure::{format_err, Error};
+use log::info;
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+#[derive(Default)]
+pub struct MockSupervisor {
+    // (action, unit) in calling order, the unit of reload is empty
+    calls: Mutex<Vec<(String, String)>>,
+    running: Mutex<HashSet<String>>,
+    failing: Mutex<HashSet<String>>,
+}
+
+impl MockSupervisor {
+    /// the start and restart of unit will fail.
+    pub fn fail_on(&self, unit: &str) {
+        self.failing.lock().unwrap().insert(unit.to_string());
+    }
+
+    pub fn calls(&self) -> Vec<(String, String)> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    pub fn is_running(&self, unit: &str) -> bool {
+        self.running.lock().unwrap().contains(unit)
+    }
+
+    fn record(&self, action: &str, unit: &str) {
+        info!("mock supervisor {} {}", action, unit);
+        self.calls
+            .lock()
+            .unwrap()
+            .push((action.to_string(), unit.to_string()));
+    }
+
+    fn run(&self, unit: &str) -> Result<(), Error> {
+        if self.failing.lock().unwrap().contains(unit) {
+            self.running.lock().unwrap().remove(unit);
+            return Err(format_err!("mock unit {} failed", unit));
+        }
+        self.running.lock().unwrap().insert(unit.to_string());
+        Ok(())
+    }
+}
+
+impl Supervisor for MockSupervisor {
+    fn reload(&self) -> Result<(), Error> {
+        self.record("reload", "");
+        Ok(())
+    }
+
+    fn start(&self, unit: &str) -> Result<(), Error> {
+        self.record("start", unit);
+        self.run(unit)
+    }
+
+    fn stop(&self, unit: &str) -> Result<(), Error> {
+        self.record("stop", unit);
+        self.running.lock().unwrap().remove(unit);
+        Ok(())
+    }
+
+    fn restart(&self, unit: &str) -> Result<(), Error> {
+        self.record("restart", unit);
+        self.run(unit)
+    }
+
+    fn status(&self, unit: &str) -> Result<UnitStatus, Error> {
+        let state = if self.is_running(unit) {
+            UnitState::Active
+        } else if self.failing.lock().unwrap().contains(unit) {
+            UnitState::Failed
+        } else {
+            UnitState::Inactive
+        };
+        Ok(UnitStatus {
+            state,
+            pid: 0,
+            restarts: 0,
+        })
+    }
+}
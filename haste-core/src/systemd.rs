@@ -1,34 +1,155 @@
-use crate::proto::SystemdAction;
+//! systemd units of cache instances.
+//!
+//! each instance runs as `cache-{cluster}-{port}.service`, the cluster is escaped like
+//! `systemd-escape` and the port is always the last `-` separated part, so the units of a
+//! cluster can be listed from the names in `UNIT_DIR` without reading them. the units of old
+//! versions are named `cache-{port}.service`, they are still found by port and renamed when the
+//! instance was deployed or converged again.
+//!
+//! the units are run by a `Supervisor`. `SystemdSupervisor` runs them by systemctl, a failed
+//! systemctl is returned as `SystemctlError` with it's output, and `start`/`restart` are done
+//! only after the unit becomes `active` in `ACTIVE_TIMEOUT`.
 
-use failure::{format_err, Error};
+use crate::layout::UNIT_DIR;
+use crate::proto::{SystemdAction, UnitState};
+use crate::supervisor::{Supervisor, UnitStatus};
+
+use failure::{format_err, Error, Fail};
 use log::{info, warn};
 
 use std::fs;
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 
-const SYSTEMD_DIR: &str = "/etc/systemd/system/";
+const UNIT_PREFIX: &str = "cache-";
+const UNIT_SUFFIX: &str = ".service";
+const ACTIVE_TIMEOUT: Duration = Duration::from_secs(30);
+const ACTIVE_INTERVAL: Duration = Duration::from_millis(500);
 
-pub fn service_name(port: i64) -> String {
-    format!("cache-{}.service", port)
+#[derive(Debug, Fail)]
+#[fail(
+    display = "systemctl {} exit with code {:?}, stdout: {} stderr: {}",
+    args, code, stdout, stderr
+)]
+pub struct SystemctlError {
+    pub args: String,
+    // None if systemctl was killed by signal
+    pub code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
 }
 
-pub fn do_action(action: SystemdAction, port: i64) -> Result<(), Error> {
-    match action {
-        SystemdAction::Setup => reload(),
-        SystemdAction::Remove => {
-            let sname = service_name(port);
-            if let Err(err) = do_action(SystemdAction::Stop, port) {
-                warn!("fail to stop cache-service {} due error {:?}", sname, err);
-            }
+/// the unit of an instance found in `UNIT_DIR`.
+#[derive(Clone, Debug)]
+pub struct Unit {
+    pub name: String,
+    // None for the legacy `cache-{port}.service`
+    pub cluster: Option<String>,
+    pub port: i64,
+}
+
+pub fn service_name(cluster: &str, port: i64) -> String {
+    format!("{}{}-{}{}", UNIT_PREFIX, escape(cluster), port, UNIT_SUFFIX)
+}
+
+pub fn legacy_service_name(port: i64) -> String {
+    format!("{}{}{}", UNIT_PREFIX, port, UNIT_SUFFIX)
+}
+
+/// parse the unit name into (cluster, port), the cluster is None for legacy units.
+pub fn parse_service_name(name: &str) -> Option<(Option<String>, i64)> {
+    if !name.starts_with(UNIT_PREFIX) || !name.ends_with(UNIT_SUFFIX) {
+        return None;
+    }
+    let body = &name[UNIT_PREFIX.len()..name.len() - UNIT_SUFFIX.len()];
+    match body.rfind('-') {
+        Some(pos) => {
+            let port = body[pos + 1..].parse().ok()?;
+            Some((Some(unescape(&body[..pos])?), port))
+        }
+        None => Some((None, body.parse().ok()?)),
+    }
+}
 
-            let mut pb = PathBuf::from(SYSTEMD_DIR);
-            pb.push(&sname);
+/// all the units of instances in `UNIT_DIR`, only the names are read.
+pub fn list_units() -> Result<Vec<Unit>, Error> {
+    let entries = match fs::read_dir(UNIT_DIR) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut units = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if let Some((cluster, port)) = parse_service_name(&name) {
+            units.push(Unit {
+                name,
+                cluster,
+                port,
+            });
+        }
+    }
+    units.sort_by_key(|x| x.port);
+    Ok(units)
+}
+
+/// ports of all the instances of cluster on this host.
+pub fn cluster_ports(cluster: &str) -> Result<Vec<i64>, Error> {
+    Ok(list_units()?
+        .into_iter()
+        .filter(|x| x.cluster.as_ref().map(|c| c == cluster).unwrap_or(false))
+        .map(|x| x.port)
+        .collect())
+}
+
+/// the name of the unit which runs on port, prefer the new named one if both exist.
+pub fn unit_of(port: i64) -> Result<String, Error> {
+    let units: Vec<_> = list_units()?
+        .into_iter()
+        .filter(|x| x.port == port)
+        .collect();
+    units
+        .iter()
+        .find(|x| x.cluster.is_some())
+        .or_else(|| units.first())
+        .map(|x| x.name.clone())
+        .ok_or_else(|| format_err!("no unit of instance {} in {}", port, UNIT_DIR))
+}
 
-            if let Err(err) = fs::remove_file(pb.as_path()) {
-                warn!("fail to delete cache service file {} due {}", sname, err);
+/// stop and remove the other units of port than the one named by cluster, they are left by
+/// old versions or the instance was moved from another cluster. return true if any removed.
+pub fn retire_units(supervisor: &dyn Supervisor, cluster: &str, port: i64) -> Result<bool, Error> {
+    let name = service_name(cluster, port);
+    let mut retired = false;
+    for unit in list_units()? {
+        if unit.port != port || unit.name == name {
+            continue;
+        }
+        info!("retire unit {} in favor of {}", unit.name, name);
+        remove_unit(supervisor, &unit.name);
+        retired = true;
+    }
+    if retired {
+        supervisor.reload()?;
+    }
+    Ok(retired)
+}
+
+pub fn do_action(
+    supervisor: &dyn Supervisor,
+    action: SystemdAction,
+    port: i64,
+) -> Result<(), Error> {
+    match action {
+        SystemdAction::Setup => supervisor.reload(),
+        SystemdAction::Remove => {
+            match unit_of(port) {
+                Ok(sname) => remove_unit(supervisor, &sname),
+                Err(err) => warn!("skip remove cache service due {}", err),
             }
-            do_action(SystemdAction::Setup, port)
+            supervisor.reload()
         }
         _ => {
             if port < 0 {
@@ -38,30 +159,172 @@ pub fn do_action(action: SystemdAction, port: i64) -> Result<(), Error> {
                     port
                 ));
             }
-            do_systemd(action_to_str(action), port)
+            let sname = unit_of(port).unwrap_or_else(|_| legacy_service_name(port));
+            match action {
+                SystemdAction::Restart => supervisor.restart(&sname),
+                SystemdAction::Start => supervisor.start(&sname),
+                SystemdAction::Stop => supervisor.stop(&sname),
+                _ => unreachable!(),
+            }
         }
     }
 }
 
-fn action_to_str(action: SystemdAction) -> &'static str {
-    match action {
-        SystemdAction::Restart => "restart",
-        SystemdAction::Start => "start",
-        SystemdAction::Stop => "stop",
-        _ => unreachable!(),
+/// run the units by systemctl.
+pub struct SystemdSupervisor;
+
+impl Supervisor for SystemdSupervisor {
+    fn reload(&self) -> Result<(), Error> {
+        call_systemd(&["daemon-reload"; 1])
+    }
+
+    fn start(&self, unit: &str) -> Result<(), Error> {
+        call_systemd(&["start", unit])?;
+        wait_active(unit)
+    }
+
+    fn stop(&self, unit: &str) -> Result<(), Error> {
+        call_systemd(&["stop", unit])
+    }
+
+    fn restart(&self, unit: &str) -> Result<(), Error> {
+        call_systemd(&["restart", unit])?;
+        wait_active(unit)
+    }
+
+    fn status(&self, unit: &str) -> Result<UnitStatus, Error> {
+        let stdout = capture_systemd(&["show", unit, "--property=ActiveState,MainPID,NRestarts"])?;
+        let mut status = UnitStatus {
+            state: UnitState::Unknown,
+            pid: 0,
+            restarts: 0,
+        };
+        for line in stdout.lines() {
+            let mut kv = line.splitn(2, '=');
+            match (kv.next(), kv.next()) {
+                (Some("ActiveState"), Some(value)) => status.state = as_unit_state(value),
+                (Some("MainPID"), Some(value)) => status.pid = value.parse().unwrap_or(0),
+                // NRestarts is missing before systemd 235
+                (Some("NRestarts"), Some(value)) => status.restarts = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        Ok(status)
     }
 }
 
-fn do_systemd(cmd: &str, port: i64) -> Result<(), Error> {
-    let args = [cmd, &service_name(port)];
-    call_systemd(&args)
+fn as_unit_state(state: &str) -> UnitState {
+    match state {
+        "active" | "reloading" => UnitState::Active,
+        "activating" => UnitState::Activating,
+        "inactive" | "deactivating" => UnitState::Inactive,
+        "failed" => UnitState::Failed,
+        _ => UnitState::Unknown,
+    }
 }
 
-fn reload() -> Result<(), Error> {
-    call_systemd(&["daemon-reload"; 1])
+fn remove_unit(supervisor: &dyn Supervisor, sname: &str) {
+    if let Err(err) = supervisor.stop(sname) {
+        warn!("fail to stop cache-service {} due error {:?}", sname, err);
+    }
+
+    let mut pb = PathBuf::from(UNIT_DIR);
+    pb.push(sname);
+
+    if let Err(err) = fs::remove_file(pb.as_path()) {
+        warn!("fail to delete cache service file {} due {}", sname, err);
+    }
+}
+
+// escape like systemd-escape, '-' is kept since the port is always the last part.
+fn escape(cluster: &str) -> String {
+    let mut escaped = String::new();
+    for (i, b) in cluster.bytes().enumerate() {
+        let c = b as char;
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == ':' || (c == '.' && i > 0) {
+            escaped.push(c);
+        } else {
+            escaped.push_str(&format!("\\x{:02x}", b));
+        }
+    }
+    escaped
+}
+
+fn unescape(escaped: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut rest = escaped.as_bytes();
+    while !rest.is_empty() {
+        if rest.starts_with(b"\\x") && rest.len() >= 4 {
+            let hex = std::str::from_utf8(&rest[2..4]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &rest[4..];
+        } else {
+            bytes.push(rest[0]);
+            rest = &rest[1..];
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// poll `systemctl is-active` until the unit is active, a failed unit is reported with
+// it's status which contains the last lines of journal.
+fn wait_active(sname: &str) -> Result<(), Error> {
+    let deadline = Instant::now() + ACTIVE_TIMEOUT;
+    loop {
+        // is-active exit with non-zero if the unit is not active, so the output is used
+        let output = run_systemctl(&["is-active", sname])?;
+        let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        match state.as_str() {
+            "active" => return Ok(()),
+            "failed" => {
+                return Err(format_err!(
+                    "unit {} failed after start, status: {}",
+                    sname,
+                    unit_status(sname)
+                ))
+            }
+            _ => {}
+        }
+        if Instant::now() >= deadline {
+            return Err(format_err!(
+                "unit {} is still {} after {:?}, status: {}",
+                sname,
+                state,
+                ACTIVE_TIMEOUT,
+                unit_status(sname)
+            ));
+        }
+        thread::sleep(ACTIVE_INTERVAL);
+    }
+}
+
+fn unit_status(sname: &str) -> String {
+    match run_systemctl(&["status", "--no-pager", "--lines=10", sname]) {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(err) => format!("unknown due {}", err),
+    }
 }
 
 fn call_systemd(args: &[&str]) -> Result<(), Error> {
+    capture_systemd(args).map(|_| ())
+}
+
+// run systemctl and return it's stdout if succeeded.
+fn capture_systemd(args: &[&str]) -> Result<String, Error> {
+    let output = run_systemctl(args)?;
+    if !output.status.success() {
+        return Err(SystemctlError {
+            args: args.join(" "),
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn run_systemctl(args: &[&str]) -> Result<Output, Error> {
     let child = Command::new("systemctl")
         .args(args)
         .stdout(Stdio::piped())
@@ -75,5 +338,88 @@ fn call_systemd(args: &[&str]) -> Result<(), Error> {
         String::from_utf8_lossy(&output.stdout),
         String::from_utf8_lossy(&output.stderr)
     );
-    Ok(())
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supervisor::mock::MockSupervisor;
+
+    #[test]
+    fn escape_round_trip() {
+        for cluster in &[
+            "mycluster",
+            "my-cluster_1",
+            "a.b",
+            ".hidden",
+            "with space",
+            "a/b",
+            "集群",
+        ] {
+            let escaped = escape(cluster);
+            assert!(
+                !escaped.contains('/') && !escaped.contains(' '),
+                "{}",
+                escaped
+            );
+            assert_eq!(unescape(&escaped).as_deref(), Some(*cluster));
+        }
+        assert_eq!(escape(".a.b"), "\\x2ea.b");
+        assert_eq!(escape("a b"), "a\\x20b");
+    }
+
+    #[test]
+    fn service_name_round_trip() {
+        for (cluster, port) in &[("mycluster", 7000), ("my-cluster", 7001), ("a b", 65535)] {
+            let name = service_name(cluster, *port);
+            assert_eq!(
+                parse_service_name(&name),
+                Some((Some(cluster.to_string()), *port))
+            );
+        }
+        assert_eq!(
+            service_name("my-cluster", 7000),
+            "cache-my-cluster-7000.service"
+        );
+    }
+
+    #[test]
+    fn parse_legacy_and_bad_names() {
+        assert_eq!(
+            parse_service_name(&legacy_service_name(7000)),
+            Some((None, 7000))
+        );
+        assert_eq!(parse_service_name("cache-7000.service"), Some((None, 7000)));
+        assert_eq!(parse_service_name("redis-7000.service"), None);
+        assert_eq!(parse_service_name("cache-7000.socket"), None);
+        assert_eq!(parse_service_name("cache-abc.service"), None);
+        assert_eq!(parse_service_name("cache-my-cluster.service"), None);
+        assert_eq!(parse_service_name("cache-bad\\xzz-7000.service"), None);
+    }
+
+    #[test]
+    fn do_action_by_supervisor() {
+        let supervisor = MockSupervisor::default();
+        // there is no unit of the port, so the legacy name is used
+        let port = 1;
+        let unit = legacy_service_name(port);
+        do_action(&supervisor, SystemdAction::Setup, -1).unwrap();
+        do_action(&supervisor, SystemdAction::Start, port).unwrap();
+        assert!(supervisor.is_running(&unit));
+        do_action(&supervisor, SystemdAction::Stop, port).unwrap();
+        assert!(!supervisor.is_running(&unit));
+
+        supervisor.fail_on(&unit);
+        assert!(do_action(&supervisor, SystemdAction::Restart, port).is_err());
+        assert_eq!(supervisor.status(&unit).unwrap().state, UnitState::Failed);
+        assert!(do_action(&supervisor, SystemdAction::Start, -1).is_err());
+
+        let calls: Vec<_> = supervisor
+            .calls()
+            .into_iter()
+            .map(|(action, _)| action)
+            .collect();
+        assert_eq!(calls, vec!["reload", "start", "stop", "restart"]);
+    }
 }
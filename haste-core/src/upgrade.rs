@@ -0,0 +1,370 @@
+//! rolling upgrade of the version of a redis cluster:
+//!   1. upgrade the slaves, one instance per host in each round
+//!   2. fail over each master to it's upgraded slave and upgrade the old master, which is a
+//!      slave now, one master per host in each round
+//!   3. save the version of cluster
+//!
+//! an instance is upgraded by converging it with the unit of new version, so the agent
+//! installs the binary and restarts it. the instances which are already in the version are
+//! skipped, so a failed upgrade can be run again. the cluster must be healthy after each
+//! round, or the upgrade stops with the problems.
+
+use crate::deploy::plan::parse_cache_type;
+use crate::deploy::server::{
+    connect_agent, version_problem, DeployParm, InvalidParm, Template, FILE_SERVER_KEY,
+};
+use crate::job::{gen_job_id, spawn_job, ClusterLock, Job};
+use crate::layout::InstanceLayout;
+use crate::myetcd::MyEtcd;
+use crate::myredis::MyRedis;
+use crate::offer::get_capacity;
+use crate::proto::{CacheInfo, CacheType, Instance, State};
+use crate::stats::{cluster_instances, wait_healthy, RedisInfo, SlaveInfo};
+
+use failure::{format_err, Error, Fail};
+use log::info;
+use redis::FromRedisValue;
+
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// seconds to wait for the cluster to be healthy after each round, the slaves may need a
+// full sync after restarted
+pub const HEALTH_TIMEOUT: u64 = 600;
+const FAILOVER_TIMEOUT: u64 = 60;
+// the slave to fail over to must be acked in seconds and behind it's master in bytes at most
+const MAX_ACK_LAG: u64 = 10;
+const MAX_OFFSET_LAG: u64 = 1024 * 1024;
+const CHECK_INTERVAL: u64 = 2;
+
+#[derive(Debug, Fail)]
+pub enum UpgradeError {
+    #[fail(display = "cluster {} is not found", _0)]
+    NotFound(String),
+    #[fail(
+        display = "cluster {} is {}, only redis_cluster can be upgraded",
+        _0, _1
+    )]
+    Unsupported(String, String),
+}
+
+/// upgrade the cluster to version as a job. the cluster is locked until the job is finished,
+/// so it's a `ClusterBusy` if another job is running on it.
+pub fn spawn_upgrade(etcd: &str, name: &str, version: &str) -> Result<Job, Error> {
+    let myetcd = MyEtcd::open(etcd)?;
+    let base = format!("/haste/clusters/{}", name);
+    let cache_type = myetcd
+        .get_value(&format!("{}/cache_type", base))?
+        .ok_or_else(|| UpgradeError::NotFound(name.to_string()))?;
+    if parse_cache_type(&cache_type)? != CacheType::RedisCluster {
+        return Err(UpgradeError::Unsupported(name.to_string(), cache_type).into());
+    }
+    if let Some(problem) = version_problem(&myetcd, CacheType::RedisCluster, version)? {
+        return Err(InvalidParm {
+            name: name.to_string(),
+            problems: vec![problem],
+        }
+        .into());
+    }
+
+    let job = Job::new(&gen_job_id(name), "upgrade", name);
+    let lock = ClusterLock::acquire(&myetcd, etcd, name, "upgrade")?;
+
+    let etcd_addr = etcd.to_string();
+    let (name, version, job_id) = (name.to_string(), version.to_string(), job.id.clone());
+    spawn_job(&myetcd, etcd, job, move || {
+        let _lock = lock;
+        MyEtcd::open(&etcd_addr)
+            .and_then(|myetcd| Upgrade::new(myetcd, &name, &version, &job_id))
+            .and_then(|upgrade| upgrade.run())
+    })
+}
+
+struct Upgrade {
+    myetcd: MyEtcd,
+    job_id: String,
+    file_server: String,
+    template: Template,
+    // the param of new version, only used to render the files
+    param: DeployParm,
+}
+
+impl Upgrade {
+    fn new(myetcd: MyEtcd, name: &str, version: &str, job_id: &str) -> Result<Upgrade, Error> {
+        let tpl_name = myetcd
+            .get_value(&format!("/haste/clusters/{}/template", name))?
+            .ok_or_else(|| format_err!("template of cluster {} is not found", name))?;
+        let template = Template::load(&myetcd, CacheType::RedisCluster, &tpl_name)?;
+        let file_server = myetcd.get_value(FILE_SERVER_KEY)?.unwrap_or_default();
+        let param = DeployParm {
+            name: name.to_string(),
+            cpu_percent: 0,
+            max_memory: 0,
+            total_memory: 0,
+            version: version.to_string(),
+            tpl_name,
+            cache_type: CacheType::RedisCluster,
+            appids: String::new(),
+            group: String::new(),
+            dial_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+        };
+        Ok(Upgrade {
+            myetcd,
+            job_id: job_id.to_string(),
+            file_server,
+            template,
+            param,
+        })
+    }
+
+    fn run(&self) -> Result<(), Error> {
+        let name = self.param.name.clone();
+        let addrs = cluster_instances(&self.myetcd, &name)?;
+        if addrs.is_empty() {
+            return Err(format_err!("cluster {} has no instances", name));
+        }
+        // never start with a broken cluster
+        wait_healthy(&name, &addrs, 0)?;
+
+        let (mut masters, mut slaves) = (Vec::new(), Vec::new());
+        for addr in &addrs {
+            let info = redis_info(addr)?;
+            if info.version == self.param.version {
+                info!("skip {} which is already in {}", addr, info.version);
+            } else if info.role == "master" {
+                masters.push(addr.clone());
+            } else {
+                slaves.push(addr.clone());
+            }
+        }
+        info!(
+            "start to upgrade {} slaves and {} masters of cluster {} to {}",
+            slaves.len(),
+            masters.len(),
+            name,
+            self.param.version
+        );
+
+        for round in rounds(&slaves) {
+            for addr in &round {
+                self.converge(addr)?;
+            }
+            wait_healthy(&name, &addrs, HEALTH_TIMEOUT)?;
+        }
+        for round in rounds(&masters) {
+            for addr in &round {
+                self.failover(addr)?;
+            }
+            for addr in &round {
+                self.converge(addr)?;
+            }
+            wait_healthy(&name, &addrs, HEALTH_TIMEOUT)?;
+        }
+
+        self.myetcd.set(
+            &format!("/haste/clusters/{}/version", name),
+            &self.param.version,
+        )?;
+        info!("cluster {} is upgraded to {}", name, self.param.version);
+        Ok(())
+    }
+
+    // converge the instance with the files of new version, the agent restarts it since the
+    // unit is changed.
+    fn converge(&self, addr: &str) -> Result<(), Error> {
+        let (host, port) = split_addr(addr)?;
+        let layout = get_capacity(&self.myetcd, host)?
+            .map(|offer| InstanceLayout::new(&offer.data_root))
+            .unwrap_or_default();
+        let files = self.template.render_redis(&layout, port, &self.param)?;
+
+        let mut inst = Instance::new();
+        inst.set_port(port as i64);
+        inst.set_files(files.into());
+        let mut ci = CacheInfo::new();
+        ci.set_job_id(self.job_id.clone());
+        ci.set_cache_type(CacheType::RedisCluster);
+        ci.set_cluster(self.param.name.clone());
+        ci.set_version(self.param.version.clone());
+        ci.set_file_server(self.file_server.clone());
+        ci.set_converge(true);
+        ci.set_insts(vec![inst].into());
+
+        info!("upgrade {} to {}", addr, self.param.version);
+        let state = connect_agent(&self.myetcd, host)?.deploy(&ci)?;
+        if state.get_state() == State::Error {
+            return Err(format_err!(
+                "fail to upgrade {} due {}",
+                addr,
+                state.get_msg()
+            ));
+        }
+        Ok(())
+    }
+
+    // fail over the master to it's slave and wait until their roles are switched.
+    fn failover(&self, master: &str) -> Result<(), Error> {
+        let slave = pick_slave(&redis_info(master)?)
+            .map(|x| x.addr.clone())
+            .ok_or_else(|| format_err!("master {} has no caught up slave to fail over", master))?;
+        info!("fail over master {} to {}", master, slave);
+        let _: () = execute(&slave, "CLUSTER FAILOVER")?;
+
+        let start = Instant::now();
+        loop {
+            thread::sleep(Duration::from_secs(CHECK_INTERVAL));
+            if redis_info(&slave)?.role == "master" && redis_info(master)?.role == "slave" {
+                break;
+            }
+            if start.elapsed() > Duration::from_secs(FAILOVER_TIMEOUT) {
+                return Err(format_err!(
+                    "fail over {} to {} is not done in {}s",
+                    master,
+                    slave,
+                    FAILOVER_TIMEOUT
+                ));
+            }
+        }
+
+        let master_id: String = execute(&slave, "CLUSTER MYID")?;
+        let base = format!("/haste/clusters/{}/instances", self.param.name);
+        self.myetcd
+            .set(&format!("{}/{}/role", base, slave), "master")?;
+        self.myetcd
+            .set(&format!("{}/{}/slaveof", base, slave), "-")?;
+        self.myetcd
+            .set(&format!("{}/{}/role", base, master), "slave")?;
+        self.myetcd
+            .set(&format!("{}/{}/slaveof", base, master), &master_id)?;
+        Ok(())
+    }
+}
+
+fn redis_info(addr: &str) -> Result<RedisInfo, Error> {
+    let text: String = execute(addr, "INFO ALL")?;
+    Ok(RedisInfo::parse(&text))
+}
+
+fn execute<T: FromRedisValue>(addr: &str, cmd: &str) -> Result<T, Error> {
+    MyRedis::open(&[addr.to_string()])?.execute(addr, cmd)
+}
+
+// the online slave which is the least behind it's master.
+fn pick_slave(master: &RedisInfo) -> Option<&SlaveInfo> {
+    master
+        .slaves
+        .iter()
+        .filter(|x| {
+            x.state == "online"
+                && x.lag <= MAX_ACK_LAG
+                && master.repl_offset.saturating_sub(x.offset) <= MAX_OFFSET_LAG
+        })
+        .max_by_key(|x| x.offset)
+}
+
+/// {ip}:{port} -> (ip, port)
+pub fn split_addr(addr: &str) -> Result<(&str, usize), Error> {
+    let pos = addr
+        .rfind(':')
+        .ok_or_else(|| format_err!("bad instance {}", addr))?;
+    let port = addr[pos + 1..]
+        .parse()
+        .map_err(|_| format_err!("bad port of instance {}", addr))?;
+    Ok((&addr[..pos], port))
+}
+
+// split the instances into rounds, each round has one instance of each host at most.
+fn rounds(addrs: &[String]) -> Vec<Vec<String>> {
+    let mut hosts: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for addr in addrs {
+        let host = addr.rfind(':').map(|pos| &addr[..pos]).unwrap_or("");
+        hosts.entry(host).or_default().push(addr.clone());
+    }
+
+    let mut rounds = Vec::new();
+    for i in 0.. {
+        let round: Vec<_> = hosts.values().filter_map(|x| x.get(i).cloned()).collect();
+        if round.is_empty() {
+            break;
+        }
+        rounds.push(round);
+    }
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slave(addr: &str, state: &str, offset: u64, lag: u64) -> SlaveInfo {
+        SlaveInfo {
+            addr: addr.to_string(),
+            state: state.to_string(),
+            offset,
+            lag,
+        }
+    }
+
+    #[test]
+    fn pick_caught_up_slave() {
+        let mut master = RedisInfo {
+            role: "master".to_string(),
+            repl_offset: 10_000_000,
+            ..RedisInfo::default()
+        };
+        assert!(pick_slave(&master).is_none());
+
+        master.slaves = vec![
+            slave("10.0.0.1:7001", "wait_bgsave", 0, 0),
+            slave("10.0.0.2:7001", "online", 9_999_000, 1),
+            slave("10.0.0.3:7001", "online", 9_999_900, 0),
+            slave("10.0.0.4:7001", "online", 10_000_000, 30),
+        ];
+        assert_eq!(pick_slave(&master).unwrap().addr, "10.0.0.3:7001");
+
+        master.slaves = vec![
+            slave("10.0.0.1:7001", "send_bulk", 10_000_000, 0),
+            slave("10.0.0.2:7001", "online", 1_000, 0),
+        ];
+        assert!(pick_slave(&master).is_none());
+    }
+
+    #[test]
+    fn split_addrs() {
+        assert_eq!(split_addr("10.0.0.1:7000").unwrap(), ("10.0.0.1", 7000));
+        assert_eq!(split_addr("[::1]:7000").unwrap(), ("[::1]", 7000));
+        assert!(split_addr("10.0.0.1").is_err());
+        assert!(split_addr("10.0.0.1:port").is_err());
+        assert!(split_addr("10.0.0.1:").is_err());
+    }
+
+    #[test]
+    fn one_instance_of_host_in_round() {
+        let addrs: Vec<_> = [
+            "10.0.0.1:7000",
+            "10.0.0.1:7001",
+            "10.0.0.1:7002",
+            "10.0.0.2:7000",
+            "10.0.0.3:7000",
+            "10.0.0.3:7001",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let rounds = rounds(&addrs);
+        assert_eq!(rounds.len(), 3);
+        for round in &rounds {
+            let mut hosts: Vec<_> = round.iter().map(|x| split_addr(x).unwrap().0).collect();
+            hosts.sort();
+            hosts.dedup();
+            assert_eq!(hosts.len(), round.len());
+        }
+        let mut all: Vec<_> = rounds.into_iter().flatten().collect();
+        all.sort();
+        assert_eq!(all, addrs);
+        assert!(super::rounds(&[]).is_empty());
+    }
+}
@@ -1,4 +1,18 @@
+use haste_core::leader::{serve, LeaderConfig, LEADER_CONFIG_FILE};
 use haste_core::run;
+
+use std::env;
+
 fn main() {
+    env_logger::init();
     run();
+
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| LEADER_CONFIG_FILE.to_string());
+    let config = LeaderConfig::load(&path).expect("fail to load leader config");
+    if let Err(err) = serve(config) {
+        eprintln!("leader exit due {}", err);
+        std::process::exit(1);
+    }
 }
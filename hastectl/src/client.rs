@@ -0,0 +1,97 @@
+//! client of the leader api, the replies are decoded as json values.
+
+use failure::{Error, Fail};
+use reqwest::{Client, RequestBuilder};
+use serde_json::Value;
+
+use std::fmt;
+
+/// the structured error replied by leader.
+#[derive(Debug, Fail)]
+pub struct ApiError {
+    pub status: u16,
+    pub code: String,
+    pub message: String,
+    pub details: Vec<String>,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({} {})", self.message, self.status, self.code)?;
+        for detail in &self.details {
+            write!(f, "\n  - {}", detail)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ApiClient {
+    base: String,
+    client: Client,
+}
+
+impl ApiClient {
+    // http://leader.example.com:7791
+    pub fn new(base: &str) -> ApiClient {
+        ApiClient {
+            base: base.trim_end_matches('/').to_string(),
+            client: Client::new(),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<Value, Error> {
+        self.send(self.client.get(&self.url(path)))
+    }
+
+    pub fn post(&self, path: &str, body: &Value) -> Result<Value, Error> {
+        self.send(self.client.post(&self.url(path)).json(body))
+    }
+
+    pub fn put(&self, path: &str, body: &Value) -> Result<Value, Error> {
+        self.send(self.client.put(&self.url(path)).json(body))
+    }
+
+    pub fn delete(&self, path: &str) -> Result<Value, Error> {
+        self.send(self.client.delete(&self.url(path)))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    fn send(&self, req: RequestBuilder) -> Result<Value, Error> {
+        let mut response = req.send()?;
+        let status = response.status();
+        let text = response.text()?;
+        if status.is_success() {
+            return Ok(serde_json::from_str(&text)?);
+        }
+
+        // the reply may not be json if it's not from leader
+        let error = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|mut x| x.get_mut("error").map(Value::take));
+        let err = match error {
+            Some(error) => ApiError {
+                status: status.as_u16(),
+                code: error["code"].as_str().unwrap_or("").to_string(),
+                message: error["message"].as_str().unwrap_or("").to_string(),
+                details: error["details"]
+                    .as_array()
+                    .map(|x| {
+                        x.iter()
+                            .filter_map(|x| x.as_str().map(|x| x.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            },
+            None => ApiError {
+                status: status.as_u16(),
+                code: "unknown".to_string(),
+                message: text,
+                details: Vec::new(),
+            },
+        };
+        Err(err.into())
+    }
+}
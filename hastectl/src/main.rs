@@ -0,0 +1,619 @@
+use crate::client::ApiClient;
+use crate::output::{print_fields, print_json, print_rows, Columns, Format};
+use crate::spec::load_spec;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use failure::{format_err, Error};
+use serde_json::{json, Map, Value};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+mod client;
+mod output;
+mod spec;
+
+const DEFAULT_LEADER: &str = "http://127.0.0.1:7791";
+const WATCH_INTERVAL: u64 = 2;
+
+const JOB_COLUMNS: Columns = &[
+    ("ID", "id"),
+    ("KIND", "kind"),
+    ("CLUSTER", "cluster"),
+    ("STATE", "state"),
+    ("START", "start"),
+    ("DONE", "done"),
+    ("MSG", "msg"),
+];
+
+const SPEC_COLUMNS: Columns = &[
+    ("NAME", "name"),
+    ("CACHE_TYPE", "cache_type"),
+    ("VERSION", "version"),
+    ("REPLICAS", "replicas"),
+    ("REVISION", "revision"),
+];
+
+fn main() {
+    let matches = app().get_matches();
+    if let Err(err) = run(&matches) {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn app() -> App<'static, 'static> {
+    let file = Arg::with_name("file")
+        .short("f")
+        .long("file")
+        .takes_value(true)
+        .required(true)
+        .help("cluster spec in yaml, toml or json");
+    let wait = Arg::with_name("wait")
+        .long("wait")
+        .help("watch the job until it's finished");
+    let cluster = Arg::with_name("cluster").required(true);
+
+    let action = |name: &'static str, about: &'static str| {
+        SubCommand::with_name(name)
+            .about(about)
+            .arg(cluster.clone())
+            .arg(
+                Arg::with_name("addrs")
+                    .multiple(true)
+                    .help("instances as {ip}:{port}, all the instances if not set"),
+            )
+            .arg(
+                Arg::with_name("host")
+                    .long("host")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .help("only the instances on host"),
+            )
+            .arg(wait.clone())
+    };
+
+    App::new("hastectl")
+        .about("command line client of haste leader")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("leader")
+                .long("leader")
+                .env("HASTE_LEADER")
+                .default_value(DEFAULT_LEADER)
+                .help("address of leader api"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .possible_values(&["table", "json"])
+                .default_value("table"),
+        )
+        .subcommand(
+            SubCommand::with_name("deploy")
+                .about("deploy a cluster by spec")
+                .arg(file.clone())
+                .arg(wait.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("plan")
+                .about("show the deploy plan of spec without deploying")
+                .arg(file.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("apply")
+                .about("save the cluster spec, which is reconciled by leader")
+                .arg(file),
+        )
+        .subcommand(
+            SubCommand::with_name("specs")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("ls").about("list specs"))
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("show the spec and it's drifts")
+                        .arg(cluster.clone()),
+                )
+                .subcommand(
+                    SubCommand::with_name("rm")
+                        .about("stop reconciling the cluster, which is kept as it is")
+                        .arg(cluster.clone()),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("clusters")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("ls").about("list clusters"))
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("show the cluster and it's instances")
+                        .arg(cluster.clone())
+                        .arg(
+                            Arg::with_name("stats")
+                                .long("stats")
+                                .help("scrape the stats of instances"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("reconfig")
+                        .about("change the configs of redis cluster by rolling")
+                        .arg(cluster.clone())
+                        .arg(
+                            Arg::with_name("template")
+                                .long("template")
+                                .takes_value(true)
+                                .help("render by the template, the current one if not set"),
+                        )
+                        .arg(
+                            Arg::with_name("dry_run")
+                                .long("dry-run")
+                                .help("only show the changed directives"),
+                        )
+                        .arg(wait.clone()),
+                )
+                .subcommand(
+                    SubCommand::with_name("upgrade")
+                        .about("upgrade the redis cluster to version by rolling")
+                        .arg(cluster.clone())
+                        .arg(Arg::with_name("version").required(true))
+                        .arg(wait.clone()),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("instances")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(action("restart", "restart instances"))
+                .subcommand(action("start", "start instances"))
+                .subcommand(action("stop", "stop instances"))
+                .subcommand(
+                    SubCommand::with_name("remove")
+                        .about("remove an instance in low ops")
+                        .arg(cluster.clone())
+                        .arg(Arg::with_name("addr").required(true))
+                        .arg(
+                            Arg::with_name("force")
+                                .long("force")
+                                .help("skip the check of low ops"),
+                        )
+                        .arg(wait.clone()),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("jobs")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("ls").about("list jobs").arg(
+                        Arg::with_name("cluster")
+                            .long("cluster")
+                            .takes_value(true)
+                            .help("only the jobs of cluster"),
+                    ),
+                )
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("show the job")
+                        .arg(Arg::with_name("id").required(true)),
+                )
+                .subcommand(
+                    SubCommand::with_name("watch")
+                        .about("watch the job until it's finished")
+                        .arg(Arg::with_name("id").required(true)),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("agents")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("ls").about("list agents")),
+        )
+        .subcommand(
+            SubCommand::with_name("templates")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("ls").about("list templates"))
+                .subcommand(
+                    SubCommand::with_name("push")
+                        .about("push template files, named by their file names")
+                        .arg(
+                            Arg::with_name("cache_type")
+                                .required(true)
+                                .possible_values(&["redis", "redis_cluster", "memcache"]),
+                        )
+                        .arg(Arg::with_name("name").required(true))
+                        .arg(Arg::with_name("files").required(true).multiple(true)),
+                ),
+        )
+}
+
+fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let client = ApiClient::new(matches.value_of("leader").unwrap_or(DEFAULT_LEADER));
+    let format = Format::parse(matches.value_of("output").unwrap_or("table"))?;
+
+    match matches.subcommand() {
+        ("deploy", Some(m)) => {
+            let spec = load_spec(m.value_of("file").unwrap())?;
+            let job = client.post("/deploys", &spec)?;
+            finish_job(&client, format, &job, m.is_present("wait"))
+        }
+        ("plan", Some(m)) => {
+            let spec = load_spec(m.value_of("file").unwrap())?;
+            let plan = client.post("/deploys?dry_run=true", &spec)?;
+            print_plan(format, &plan);
+            Ok(())
+        }
+        ("apply", Some(m)) => {
+            let spec = load_spec(m.value_of("file").unwrap())?;
+            let name = spec["name"]
+                .as_str()
+                .ok_or_else(|| format_err!("spec must have a name"))?;
+            let spec = client.put(&format!("/specs/{}", name), &spec)?;
+            print_rows(format, &spec, SPEC_COLUMNS);
+            Ok(())
+        }
+        ("specs", Some(m)) => specs(&client, format, m),
+        ("clusters", Some(m)) => clusters(&client, format, m),
+        ("instances", Some(m)) => instances(&client, format, m),
+        ("jobs", Some(m)) => jobs(&client, format, m),
+        ("agents", Some(_)) => {
+            let agents = client.get("/agents")?;
+            print_rows(
+                format,
+                &agents,
+                &[
+                    ("HOST", "host"),
+                    ("ADDR", "addr"),
+                    ("CPU", "capacity.cpu"),
+                    ("MEMORY", "capacity.memory"),
+                    ("FREE_CPU", "available.cpu"),
+                    ("FREE_MEMORY", "available.memory"),
+                    ("DATA_ROOT", "capacity.data_root"),
+                ],
+            );
+            Ok(())
+        }
+        ("templates", Some(m)) => templates(&client, format, m),
+        _ => Err(format_err!("unknown command")),
+    }
+}
+
+fn specs(client: &ApiClient, format: Format, matches: &ArgMatches) -> Result<(), Error> {
+    match matches.subcommand() {
+        ("ls", Some(_)) => print_rows(format, &client.get("/specs")?, SPEC_COLUMNS),
+        ("show", Some(m)) => {
+            let name = m.value_of("cluster").unwrap();
+            let detail = client.get(&format!("/specs/{}", name))?;
+            if format == Format::Json {
+                print_json(&detail);
+                return Ok(());
+            }
+
+            print_fields(
+                format,
+                &detail,
+                &[
+                    ("name", "spec.name"),
+                    ("cache_type", "spec.cache_type"),
+                    ("version", "spec.version"),
+                    ("template", "spec.tpl_name"),
+                    ("replicas", "spec.replicas"),
+                    ("max_memory", "spec.max_memory"),
+                    ("appids", "spec.appids"),
+                    ("revision", "spec.revision"),
+                    ("reconciled", "status.revision"),
+                    ("last_job", "status.last_job"),
+                    ("checked_at", "status.checked_at"),
+                ],
+            );
+            let drifts = detail["status"]["drifts"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if !drifts.is_empty() {
+                println!("\ndrifts:");
+                for drift in &drifts {
+                    println!("  - {}", drift.as_str().unwrap_or(""));
+                }
+            }
+        }
+        ("rm", Some(m)) => {
+            let name = m.value_of("cluster").unwrap();
+            client.delete(&format!("/specs/{}", name))?;
+            println!("spec {} is removed", name);
+        }
+        _ => return Err(format_err!("unknown specs command")),
+    }
+    Ok(())
+}
+
+fn clusters(client: &ApiClient, format: Format, matches: &ArgMatches) -> Result<(), Error> {
+    match matches.subcommand() {
+        ("ls", Some(_)) => {
+            let clusters = client.get("/clusters")?;
+            print_rows(
+                format,
+                &clusters,
+                &[
+                    ("NAME", "name"),
+                    ("CACHE_TYPE", "cache_type"),
+                    ("VERSION", "version"),
+                ],
+            );
+        }
+        ("show", Some(m)) => {
+            let name = m.value_of("cluster").unwrap();
+            let path = if m.is_present("stats") {
+                format!("/clusters/{}?stats=true", name)
+            } else {
+                format!("/clusters/{}", name)
+            };
+            let cluster = client.get(&path)?;
+            if format == Format::Json {
+                print_json(&cluster);
+                return Ok(());
+            }
+
+            print_fields(
+                format,
+                &cluster,
+                &[
+                    ("name", "name"),
+                    ("cache_type", "cache_type"),
+                    ("version", "version"),
+                    ("template", "template"),
+                    ("appids", "appids"),
+                ],
+            );
+            println!();
+            print_rows(
+                format,
+                &cluster["instances"],
+                &[
+                    ("ADDR", "addr"),
+                    ("ROLE", "role"),
+                    ("SLAVEOF", "slaveof"),
+                    ("STATE", "state"),
+                    ("SLOTS", "slots"),
+                ],
+            );
+            if let Some(stats) = cluster.get("stats") {
+                println!();
+                print_rows(
+                    format,
+                    &stats["instances"],
+                    &[
+                        ("ADDR", "addr"),
+                        ("ROLE", "info.role"),
+                        ("OPS", "info.ops_per_sec"),
+                        ("MEMORY", "info.used_memory"),
+                        ("CLIENTS", "info.connected_clients"),
+                        ("ERROR", "error"),
+                    ],
+                );
+            }
+        }
+        ("reconfig", Some(m)) => {
+            let name = m.value_of("cluster").unwrap();
+            let body = json!({ "template": m.value_of("template") });
+            if !m.is_present("dry_run") {
+                let job = client.post(&format!("/clusters/{}/config", name), &body)?;
+                return finish_job(client, format, &job, m.is_present("wait"));
+            }
+            let plan = client.post(&format!("/clusters/{}/config?dry_run=true", name), &body)?;
+            print_reconfig(format, &plan);
+        }
+        ("upgrade", Some(m)) => {
+            let path = format!("/clusters/{}/upgrade", m.value_of("cluster").unwrap());
+            let body = json!({ "version": m.value_of("version").unwrap() });
+            let job = client.post(&path, &body)?;
+            return finish_job(client, format, &job, m.is_present("wait"));
+        }
+        _ => return Err(format_err!("unknown clusters command")),
+    }
+    Ok(())
+}
+
+fn instances(client: &ApiClient, format: Format, matches: &ArgMatches) -> Result<(), Error> {
+    let (action, m) = match matches.subcommand() {
+        (action, Some(m)) => (action, m),
+        _ => return Err(format_err!("unknown instances command")),
+    };
+    let cluster = m.value_of("cluster").unwrap();
+
+    let job = if action == "remove" {
+        let addr = m.value_of("addr").unwrap();
+        let force = if m.is_present("force") {
+            "?force=true"
+        } else {
+            ""
+        };
+        client.delete(&format!(
+            "/clusters/{}/instances/{}{}",
+            cluster, addr, force
+        ))?
+    } else {
+        let values =
+            |name| -> Vec<&str> { m.values_of(name).map(|x| x.collect()).unwrap_or_default() };
+        let body = json!({
+            "action": action,
+            "instances": values("addrs"),
+            "hosts": values("host"),
+        });
+        client.post(&format!("/clusters/{}/actions", cluster), &body)?
+    };
+    finish_job(client, format, &job, m.is_present("wait"))
+}
+
+fn jobs(client: &ApiClient, format: Format, matches: &ArgMatches) -> Result<(), Error> {
+    match matches.subcommand() {
+        ("ls", Some(m)) => {
+            let jobs = match m.value_of("cluster") {
+                Some(cluster) => client.get(&format!("/clusters/{}/audit", cluster))?,
+                None => client.get("/jobs")?,
+            };
+            print_rows(format, &jobs, JOB_COLUMNS);
+        }
+        ("show", Some(m)) => {
+            let job = client.get(&format!("/jobs/{}", m.value_of("id").unwrap()))?;
+            print_fields(format, &job, JOB_COLUMNS);
+        }
+        ("watch", Some(m)) => {
+            let job = watch_job(client, m.value_of("id").unwrap())?;
+            return check_job(&job);
+        }
+        _ => return Err(format_err!("unknown jobs command")),
+    }
+    Ok(())
+}
+
+fn templates(client: &ApiClient, format: Format, matches: &ArgMatches) -> Result<(), Error> {
+    match matches.subcommand() {
+        ("ls", Some(_)) => {
+            let templates = client.get("/templates")?;
+            print_rows(
+                format,
+                &templates,
+                &[
+                    ("CACHE_TYPE", "cache_type"),
+                    ("NAME", "name"),
+                    ("FILES", "files"),
+                ],
+            );
+        }
+        ("push", Some(m)) => {
+            let mut files = BTreeMap::new();
+            for path in m.values_of("files").unwrap() {
+                let name = Path::new(path)
+                    .file_name()
+                    .and_then(|x| x.to_str())
+                    .ok_or_else(|| format_err!("bad template file {}", path))?;
+                files.insert(name.to_string(), fs::read_to_string(path)?);
+            }
+            let path = format!(
+                "/templates/{}/{}",
+                m.value_of("cache_type").unwrap(),
+                m.value_of("name").unwrap()
+            );
+            let template = client.put(&path, &json!({ "files": files }))?;
+            print_rows(
+                format,
+                &template,
+                &[
+                    ("CACHE_TYPE", "cache_type"),
+                    ("NAME", "name"),
+                    ("FILES", "files"),
+                ],
+            );
+        }
+        _ => return Err(format_err!("unknown templates command")),
+    }
+    Ok(())
+}
+
+// the instances of each host in plan
+fn print_plan(format: Format, plan: &Value) {
+    if format == Format::Json {
+        print_json(plan);
+        return;
+    }
+
+    let rows: Vec<Value> = plan["hosts"]
+        .as_array()
+        .map(|x| x.as_slice())
+        .unwrap_or(&[])
+        .iter()
+        .flat_map(|host| {
+            let insts = host["insts"].as_array().cloned().unwrap_or_default();
+            insts.into_iter().map(move |inst| {
+                let mut row = Map::new();
+                row.insert("host".to_string(), host["host"].clone());
+                row.insert("port".to_string(), inst["port"].clone());
+                let files: Vec<_> = inst["files"]
+                    .as_array()
+                    .map(|x| x.iter().map(|f| f["fpath"].clone()).collect())
+                    .unwrap_or_default();
+                row.insert("files".to_string(), Value::Array(files));
+                Value::Object(row)
+            })
+        })
+        .collect();
+    println!("job {}", output::field(plan, "job_id"));
+    print_rows(
+        format,
+        &Value::Array(rows),
+        &[("HOST", "host"), ("PORT", "port"), ("FILES", "files")],
+    );
+}
+
+// the changed directives of each instance
+fn print_reconfig(format: Format, plan: &Value) {
+    if format == Format::Json {
+        print_json(plan);
+        return;
+    }
+
+    let mut rows = Vec::new();
+    for inst in plan.as_array().map(|x| x.as_slice()).unwrap_or(&[]) {
+        for change in inst["changes"]
+            .as_array()
+            .map(|x| x.as_slice())
+            .unwrap_or(&[])
+        {
+            let apply = if change["hot"] == true {
+                "config set"
+            } else {
+                "restart"
+            };
+            rows.push(vec![
+                output::field(inst, "addr"),
+                output::field(change, "name"),
+                output::field(change, "current"),
+                output::field(change, "desired"),
+                apply.to_string(),
+            ]);
+        }
+    }
+    output::print_table(&["ADDR", "NAME", "CURRENT", "DESIRED", "APPLY"], &rows);
+}
+
+fn finish_job(client: &ApiClient, format: Format, job: &Value, wait: bool) -> Result<(), Error> {
+    if !wait {
+        print_fields(format, job, JOB_COLUMNS);
+        return Ok(());
+    }
+    let id = job["id"]
+        .as_str()
+        .ok_or_else(|| format_err!("bad job {}", job))?;
+    let job = watch_job(client, id)?;
+    check_job(&job)
+}
+
+// poll the job and print it's state when changed, until it's not running.
+fn watch_job(client: &ApiClient, id: &str) -> Result<Value, Error> {
+    let mut last = String::new();
+    loop {
+        let job = client.get(&format!("/jobs/{}", id))?;
+        let state = output::field(&job, "state");
+        if state != last {
+            println!("job {} is {}", id, state);
+            last = state.clone();
+        }
+        if state != "running" {
+            return Ok(job);
+        }
+        thread::sleep(Duration::from_secs(WATCH_INTERVAL));
+    }
+}
+
+fn check_job(job: &Value) -> Result<(), Error> {
+    if job["state"] == "error" {
+        return Err(format_err!(
+            "job {} failed due {}",
+            output::field(job, "id"),
+            output::field(job, "msg")
+        ));
+    }
+    Ok(())
+}
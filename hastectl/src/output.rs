@@ -0,0 +1,148 @@
+//! table and json output of the replies.
+//!
+//! columns are picked from json values by dotted paths like `capacity.cpu`, the arrays are
+//! joined by ',' and the missing values are shown as '-'.
+
+use failure::{format_err, Error};
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, Error> {
+        match name {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format_err!("bad output format {}", name)),
+        }
+    }
+}
+
+// (header, path)
+pub type Columns<'a> = &'a [(&'a str, &'a str)];
+
+/// print an array of values as rows, or a single value as one row.
+pub fn print_rows(format: Format, value: &Value, columns: Columns) {
+    if format == Format::Json {
+        print_json(value);
+        return;
+    }
+
+    let items = match value.as_array() {
+        Some(items) => items.iter().collect(),
+        None => vec![value],
+    };
+    let rows: Vec<Vec<String>> = items
+        .into_iter()
+        .map(|item| columns.iter().map(|(_, path)| field(item, path)).collect())
+        .collect();
+    let headers: Vec<_> = columns.iter().map(|(header, _)| *header).collect();
+    print_table(&headers, &rows);
+}
+
+/// print the fields of a value as `{name}: {value}` lines.
+pub fn print_fields(format: Format, value: &Value, columns: Columns) {
+    if format == Format::Json {
+        print_json(value);
+        return;
+    }
+
+    let width = columns
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (name, path) in columns {
+        println!("{:width$}  {}", name, field(value, path), width = width);
+    }
+}
+
+pub fn print_json(value: &Value) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{}", text),
+        Err(err) => eprintln!("fail to encode json due {}", err),
+    }
+}
+
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<_> = headers.iter().map(|x| x.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let cells: Vec<_> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    };
+    line(headers.to_vec());
+    for row in rows {
+        line(row.iter().map(|x| x.as_str()).collect());
+    }
+}
+
+/// the value of dotted path as text.
+pub fn field(value: &Value, path: &str) -> String {
+    let mut value = value;
+    for key in path.split('.') {
+        value = match value.get(key) {
+            Some(value) => value,
+            None => return "-".to_string(),
+        };
+    }
+    as_text(value)
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) if text.is_empty() => "-".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => {
+            let items: Vec<_> = items.iter().map(as_text).collect();
+            items.join(",")
+        }
+        Value::Object(_) => value.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn field_by_dotted_path() {
+        let agent = json!({
+            "host": "10.0.0.1",
+            "capacity": {"cpu": 800, "memory": 65536, "ports": [7000, 7001]},
+            "available": null,
+            "addr": "",
+        });
+        assert_eq!(field(&agent, "host"), "10.0.0.1");
+        assert_eq!(field(&agent, "capacity.cpu"), "800");
+        assert_eq!(field(&agent, "capacity.ports"), "7000,7001");
+        assert_eq!(field(&agent, "capacity"), agent["capacity"].to_string());
+        assert_eq!(field(&agent, "available"), "-");
+        assert_eq!(field(&agent, "available.cpu"), "-");
+        assert_eq!(field(&agent, "capacity.disk"), "-");
+        assert_eq!(field(&agent, "addr"), "-");
+    }
+
+    #[test]
+    fn join_arrays() {
+        let cluster = json!({"appids": ["account.session", "", null], "ports": []});
+        assert_eq!(field(&cluster, "appids"), "account.session,-,-");
+        assert_eq!(field(&cluster, "ports"), "");
+    }
+}
@@ -0,0 +1,132 @@
+//! cluster spec files in yaml, toml or json. `deploy` and `plan` take the `DeployParm` of
+//! leader like:
+//!
+//! ```yaml
+//! name: session
+//! cache_type: redis_cluster
+//! version: 4.0.11
+//! tpl_name: default
+//! cpu_percent: 100
+//! max_memory: 4096
+//! total_memory: 65536
+//! appids: "account.session"
+//! group: default
+//! dial_timeout: 1000
+//! ```
+//!
+//! and `apply` takes the `ClusterSpec` which is reconciled by leader like:
+//!
+//! ```yaml
+//! name: session
+//! cache_type: redis_cluster
+//! version: 4.0.11
+//! tpl_name: default
+//! max_memory: 4096
+//! replicas: 16
+//! appids: ["account.session"]
+//! dial_timeout: 1000
+//! # the revision which was read, the apply fails if someone else changed it since then
+//! revision: 3
+//! ```
+//!
+//! the format is picked by the extension of file.
+
+use failure::{format_err, Error};
+use serde_json::Value;
+
+use std::fs;
+use std::path::Path;
+
+pub fn load_spec<P: AsRef<Path>>(path: P) -> Result<Value, Error> {
+    let path = path.as_ref();
+    let data = fs::read_to_string(path)
+        .map_err(|err| format_err!("fail to read spec {:?} due {}", path, err))?;
+    let ext = path.extension().and_then(|x| x.to_str()).unwrap_or("");
+    let spec = match ext {
+        "yaml" | "yml" => serde_yaml::from_str(&data)?,
+        "toml" => serde_json::to_value(toml::from_str::<toml::Value>(&data)?)?,
+        "json" => serde_json::from_str(&data)?,
+        _ => {
+            return Err(format_err!(
+                "unknown spec format of {:?}, must be yaml, toml or json",
+                path
+            ))
+        }
+    };
+
+    match spec {
+        Value::Object(_) => Ok(spec),
+        _ => Err(format_err!("spec {:?} must be a map", path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    fn write_spec(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("hastectl-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn check_spec(name: &str, content: &str) {
+        let path = write_spec(name, content);
+        let spec = load_spec(&path);
+        fs::remove_file(&path).unwrap();
+        let spec = spec.unwrap();
+        assert_eq!(spec["name"], "session");
+        assert_eq!(spec["cache_type"], "redis_cluster");
+        assert_eq!(spec["version"], "4.0.11");
+        assert_eq!(spec["max_memory"], 4096);
+        assert_eq!(spec["appids"][0], "account.session");
+    }
+
+    #[test]
+    fn load_every_format() {
+        let yaml = r#"
+name: session
+cache_type: redis_cluster
+version: 4.0.11
+max_memory: 4096
+appids: ["account.session"]
+"#;
+        check_spec("spec.yaml", yaml);
+        check_spec("spec.yml", yaml);
+
+        let toml = r#"
+name = "session"
+cache_type = "redis_cluster"
+version = "4.0.11"
+max_memory = 4096
+appids = ["account.session"]
+"#;
+        check_spec("spec.toml", toml);
+
+        let json = r#"{
+  "name": "session",
+  "cache_type": "redis_cluster",
+  "version": "4.0.11",
+  "max_memory": 4096,
+  "appids": ["account.session"]
+}"#;
+        check_spec("spec.json", json);
+    }
+
+    #[test]
+    fn reject_bad_specs() {
+        for (name, content) in &[
+            ("bad.txt", "name: session"),
+            ("list.yaml", "- session"),
+            ("broken.json", "{\"name\": "),
+        ] {
+            let path = write_spec(name, content);
+            let spec = load_spec(&path);
+            fs::remove_file(&path).unwrap();
+            assert!(spec.is_err(), "{} is loaded", name);
+        }
+        assert!(load_spec("/nonexistent/spec.yaml").is_err());
+    }
+}